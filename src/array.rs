@@ -0,0 +1,130 @@
+//! Generate noise directly into `ndarray` arrays, enabled with the `ndarray` feature.
+//!
+//! Arrays are indexed `[y, x]`, `[z, y, x]` and `[w, z, y, x]`, so a standard layout
+//! array has the same memory order as the `Vec` returned by `generate`. Any other
+//! layout, such as Fortran order or a strided slice of a bigger array, is written in
+//! place by the block helpers without an intermediate `Vec`.
+//!
+//! ```rust
+//! use ndarray::{Array3, ShapeBuilder};
+//! use simdnoise::*;
+//!
+//! let (noise, min, max) = NoiseBuilder::fbm_2d(64, 32).generate_array2();
+//! assert_eq!(noise.dim(), (32, 64));
+//!
+//! let mut fortran = Array3::<f32>::zeros((8, 16, 16).f());
+//! NoiseBuilder::ridge_3d(16, 16, 8).generate_into(fortran.view_mut());
+//! ```
+
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+use crate::noise_helpers_32::BlockOut;
+use crate::noise_type::NoiseType;
+use crate::settings::Settings;
+
+use ndarray::{Array1, Array2, Array3, Array4, ArrayViewMut, Dimension};
+
+pub trait ArraySettings: Settings {
+    /// Generate noise into `view`, whose shape must be the noise dimensions with x
+    /// last, returning the min and max. Panics if the shape does not match.
+    fn generate_into<D: Dimension>(self, view: ArrayViewMut<f32, D>) -> (f32, f32);
+
+    /// Generate 1d noise into a new array, along with the min and max
+    fn generate_array1(self) -> (Array1<f32>, f32, f32);
+
+    /// Generate 2d noise into a new `[y, x]` array, along with the min and max
+    fn generate_array2(self) -> (Array2<f32>, f32, f32);
+
+    /// Generate 3d noise into a new `[z, y, x]` array, along with the min and max
+    fn generate_array3(self) -> (Array3<f32>, f32, f32);
+
+    /// Generate 4d noise into a new `[w, z, y, x]` array, along with the min and max
+    fn generate_array4(self) -> (Array4<f32>, f32, f32);
+}
+
+impl<T: Settings> ArraySettings for T {
+    fn generate_into<D: Dimension>(self, view: ArrayViewMut<f32, D>) -> (f32, f32) {
+        fill(&self.wrap(), view)
+    }
+
+    fn generate_array1(self) -> (Array1<f32>, f32, f32) {
+        let noise_type = checked_wrap(self, 1);
+        let dim = noise_type.get_dimensions();
+        let mut array = Array1::zeros(dim.width);
+        let (min, max) = fill(&noise_type, array.view_mut());
+        (array, min, max)
+    }
+
+    fn generate_array2(self) -> (Array2<f32>, f32, f32) {
+        let noise_type = checked_wrap(self, 2);
+        let dim = noise_type.get_dimensions();
+        let mut array = Array2::zeros((dim.height, dim.width));
+        let (min, max) = fill(&noise_type, array.view_mut());
+        (array, min, max)
+    }
+
+    fn generate_array3(self) -> (Array3<f32>, f32, f32) {
+        let noise_type = checked_wrap(self, 3);
+        let dim = noise_type.get_dimensions();
+        let mut array = Array3::zeros((dim.depth, dim.height, dim.width));
+        let (min, max) = fill(&noise_type, array.view_mut());
+        (array, min, max)
+    }
+
+    fn generate_array4(self) -> (Array4<f32>, f32, f32) {
+        let noise_type = checked_wrap(self, 4);
+        let dim = noise_type.get_dimensions();
+        let mut array = Array4::zeros((dim.time, dim.depth, dim.height, dim.width));
+        let (min, max) = fill(&noise_type, array.view_mut());
+        (array, min, max)
+    }
+}
+
+fn checked_wrap<T: Settings>(settings: T, d: usize) -> NoiseType {
+    let noise_type = settings.wrap();
+    if noise_type.get_dimensions().dim != d {
+        panic!("settings are not {}d", d);
+    }
+    noise_type
+}
+
+fn fill<D: Dimension>(noise_type: &NoiseType, mut view: ArrayViewMut<f32, D>) -> (f32, f32) {
+    let dim = noise_type.get_dimensions();
+    let extents = [dim.width, dim.height, dim.depth, dim.time];
+    if !view.shape().iter().rev().eq(extents[..dim.dim].iter()) {
+        panic!("array shape does not match the noise dimensions");
+    }
+    // Array axes run from the slowest noise axis to x
+    let mut strides = [0; 4];
+    for (axis, stride) in view.strides().iter().rev().enumerate() {
+        strides[axis] = *stride;
+    }
+    let out = BlockOut {
+        ptr: view.as_mut_ptr(),
+        strides,
+    };
+    get_noise_into!(noise_type, &out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use ndarray::{s, Array3, ShapeBuilder};
+
+    #[test]
+    fn layouts_match_flat_output() {
+        let (flat, _, _) = NoiseBuilder::gradient_3d(13, 6, 5).generate();
+        let (standard, _, _) = NoiseBuilder::gradient_3d(13, 6, 5).generate_array3();
+        assert_eq!(standard.as_slice().unwrap(), &flat[..]);
+
+        let mut fortran = Array3::zeros((5, 6, 13).f());
+        NoiseBuilder::gradient_3d(13, 6, 5).generate_into(fortran.view_mut());
+        assert_eq!(fortran, standard);
+
+        // A reversed, strided window into a bigger array
+        let mut big = Array3::zeros((10, 6, 13));
+        NoiseBuilder::gradient_3d(13, 6, 5).generate_into(big.slice_mut(s![..;-2, .., ..]));
+        assert_eq!(big.slice(s![..;-2, .., ..]), standard);
+    }
+}
@@ -0,0 +1,409 @@
+//! Blue noise sample sets and threshold masks
+//!
+//! Blue noise has no low frequency content: samples are spread evenly without forming the
+//! regular patterns of a grid. Two flavours are provided:
+//!
+//! * [`BlueNoiseMask`], a tileable threshold mask built with the void-and-cluster method, for
+//!   ordered dithering and stippling of textures.
+//! * [`BlueNoisePoints`], jittered points per grid cell with a minimum distance between them
+//!   (Poisson-disk like), for scattering objects such as foliage over unbounded worlds.
+//!
+//! Both are derived from hashes of the integer pixel and cell coordinates, so the output only
+//! depends on the seed. The mask wraps around at its edges and every point is decided from its
+//! own neighbourhood of cells, so chunks generated separately line up exactly.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::float::Float;
+use crate::seed::mix64;
+
+/// Standard deviation of the gaussian energy filter used by the void-and-cluster method
+const SIGMA: f32 = 1.5;
+/// Fraction of the mask that is filled by the initial random pattern
+const INITIAL_DENSITY: usize = 10;
+
+/// Hashes the integer cell coordinates, so cells far from the origin stay distinct
+#[inline(always)]
+fn hash_u32(x: i64, y: i64, seed: i32) -> u32 {
+    (mix64(x as u64 ^ mix64(y as u64 ^ mix64(seed as u32 as u64))) >> 32) as u32
+}
+
+/// Maps a hash to a value 0 ≤ n < 1
+#[inline(always)]
+fn hash_to_unit(hash: u32) -> f32 {
+    (hash >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// Gaussian weighted density of the pixels of one colour, on a torus
+struct EnergyField {
+    size: usize,
+    radius: i64,
+    kernel: Vec<f32>,
+    energy: Vec<f32>,
+}
+
+impl EnergyField {
+    fn new(size: usize) -> EnergyField {
+        // Contributions beyond 4 sigma are negligible
+        let radius = ((4.0 * SIGMA).ceil() as i64).min(size as i64 / 2);
+        let span = (2 * radius + 1) as usize;
+        let mut kernel = Vec::with_capacity(span * span);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let d2 = (dx * dx + dy * dy) as f32;
+                kernel.push(Float::exp(-d2 / (2.0 * SIGMA * SIGMA)));
+            }
+        }
+        EnergyField {
+            size,
+            radius,
+            kernel,
+            energy: vec![0.0; size * size],
+        }
+    }
+
+    fn splat(&mut self, index: usize, sign: f32) {
+        let size = self.size as i64;
+        let x = (index % self.size) as i64;
+        let y = (index / self.size) as i64;
+        let mut k = 0;
+        for dy in -self.radius..=self.radius {
+            let row = (y + dy).rem_euclid(size) * size;
+            for dx in -self.radius..=self.radius {
+                let i = (row + (x + dx).rem_euclid(size)) as usize;
+                self.energy[i] += sign * self.kernel[k];
+                k += 1;
+            }
+        }
+    }
+
+    fn rebuild(&mut self, pattern: &[bool], colour: bool) {
+        self.energy.iter_mut().for_each(|e| *e = 0.0);
+        for i in 0..pattern.len() {
+            if pattern[i] == colour {
+                self.splat(i, 1.0);
+            }
+        }
+    }
+
+    /// The pixel of `colour` with the highest energy, ties broken by hash
+    fn tightest_cluster(&self, pattern: &[bool], colour: bool, hashes: &[u32]) -> usize {
+        let mut best = usize::MAX;
+        for i in 0..pattern.len() {
+            if pattern[i] != colour {
+                continue;
+            }
+            if best == usize::MAX
+                || self.energy[i] > self.energy[best]
+                || (self.energy[i] == self.energy[best] && hashes[i] < hashes[best])
+            {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// The pixel not of `colour` with the lowest energy, ties broken by hash
+    fn largest_void(&self, pattern: &[bool], colour: bool, hashes: &[u32]) -> usize {
+        let mut best = usize::MAX;
+        for i in 0..pattern.len() {
+            if pattern[i] == colour {
+                continue;
+            }
+            if best == usize::MAX
+                || self.energy[i] < self.energy[best]
+                || (self.energy[i] == self.energy[best] && hashes[i] < hashes[best])
+            {
+                best = i;
+            }
+        }
+        best
+    }
+}
+
+/// A square, tileable blue noise threshold mask
+///
+/// Every pixel of the mask holds a distinct threshold 0 < t < 1. Thresholding the mask at
+/// any level gives an evenly spread pattern with that density, which makes it well suited to
+/// ordered dithering.
+#[derive(Clone)]
+pub struct BlueNoiseMask {
+    size: usize,
+    thresholds: Vec<f32>,
+}
+
+impl BlueNoiseMask {
+    /// Builds a `size` x `size` mask with the void-and-cluster method
+    ///
+    /// Generation is quadratic in the number of pixels, sizes up to 128 are practical.
+    pub fn new(size: usize, seed: i32) -> BlueNoiseMask {
+        if size == 0 {
+            panic!("mask size must be at least 1");
+        }
+        let n = size * size;
+        let hashes: Vec<u32> = (0..n)
+            .map(|i| hash_u32((i % size) as i64, (i / size) as i64, seed))
+            .collect();
+
+        // Initial binary pattern: the pixels with the lowest hashes
+        let initial = (n / INITIAL_DENSITY).max(1);
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| hashes[i]);
+        let mut prototype = vec![false; n];
+        for &i in &order[..initial] {
+            prototype[i] = true;
+        }
+
+        // Move points from the tightest cluster into the largest void until it settles
+        let mut field = EnergyField::new(size);
+        field.rebuild(&prototype, true);
+        for _ in 0..n {
+            let cluster = field.tightest_cluster(&prototype, true, &hashes);
+            prototype[cluster] = false;
+            field.splat(cluster, -1.0);
+            let void = field.largest_void(&prototype, true, &hashes);
+            prototype[void] = true;
+            field.splat(void, 1.0);
+            if void == cluster {
+                break;
+            }
+        }
+
+        let mut ranks = vec![0usize; n];
+
+        // Phase 1: rank the initial points by removing the tightest clusters
+        let mut pattern = prototype.clone();
+        for rank in (0..initial).rev() {
+            let cluster = field.tightest_cluster(&pattern, true, &hashes);
+            pattern[cluster] = false;
+            field.splat(cluster, -1.0);
+            ranks[cluster] = rank;
+        }
+
+        // Phase 2: fill the largest voids up to half of the mask
+        let mut pattern = prototype;
+        field.rebuild(&pattern, true);
+        let mut rank = initial;
+        while rank < n / 2 {
+            let void = field.largest_void(&pattern, true, &hashes);
+            pattern[void] = true;
+            field.splat(void, 1.0);
+            ranks[void] = rank;
+            rank += 1;
+        }
+
+        // Phase 3: the empty pixels are now the minority, fill their tightest clusters
+        field.rebuild(&pattern, false);
+        while rank < n {
+            let cluster = field.tightest_cluster(&pattern, false, &hashes);
+            pattern[cluster] = true;
+            field.splat(cluster, -1.0);
+            ranks[cluster] = rank;
+            rank += 1;
+        }
+
+        let thresholds = ranks.iter().map(|&r| (r as f32 + 0.5) / n as f32).collect();
+        BlueNoiseMask { size, thresholds }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The thresholds in row major order
+    pub fn data(&self) -> &[f32] {
+        &self.thresholds
+    }
+
+    /// The threshold at `[x, y]`, the mask repeats in both directions
+    pub fn threshold(&self, x: i64, y: i64) -> f32 {
+        let size = self.size as i64;
+        let i = y.rem_euclid(size) * size + x.rem_euclid(size);
+        self.thresholds[i as usize]
+    }
+
+    /// Dithers a value 0 ≤ v ≤ 1 at `[x, y]` to on or off
+    pub fn dither(&self, value: f32, x: i64, y: i64) -> bool {
+        value > self.threshold(x, y)
+    }
+}
+
+/// Blue noise points with at most one point per grid cell
+///
+/// Each cell gets a candidate point, jittered away from the cell centre, and a random
+/// priority. A candidate is kept unless a candidate with a higher priority lies within
+/// `min_distance` of it. That decision only looks at the neighbouring cells, so any region
+/// can be generated on its own and agrees with its neighbours.
+#[derive(Copy, Clone)]
+pub struct BlueNoisePoints {
+    pub cell_size: f32,
+    pub min_distance: f32,
+    pub jitter: f32,
+    pub seed: i32,
+}
+
+impl BlueNoisePoints {
+    pub fn new(cell_size: f32) -> BlueNoisePoints {
+        BlueNoisePoints {
+            cell_size,
+            min_distance: cell_size * 0.75,
+            jitter: 1.0,
+            seed: 1,
+        }
+    }
+
+    pub fn with_seed(&mut self, seed: i32) -> &mut BlueNoisePoints {
+        self.seed = seed;
+        self
+    }
+
+    /// Minimum distance between two points, in the same units as the cell size
+    pub fn with_min_distance(&mut self, min_distance: f32) -> &mut BlueNoisePoints {
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// How far the candidates may move from the cell centre, 0 gives a grid, 1 uses the
+    /// whole cell. It is clamped to that range, so points stay in their cell.
+    pub fn with_jitter(&mut self, jitter: f32) -> &mut BlueNoisePoints {
+        self.jitter = jitter;
+        self
+    }
+
+    fn candidate(&self, cx: i64, cy: i64) -> ([f32; 2], u32) {
+        // The neighbourhoods searched assume every point lies in its own cell
+        let jitter = self.jitter.max(0.0).min(1.0);
+        let jx = hash_to_unit(hash_u32(cx, cy, self.seed)) - 0.5;
+        let jy = hash_to_unit(hash_u32(cx, cy, self.seed ^ 0x5bd1e995)) - 0.5;
+        let priority = hash_u32(cx, cy, self.seed ^ 0x27d4eb2f);
+        let point = [
+            (cx as f32 + 0.5 + jx * jitter) * self.cell_size,
+            (cy as f32 + 0.5 + jy * jitter) * self.cell_size,
+        ];
+        (point, priority)
+    }
+
+    /// The point in cell `[cx, cy]`, if it survived
+    pub fn cell_point(&self, cx: i64, cy: i64) -> Option<[f32; 2]> {
+        let (point, priority) = self.candidate(cx, cy);
+        let reach = (self.min_distance / self.cell_size).ceil() as i64 + 1;
+        let min_d2 = self.min_distance * self.min_distance;
+        for ny in cy - reach..=cy + reach {
+            for nx in cx - reach..=cx + reach {
+                if nx == cx && ny == cy {
+                    continue;
+                }
+                let (other, other_priority) = self.candidate(nx, ny);
+                let dx = other[0] - point[0];
+                let dy = other[1] - point[1];
+                if dx * dx + dy * dy >= min_d2 {
+                    continue;
+                }
+                if (other_priority, nx, ny) > (priority, cx, cy) {
+                    return None;
+                }
+            }
+        }
+        Some(point)
+    }
+
+    /// All points with `x_min ≤ x < x_max` and `y_min ≤ y < y_max`, in row major cell order
+    pub fn points_in_region(
+        &self,
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<[f32; 2]> {
+        let mut points = Vec::new();
+        // Jitter can push a point into the neighbouring cell
+        let cx_min = (x_min / self.cell_size).floor() as i64 - 1;
+        let cy_min = (y_min / self.cell_size).floor() as i64 - 1;
+        let cx_max = (x_max / self.cell_size).floor() as i64 + 1;
+        let cy_max = (y_max / self.cell_size).floor() as i64 + 1;
+        for cy in cy_min..=cy_max {
+            for cx in cx_min..=cx_max {
+                if let Some(p) = self.cell_point(cx, cy) {
+                    if p[0] >= x_min && p[0] < x_max && p[1] >= y_min && p[1] < y_max {
+                        points.push(p);
+                    }
+                }
+            }
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_thresholds_are_distinct() {
+        let mask = BlueNoiseMask::new(16, 3);
+        let mut ranks: Vec<usize> = mask.data().iter().map(|t| (t * 256.0) as usize).collect();
+        ranks.sort();
+        assert_eq!(ranks, (0..256).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn mask_is_reproducible_and_wraps() {
+        let a = BlueNoiseMask::new(8, 11);
+        let b = BlueNoiseMask::new(8, 11);
+        assert_eq!(a.data(), b.data());
+        assert_eq!(a.threshold(-1, 3), a.threshold(7, 11));
+        assert_ne!(a.data(), BlueNoiseMask::new(8, 12).data());
+    }
+
+    #[test]
+    fn far_cells_are_distinct() {
+        let x = 1 << 40;
+        assert_ne!(hash_u32(x, 0, 3), hash_u32(x + 1, 0, 3));
+        assert_ne!(hash_u32(0, x, 3), hash_u32(0, x + 1, 3));
+    }
+
+    #[test]
+    fn jitter_is_clamped() {
+        let mut settings = BlueNoisePoints::new(2.0);
+        settings
+            .with_seed(4)
+            .with_min_distance(0.0)
+            .with_jitter(5.0);
+        for cy in -3..3 {
+            for cx in -3..3 {
+                let p = settings.cell_point(cx, cy).unwrap();
+                assert!(p[0] >= cx as f32 * 2.0 && p[0] <= (cx + 1) as f32 * 2.0);
+                assert!(p[1] >= cy as f32 * 2.0 && p[1] <= (cy + 1) as f32 * 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn points_respect_min_distance() {
+        let points = BlueNoisePoints::new(4.0)
+            .with_seed(5)
+            .points_in_region(0.0, 0.0, 100.0, 100.0);
+        assert!(points.len() > 100);
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let dx = points[i][0] - points[j][0];
+                let dy = points[i][1] - points[j][1];
+                assert!(dx * dx + dy * dy >= 9.0);
+            }
+        }
+    }
+
+    #[test]
+    fn points_are_tile_stable() {
+        let mut settings = BlueNoisePoints::new(3.0);
+        settings.with_seed(-9).with_min_distance(2.5);
+        let mut whole = settings.points_in_region(-20.0, -20.0, 40.0, 20.0);
+        let mut tiles = settings.points_in_region(-20.0, -20.0, 10.0, 20.0);
+        tiles.extend(settings.points_in_region(10.0, -20.0, 40.0, 20.0));
+        let key = |p: &[f32; 2]| (p[0].to_bits(), p[1].to_bits());
+        whole.sort_by_key(key);
+        tiles.sort_by_key(key);
+        assert_eq!(whole, tiles);
+    }
+}
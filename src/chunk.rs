@@ -0,0 +1,75 @@
+//! Exact sample coordinates for chunked generation.
+//!
+//! A chunk's samples sit at integer positions `chunk * size + index` on each axis,
+//! multiplied by the step.
+//! Those positions are formed in integer arithmetic and scaled by the frequency in
+//! double precision, so a sample's coordinate depends only on its global position.
+//! Neighbouring chunks therefore produce bit-identical values where they share a
+//! border, however far from the origin they are.
+
+use alloc::vec::Vec;
+
+use crate::noise_dimensions::NoiseDimensions;
+
+/// Frequency scaled coordinates along each axis of a chunk
+pub(crate) struct ChunkAxes<T> {
+    pub axes: [Vec<T>; 4],
+}
+
+impl NoiseDimensions {
+    /// Place the block at integer chunk coordinates, one per dimension, with `size`
+    /// samples between chunk origins. Panics if the number of coordinates does not
+    /// match the dimension or if `size` is 0.
+    pub(crate) fn set_chunk(&mut self, chunk: &[i64], size: usize) {
+        if chunk.len() != self.dim {
+            panic!("chunk coordinates must match the noise dimension");
+        }
+        if size == 0 {
+            panic!("chunk size must be greater than 0");
+        }
+        let mut origin = [0; 4];
+        for (o, c) in origin.iter_mut().zip(chunk) {
+            *o = c
+                .checked_mul(size as i64)
+                .expect("chunk origin overflows an i64");
+        }
+        self.chunk_origin = Some(origin);
+    }
+}
+
+// Axis i of the block has `extents[i]` samples; x is padded to whole vectors so the
+// remainder can be loaded like any other vector
+fn axis_coordinates(
+    dim: &NoiseDimensions,
+    freq: &[f32],
+    vector_width: usize,
+) -> Option<[Vec<f64>; 4]> {
+    let origin = dim.chunk_origin?;
+    let padded_width = (dim.width + vector_width - 1) / vector_width * vector_width;
+    let extents = [padded_width, dim.height, dim.depth, dim.time];
+    let steps = [dim.step_x, dim.step_y, dim.step_z, dim.step_w];
+    let mut axes = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    for (axis, f) in freq.iter().enumerate() {
+        let scale = steps[axis] as f64 * *f as f64;
+        axes[axis] = (0..extents[axis])
+            .map(|i| (origin[axis] + i as i64) as f64 * scale)
+            .collect();
+    }
+    Some(axes)
+}
+
+impl ChunkAxes<f32> {
+    pub(crate) fn new(dim: &NoiseDimensions, freq: &[f32], vector_width: usize) -> Option<Self> {
+        let [x, y, z, w] = axis_coordinates(dim, freq, vector_width)?;
+        let narrow = |v: Vec<f64>| v.into_iter().map(|c| c as f32).collect();
+        Some(ChunkAxes {
+            axes: [narrow(x), narrow(y), narrow(z), narrow(w)],
+        })
+    }
+}
+
+impl ChunkAxes<f64> {
+    pub(crate) fn new(dim: &NoiseDimensions, freq: &[f32], vector_width: usize) -> Option<Self> {
+        axis_coordinates(dim, freq, vector_width).map(|axes| ChunkAxes { axes })
+    }
+}
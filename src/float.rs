@@ -0,0 +1,157 @@
+//! The float functions std provides as inherent methods, implemented with `libm` for
+//! builds without std. Modules that only call them as methods import `Float` when the
+//! `std` feature is disabled, so std builds keep using the inherent methods.
+//!
+//! The transcendental functions of std call the platform's math library, which may round
+//! differently. Values that end up in the noise, like the wavelet tile and rotations, call
+//! `Float` explicitly in every build, so they are the same on every platform. The other
+//! functions are correctly rounded either way, and only exist without std.
+
+pub(crate) trait Float: Sized {
+    #[cfg(not(feature = "std"))]
+    fn abs(self) -> Self;
+    #[cfg(not(feature = "std"))]
+    fn sqrt(self) -> Self;
+    #[cfg(not(feature = "std"))]
+    fn floor(self) -> Self;
+    #[cfg(not(feature = "std"))]
+    fn ceil(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    #[cfg(not(feature = "std"))]
+    fn powf(self, n: Self) -> Self;
+    #[cfg(not(feature = "std"))]
+    fn powi(self, n: i32) -> Self;
+    #[cfg(not(feature = "std"))]
+    fn copysign(self, sign: Self) -> Self;
+}
+
+impl Float for f32 {
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn abs(self) -> f32 {
+        libm::fabsf(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn sqrt(self) -> f32 {
+        libm::sqrtf(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn floor(self) -> f32 {
+        libm::floorf(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn ceil(self) -> f32 {
+        libm::ceilf(self)
+    }
+
+    #[inline(always)]
+    fn exp(self) -> f32 {
+        libm::expf(self)
+    }
+
+    #[inline(always)]
+    fn ln(self) -> f32 {
+        libm::logf(self)
+    }
+
+    #[inline(always)]
+    fn cos(self) -> f32 {
+        libm::cosf(self)
+    }
+
+    #[inline(always)]
+    fn sin_cos(self) -> (f32, f32) {
+        libm::sincosf(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn powf(self, n: f32) -> f32 {
+        libm::powf(self, n)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn powi(self, n: i32) -> f32 {
+        libm::powf(self, n as f32)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn copysign(self, sign: f32) -> f32 {
+        libm::copysignf(self, sign)
+    }
+}
+
+impl Float for f64 {
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn abs(self) -> f64 {
+        libm::fabs(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn ceil(self) -> f64 {
+        libm::ceil(self)
+    }
+
+    #[inline(always)]
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    #[inline(always)]
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    #[inline(always)]
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    #[inline(always)]
+    fn sin_cos(self) -> (f64, f64) {
+        libm::sincos(self)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[inline(always)]
+    fn copysign(self, sign: f64) -> f64 {
+        libm::copysign(self, sign)
+    }
+}
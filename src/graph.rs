@@ -0,0 +1,622 @@
+//! Composition of noise types into a single node graph, in the spirit of libnoise's
+//! modules. The graph is flattened into a list of operations when it is built, and
+//! evaluated one SIMD vector at a time, so no intermediate buffers are allocated no
+//! matter how many sources are combined.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let hills = NoiseBuilder::fbm_2d(0, 0).with_freq(0.01).wrap();
+//! let peaks = NoiseBuilder::ridge_2d(0, 0).with_freq(0.03).wrap();
+//! let mask = NoiseBuilder::gradient_2d(0, 0).with_freq(0.002).wrap();
+//!
+//! let terrain = Node::source(hills).blend(Node::source(peaks), Node::source(mask));
+//! let mut dim = NoiseDimensions::default(2);
+//! dim.width = 256;
+//! dim.height = 256;
+//! let (noise, min, max) = NoiseGraph::new(terrain, dim).generate();
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+use crate::noise::math_32::pow_ps;
+use crate::noise::wavelet_32::WaveletTile;
+use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_helpers_32::sample_noise;
+use crate::noise_type::NoiseType;
+use crate::point::wavelet_tile;
+use crate::range_mode::RangeMode;
+use crate::remap::{remap_ps, Remap};
+use crate::transform::Transform;
+
+use simdeez::Simd;
+
+use core::ops;
+
+/// A node in a noise graph. Leaves are noise types, everything else combines the
+/// values of its children. Only the frequency, transform, seed and noise specific
+/// parameters of a source are used, its block dimensions are ignored.
+#[derive(Clone)]
+pub enum Node {
+    Source(NoiseType),
+    Constant(f32),
+    Add(Box<Node>, Box<Node>),
+    Multiply(Box<Node>, Box<Node>),
+    Min(Box<Node>, Box<Node>),
+    Max(Box<Node>, Box<Node>),
+    /// Linear interpolation from `a` to `b`, with a control of -1 giving `a` and 1 giving `b`
+    Blend {
+        a: Box<Node>,
+        b: Box<Node>,
+        control: Box<Node>,
+    },
+    /// `b` where the control lies within `lower..=upper`, `a` elsewhere, with an
+    /// s-curve transition `falloff` wide on either side of each edge
+    Select {
+        a: Box<Node>,
+        b: Box<Node>,
+        control: Box<Node>,
+        lower: f32,
+        upper: f32,
+        falloff: f32,
+    },
+    Clamp {
+        source: Box<Node>,
+        lower: f32,
+        upper: f32,
+    },
+    Abs(Box<Node>),
+    /// Maps the source from [-1, 1] to [0, 1], raises it to `exponent`, and maps it back
+    Exponent {
+        source: Box<Node>,
+        exponent: f32,
+    },
+    /// source * scale + bias
+    ScaleBias {
+        source: Box<Node>,
+        scale: f32,
+        bias: f32,
+    },
+    /// Curves, terraces and power curves, see `Remap`
+    Remap {
+        source: Box<Node>,
+        remap: Remap,
+    },
+}
+
+impl Node {
+    pub fn source(noise_type: NoiseType) -> Node {
+        Node::Source(noise_type)
+    }
+
+    pub fn constant(value: f32) -> Node {
+        Node::Constant(value)
+    }
+
+    pub fn min(self, other: Node) -> Node {
+        Node::Min(Box::new(self), Box::new(other))
+    }
+
+    pub fn max(self, other: Node) -> Node {
+        Node::Max(Box::new(self), Box::new(other))
+    }
+
+    pub fn blend(self, b: Node, control: Node) -> Node {
+        Node::Blend {
+            a: Box::new(self),
+            b: Box::new(b),
+            control: Box::new(control),
+        }
+    }
+
+    pub fn select(self, b: Node, control: Node, lower: f32, upper: f32, falloff: f32) -> Node {
+        if lower > upper {
+            panic!("select lower bound must not be above the upper bound");
+        }
+        Node::Select {
+            a: Box::new(self),
+            b: Box::new(b),
+            control: Box::new(control),
+            lower,
+            upper,
+            falloff,
+        }
+    }
+
+    pub fn clamp(self, lower: f32, upper: f32) -> Node {
+        if lower > upper {
+            panic!("clamp lower bound must not be above the upper bound");
+        }
+        Node::Clamp {
+            source: Box::new(self),
+            lower,
+            upper,
+        }
+    }
+
+    pub fn abs(self) -> Node {
+        Node::Abs(Box::new(self))
+    }
+
+    pub fn exponent(self, exponent: f32) -> Node {
+        if !(exponent > 0.0) {
+            panic!("the exponent must be positive");
+        }
+        Node::Exponent {
+            source: Box::new(self),
+            exponent,
+        }
+    }
+
+    pub fn scale_bias(self, scale: f32, bias: f32) -> Node {
+        Node::ScaleBias {
+            source: Box::new(self),
+            scale,
+            bias,
+        }
+    }
+
+    pub fn remap(self, remap: Remap) -> Node {
+        Node::Remap {
+            source: Box::new(self),
+            remap,
+        }
+    }
+}
+
+impl ops::Add for Node {
+    type Output = Node;
+    fn add(self, other: Node) -> Node {
+        Node::Add(Box::new(self), Box::new(other))
+    }
+}
+
+impl ops::Mul for Node {
+    type Output = Node;
+    fn mul(self, other: Node) -> Node {
+        Node::Multiply(Box::new(self), Box::new(other))
+    }
+}
+
+// Each op writes one register, and only reads registers written before it,
+// so evaluating the list in order is a post-order walk of the tree.
+#[derive(Clone)]
+enum Op {
+    Source(NoiseType, Option<WaveletTile>),
+    Constant(f32),
+    Add(usize, usize),
+    Multiply(usize, usize),
+    Min(usize, usize),
+    Max(usize, usize),
+    Blend(usize, usize, usize),
+    Select {
+        a: usize,
+        b: usize,
+        control: usize,
+        lower: f32,
+        upper: f32,
+        falloff: f32,
+    },
+    Clamp(usize, f32, f32),
+    Abs(usize),
+    Exponent(usize, f32),
+    ScaleBias(usize, f32, f32),
+    Remap(usize, Remap),
+}
+
+/// A node graph, flattened and ready to generate blocks of noise
+#[derive(Clone)]
+pub struct NoiseGraph {
+    dim: NoiseDimensions,
+    pub freq_x: f32,
+    pub freq_y: f32,
+    pub freq_z: f32,
+    pub freq_w: f32,
+    ops: Vec<Op>,
+}
+
+impl DimensionalBeing for NoiseGraph {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        return self.dim;
+    }
+}
+
+impl NoiseGraph {
+    /// Build a graph generating blocks of the size and offset given by `dim`. The
+    /// frequencies default to 1 so the sources see the block coordinates unchanged.
+    pub fn new(root: Node, dim: NoiseDimensions) -> NoiseGraph {
+        let mut ops = Vec::new();
+        flatten(&root, dim.dim, &mut ops);
+        NoiseGraph {
+            dim,
+            freq_x: 1.0,
+            freq_y: 1.0,
+            freq_z: 1.0,
+            freq_w: 1.0,
+            ops,
+        }
+    }
+
+    pub fn with_freq(&mut self, freq: f32) -> &mut NoiseGraph {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    pub fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut NoiseGraph {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    pub fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut NoiseGraph {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    pub fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut NoiseGraph {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    /// Transform the coordinates of the whole graph, before any source's own transform
+    pub fn with_transform(&mut self, transform: Transform) -> &mut NoiseGraph {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    /// Choose how the min and max of generated blocks are found, see `RangeMode`
+    pub fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut NoiseGraph {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    /// Generate the block as a chunk of an integer grid, see `Settings::with_chunk`
+    pub fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut NoiseGraph {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    /// Space samples `step` apart, see `Settings::with_step`
+    pub fn with_step(&mut self, step: &[f32]) -> &mut NoiseGraph {
+        self.dim.set_step(step);
+        self
+    }
+
+    /// Bounds of the graph's output, from the analytic ranges of its sources
+    pub fn analytic_range(&self) -> (f32, f32) {
+        let mut ranges: Vec<(f32, f32)> = Vec::with_capacity(self.ops.len());
+        for op in &self.ops {
+            let range = match *op {
+                Op::Source(ref noise_type, _) => noise_type.analytic_range(),
+                Op::Constant(c) => (c, c),
+                Op::Add(a, b) => (ranges[a].0 + ranges[b].0, ranges[a].1 + ranges[b].1),
+                Op::Multiply(a, b) => {
+                    let (a, b) = (ranges[a], ranges[b]);
+                    extremes(&[a.0 * b.0, a.0 * b.1, a.1 * b.0, a.1 * b.1])
+                }
+                Op::Min(a, b) => (ranges[a].0.min(ranges[b].0), ranges[a].1.min(ranges[b].1)),
+                Op::Max(a, b) => (ranges[a].0.max(ranges[b].0), ranges[a].1.max(ranges[b].1)),
+                Op::Blend(a, b, control) => {
+                    // Bilinear in the weight and both inputs, so the corners bound it
+                    let (a, b) = (ranges[a], ranges[b]);
+                    let mut corners = Vec::with_capacity(8);
+                    for &c in &[ranges[control].0, ranges[control].1] {
+                        let t = (c + 1.0) * 0.5;
+                        for &va in &[a.0, a.1] {
+                            for &vb in &[b.0, b.1] {
+                                corners.push(va + t * (vb - va));
+                            }
+                        }
+                    }
+                    extremes(&corners)
+                }
+                Op::Select { a, b, .. } => {
+                    (ranges[a].0.min(ranges[b].0), ranges[a].1.max(ranges[b].1))
+                }
+                Op::Clamp(a, lower, upper) => (
+                    ranges[a].0.max(lower).min(upper),
+                    ranges[a].1.max(lower).min(upper),
+                ),
+                Op::Abs(a) => {
+                    let (lo, hi) = ranges[a];
+                    if lo >= 0.0 {
+                        (lo, hi)
+                    } else if hi <= 0.0 {
+                        (-hi, -lo)
+                    } else {
+                        (0.0, hi.max(-lo))
+                    }
+                }
+                Op::Exponent(a, exponent) => {
+                    let (lo, hi) = ranges[a];
+                    let (lo, hi) = ((lo + 1.0) * 0.5, (hi + 1.0) * 0.5);
+                    let (lo, hi) = if lo >= 0.0 {
+                        (lo, hi)
+                    } else if hi <= 0.0 {
+                        (-hi, -lo)
+                    } else {
+                        (0.0, hi.max(-lo))
+                    };
+                    (lo.powf(exponent) * 2.0 - 1.0, hi.powf(exponent) * 2.0 - 1.0)
+                }
+                Op::ScaleBias(a, scale, bias) => {
+                    extremes(&[ranges[a].0 * scale + bias, ranges[a].1 * scale + bias])
+                }
+                Op::Remap(a, ref remap) => remap.output_range(ranges[a].0, ranges[a].1),
+            };
+            ranges.push(range);
+        }
+        ranges[ranges.len() - 1]
+    }
+
+    // The min and max `generate_scaled` maps to its output range
+    pub(crate) fn scaling_range(&self, min: f32, max: f32) -> (f32, f32) {
+        match self.dim.range_mode {
+            RangeMode::Measured => (min, max),
+            RangeMode::Analytic | RangeMode::Untracked => self.analytic_range(),
+        }
+    }
+
+    /// Generate a block of noise, returned with its min and max values
+    pub fn generate(&self) -> (Vec<f32>, f32, f32) {
+        get_graph_noise!(self)
+    }
+
+    /// Generate a block of noise scaled to the range `min..=max`
+    pub fn generate_scaled(&self, min: f32, max: f32) -> Vec<f32> {
+        get_graph_scaled_noise!(self, min, max)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn eval_1d<S: Simd>(&self, regs: &mut Vec<S::Vf32>, x: S::Vf32) -> S::Vf32 {
+        let zero = S::setzero_ps();
+        self.eval::<S, _>(regs, |noise_type, tile| {
+            sample_noise::<S>(noise_type, tile, 1, x, zero, zero, zero)
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn eval_2d<S: Simd>(
+        &self,
+        regs: &mut Vec<S::Vf32>,
+        x: S::Vf32,
+        y: S::Vf32,
+    ) -> S::Vf32 {
+        let zero = S::setzero_ps();
+        self.eval::<S, _>(regs, |noise_type, tile| {
+            sample_noise::<S>(noise_type, tile, 2, x, y, zero, zero)
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn eval_3d<S: Simd>(
+        &self,
+        regs: &mut Vec<S::Vf32>,
+        x: S::Vf32,
+        y: S::Vf32,
+        z: S::Vf32,
+    ) -> S::Vf32 {
+        let zero = S::setzero_ps();
+        self.eval::<S, _>(regs, |noise_type, tile| {
+            sample_noise::<S>(noise_type, tile, 3, x, y, z, zero)
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn eval_4d<S: Simd>(
+        &self,
+        regs: &mut Vec<S::Vf32>,
+        x: S::Vf32,
+        y: S::Vf32,
+        z: S::Vf32,
+        w: S::Vf32,
+    ) -> S::Vf32 {
+        self.eval::<S, _>(regs, |noise_type, tile| {
+            sample_noise::<S>(noise_type, tile, 4, x, y, z, w)
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn eval<S: Simd, F>(&self, regs: &mut Vec<S::Vf32>, sample: F) -> S::Vf32
+    where
+        F: Fn(&NoiseType, Option<&WaveletTile>) -> S::Vf32,
+    {
+        regs.clear();
+        for op in &self.ops {
+            let v = match *op {
+                Op::Source(ref noise_type, ref tile) => sample(noise_type, tile.as_ref()),
+                Op::Constant(c) => S::set1_ps(c),
+                Op::Add(a, b) => S::add_ps(regs[a], regs[b]),
+                Op::Multiply(a, b) => S::mul_ps(regs[a], regs[b]),
+                Op::Min(a, b) => S::min_ps(regs[a], regs[b]),
+                Op::Max(a, b) => S::max_ps(regs[a], regs[b]),
+                Op::Blend(a, b, control) => {
+                    let t = S::mul_ps(S::add_ps(regs[control], S::set1_ps(1.0)), S::set1_ps(0.5));
+                    S::add_ps(regs[a], S::mul_ps(t, S::sub_ps(regs[b], regs[a])))
+                }
+                Op::Select {
+                    a,
+                    b,
+                    control,
+                    lower,
+                    upper,
+                    falloff,
+                } => {
+                    let t = select_weight::<S>(regs[control], lower, upper, falloff);
+                    S::add_ps(regs[a], S::mul_ps(t, S::sub_ps(regs[b], regs[a])))
+                }
+                Op::Clamp(a, lower, upper) => {
+                    S::max_ps(S::min_ps(regs[a], S::set1_ps(upper)), S::set1_ps(lower))
+                }
+                Op::Abs(a) => S::abs_ps(regs[a]),
+                Op::Exponent(a, exponent) => {
+                    let v = S::abs_ps(S::mul_ps(
+                        S::add_ps(regs[a], S::set1_ps(1.0)),
+                        S::set1_ps(0.5),
+                    ));
+                    let v = pow_ps::<S>(v, S::set1_ps(exponent));
+                    S::sub_ps(S::mul_ps(v, S::set1_ps(2.0)), S::set1_ps(1.0))
+                }
+                Op::ScaleBias(a, scale, bias) => {
+                    S::add_ps(S::mul_ps(regs[a], S::set1_ps(scale)), S::set1_ps(bias))
+                }
+                Op::Remap(a, ref remap) => remap_ps::<S>(remap, regs[a]),
+            };
+            regs.push(v);
+        }
+        regs[regs.len() - 1]
+    }
+}
+
+fn extremes(values: &[f32]) -> (f32, f32) {
+    values
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+}
+
+// 0 outside lower..=upper and 1 inside it, eased across `falloff` on each side of the edges
+#[inline(always)]
+unsafe fn select_weight<S: Simd>(
+    control: S::Vf32,
+    lower: f32,
+    upper: f32,
+    falloff: f32,
+) -> S::Vf32 {
+    let falloff = falloff.min((upper - lower) * 0.5);
+    if falloff > 0.0 {
+        let inv = S::set1_ps(0.5 / falloff);
+        let rise = S::mul_ps(S::sub_ps(control, S::set1_ps(lower - falloff)), inv);
+        let fall = S::mul_ps(S::sub_ps(S::set1_ps(upper + falloff), control), inv);
+        let t = S::max_ps(
+            S::min_ps(S::min_ps(rise, fall), S::set1_ps(1.0)),
+            S::setzero_ps(),
+        );
+        // 3t^2 - 2t^3
+        S::mul_ps(S::mul_ps(t, t), S::sub_ps(S::set1_ps(3.0), S::add_ps(t, t)))
+    } else {
+        let inside = S::andnot_ps(
+            S::cmplt_ps(control, S::set1_ps(lower)),
+            S::cmpge_ps(S::set1_ps(upper), control),
+        );
+        S::and_ps(inside, S::set1_ps(1.0))
+    }
+}
+
+// Appends the ops for `node` and its children, returning the register holding its value
+fn flatten(node: &Node, dim: usize, ops: &mut Vec<Op>) -> usize {
+    let op = match node {
+        Node::Source(noise_type) => {
+            let tile = match noise_type {
+                NoiseType::Wavelet(s) => Some(wavelet_tile(s, dim)),
+                _ => None,
+            };
+            Op::Source(*noise_type, tile)
+        }
+        Node::Constant(c) => Op::Constant(*c),
+        Node::Add(a, b) => Op::Add(flatten(a, dim, ops), flatten(b, dim, ops)),
+        Node::Multiply(a, b) => Op::Multiply(flatten(a, dim, ops), flatten(b, dim, ops)),
+        Node::Min(a, b) => Op::Min(flatten(a, dim, ops), flatten(b, dim, ops)),
+        Node::Max(a, b) => Op::Max(flatten(a, dim, ops), flatten(b, dim, ops)),
+        Node::Blend { a, b, control } => Op::Blend(
+            flatten(a, dim, ops),
+            flatten(b, dim, ops),
+            flatten(control, dim, ops),
+        ),
+        Node::Select {
+            a,
+            b,
+            control,
+            lower,
+            upper,
+            falloff,
+        } => Op::Select {
+            a: flatten(a, dim, ops),
+            b: flatten(b, dim, ops),
+            control: flatten(control, dim, ops),
+            lower: *lower,
+            upper: *upper,
+            falloff: *falloff,
+        },
+        Node::Clamp {
+            source,
+            lower,
+            upper,
+        } => Op::Clamp(flatten(source, dim, ops), *lower, *upper),
+        Node::Abs(source) => Op::Abs(flatten(source, dim, ops)),
+        Node::Exponent { source, exponent } => Op::Exponent(flatten(source, dim, ops), *exponent),
+        Node::ScaleBias {
+            source,
+            scale,
+            bias,
+        } => Op::ScaleBias(flatten(source, dim, ops), *scale, *bias),
+        Node::Remap { source, remap } => Op::Remap(flatten(source, dim, ops), remap.clone()),
+    };
+    ops.push(op);
+    ops.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoiseBuilder, Settings, SimplexSettings};
+
+    #[test]
+    fn single_source_matches_block() {
+        let fbm = NoiseBuilder::fbm_2d(37, 11).with_octaves(4).wrap();
+        let (expected, _, _) = NoiseBuilder::fbm_2d(37, 11).with_octaves(4).generate();
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = 37;
+        dim.height = 11;
+        let (noise, _, _) = NoiseGraph::new(Node::source(fbm), dim).generate();
+        assert_eq!(noise, expected);
+    }
+
+    #[test]
+    fn combinators() {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = 5;
+        let gradient = Node::source(NoiseBuilder::gradient_1d(0).with_freq(0.3).wrap());
+        let (plain, _, _) = NoiseGraph::new(gradient.clone(), dim).generate();
+
+        let node = (gradient.clone() + Node::constant(1.0)) * Node::constant(0.5);
+        let (mapped, _, _) = NoiseGraph::new(node, dim).generate();
+        let (clamped, _, _) = NoiseGraph::new(gradient.clamp(-0.1, 0.1), dim).generate();
+        for i in 0..plain.len() {
+            assert!((mapped[i] - (plain[i] + 1.0) * 0.5).abs() < 1e-6);
+            assert_eq!(clamped[i], plain[i].max(-0.1).min(0.1));
+        }
+
+        let select =
+            Node::constant(-1.0).select(Node::constant(1.0), Node::constant(0.5), 0.0, 1.0, 0.0);
+        let (selected, _, _) = NoiseGraph::new(select, dim).generate();
+        assert!(selected.iter().all(|&v| v == 1.0));
+
+        let exponent = Node::constant(0.0).exponent(2.0);
+        let (squared, _, _) = NoiseGraph::new(exponent, dim).generate();
+        assert!(squared.iter().all(|&v| (v + 0.5).abs() < 1e-5));
+    }
+}
@@ -0,0 +1,137 @@
+//! Half precision output for GPU upload, enabled with the `half` feature.
+//!
+//! Noise is generated in f32 and converted with F16C when the CPU has it, or in
+//! software otherwise. Conversion rounds to nearest even, like `f16::from_f32`.
+//!
+//! ```rust
+//! use half::f16;
+//! use simdnoise::*;
+//!
+//! let (volume, min, max) = NoiseBuilder::fbm_3d(32, 32, 32).generate_f16();
+//!
+//! let mut staging = vec![f16::ZERO; 32 * 32 * 32];
+//! NoiseBuilder::fbm_3d(32, 32, 32).generate_f16_into(&mut staging);
+//! assert_eq!(volume, staging);
+//! ```
+
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::intrinsics::avx2;
+use crate::intrinsics::scalar;
+use crate::settings::Settings;
+use crate::stream::rows;
+
+use core::convert::Infallible;
+
+use half::f16;
+use half::slice::HalfFloatSliceExt;
+
+pub trait HalfSettings: Settings {
+    /// Generate a chunk of noise as f16, along with the min and max value generated
+    fn generate_f16(self) -> (Vec<f16>, f32, f32);
+
+    /// Generate a chunk of noise as f16 into `out`, returning the min and max. Panics
+    /// if `out` is not the size of the chunk. Only one row is held as f32 at a time.
+    fn generate_f16_into(self, out: &mut [f16]) -> (f32, f32);
+}
+
+impl<T: Settings> HalfSettings for T {
+    fn generate_f16(self) -> (Vec<f16>, f32, f32) {
+        let (noise, min, max) = self.generate();
+        let mut result = vec![f16::ZERO; noise.len()];
+        convert_f16(&noise, &mut result);
+        (result, min, max)
+    }
+
+    fn generate_f16_into(self, out: &mut [f16]) -> (f32, f32) {
+        let noise_type = self.wrap();
+        let dim = noise_type.get_dimensions();
+        if let Err(e) = dim.try_validate_block() {
+            panic!("{}", e);
+        }
+        let extents = [dim.width, dim.height, dim.depth, dim.time];
+        if extents[..dim.dim].iter().product::<usize>() != out.len() {
+            panic!("output length does not match the noise dimensions");
+        }
+        let mut out_rows = out.chunks_exact_mut(dim.width);
+        let result: Result<_, Infallible> = rows(&noise_type, &mut |row| {
+            convert_f16(row, out_rows.next().unwrap());
+            Ok(())
+        });
+        match result {
+            Ok(range) => range,
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// Converts `src` to f16 in `dst`, which must be the same length
+pub fn convert_f16(src: &[f32], dst: &mut [f16]) {
+    if src.len() != dst.len() {
+        panic!("source and destination lengths differ");
+    }
+    let bits = dst.reinterpret_cast_mut();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx") && is_x86_feature_detected!("f16c") {
+            unsafe { avx2::convert_f16(src, bits) };
+            return;
+        }
+    }
+    scalar::convert_f16(src, bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoiseBuilder;
+
+    #[test]
+    fn conversion_matches_half_crate() {
+        let mut values = vec![
+            0.0,
+            -0.0,
+            1.0,
+            -2.5,
+            65504.0,
+            65520.0,
+            1.0e6,
+            -1.0e6,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            6.1e-5,
+            5.96e-8,
+            2.98e-8,
+            1.0e-9,
+            1.0 + 1.0 / 2048.0,
+            1.0 + 3.0 / 2048.0,
+        ];
+        values.extend((0..1000).map(|i| (i as f32 - 500.0) * 0.0137));
+        values.extend((0..100).map(|i| 1.0e-7 * i as f32));
+        let expected: Vec<u16> = values.iter().map(|v| f16::from_f32(*v).to_bits()).collect();
+
+        let mut software = vec![0u16; values.len()];
+        scalar::convert_f16(&values, &mut software);
+        assert_eq!(software, expected);
+
+        let mut dispatched = vec![f16::ZERO; values.len()];
+        convert_f16(&values, &mut dispatched);
+        let dispatched: Vec<u16> = dispatched.iter().map(|h| h.to_bits()).collect();
+        assert_eq!(dispatched, expected);
+    }
+
+    #[test]
+    fn rows_convert_into_place() {
+        let settings = *NoiseBuilder::ridge_3d(13, 5, 3).with_seed(4);
+        let (expected, min, max) = settings.generate_f16();
+        let mut out = vec![f16::ZERO; 13 * 5 * 3];
+        assert_eq!(settings.generate_f16_into(&mut out), (min, max));
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "output length does not match the noise dimensions")]
+    fn wrong_output_length_is_rejected() {
+        NoiseBuilder::fbm_2d(8, 8).generate_f16_into(&mut [f16::ZERO; 63]);
+    }
+}
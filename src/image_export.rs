@@ -0,0 +1,153 @@
+//! Turn blocks of noise into images, enabled with the `image` feature.
+//!
+//! Values are scaled from the block's min and max, so a block should be generated with
+//! a measured or analytic range. Take 2d slices of 3d and 4d blocks with
+//! `NoiseBlock::slice_xy`; slices keep the range of the whole block.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let block = NoiseBuilder::fbm_3d(128, 128, 16).generate_block();
+//! let gray = block.slice_xy(8, 0).to_gray_image();
+//! let terrain = block.slice_xy(8, 0).to_rgb32f_image(&ColorRamp::terrain());
+//! assert_eq!(gray.dimensions(), terrain.dimensions());
+//! ```
+
+use crate::noise_block::NoiseBlock;
+
+use image::{GrayImage, ImageBuffer, Luma, Rgb, Rgb32FImage};
+
+/// A colour gradient through `(position, [r, g, b])` stops, for mapping noise to RGB.
+/// Positions run from 0 at the block's min to 1 at its max, and colour components
+/// from 0 to 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorRamp {
+    stops: Vec<(f32, [f32; 3])>,
+}
+
+impl ColorRamp {
+    /// Ramp through the stops, sorted by position. Panics with no stops or a position that
+    /// is not finite.
+    pub fn new(mut stops: Vec<(f32, [f32; 3])>) -> ColorRamp {
+        if stops.is_empty() {
+            panic!("a color ramp needs at least 1 stop");
+        }
+        if stops.iter().any(|s| !s.0.is_finite()) {
+            panic!("color ramp stop positions must be finite");
+        }
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        ColorRamp { stops }
+    }
+
+    /// Black to white
+    pub fn grayscale() -> ColorRamp {
+        ColorRamp::new(vec![(0.0, [0.0, 0.0, 0.0]), (1.0, [1.0, 1.0, 1.0])])
+    }
+
+    /// Deep water, shallows, sand, grass, rock and snow
+    pub fn terrain() -> ColorRamp {
+        ColorRamp::new(vec![
+            (0.0, [0.0, 0.05, 0.3]),
+            (0.45, [0.1, 0.35, 0.7]),
+            (0.5, [0.85, 0.8, 0.55]),
+            (0.55, [0.25, 0.55, 0.2]),
+            (0.75, [0.4, 0.35, 0.3]),
+            (0.9, [0.95, 0.95, 0.95]),
+            (1.0, [1.0, 1.0, 1.0]),
+        ])
+    }
+
+    /// The colour at position `t`. Positions outside the stops take the end colours.
+    pub fn color(&self, t: f32) -> [f32; 3] {
+        let first = self.stops[0];
+        if !(t > first.0) {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let ((p0, c0), (p1, c1)) = (pair[0], pair[1]);
+            if t <= p1 {
+                let f = if p1 > p0 { (t - p0) / (p1 - p0) } else { 1.0 };
+                return [
+                    c0[0] + (c1[0] - c0[0]) * f,
+                    c0[1] + (c1[1] - c0[1]) * f,
+                    c0[2] + (c1[2] - c0[2]) * f,
+                ];
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+impl NoiseBlock {
+    /// 8 bit grayscale image of a 2d block, min is black and max is white
+    pub fn to_gray_image(&self) -> GrayImage {
+        let (width, height) = self.image_dimensions();
+        ImageBuffer::from_fn(width, height, |x, y| {
+            Luma([(self.unit(x, y) * 255.0).round() as u8])
+        })
+    }
+
+    /// 16 bit grayscale image of a 2d block, min is black and max is white
+    pub fn to_gray16_image(&self) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+        let (width, height) = self.image_dimensions();
+        ImageBuffer::from_fn(width, height, |x, y| {
+            Luma([(self.unit(x, y) * 65535.0).round() as u16])
+        })
+    }
+
+    /// Floating point RGB image of a 2d block, coloured by `ramp`
+    pub fn to_rgb32f_image(&self, ramp: &ColorRamp) -> Rgb32FImage {
+        let (width, height) = self.image_dimensions();
+        ImageBuffer::from_fn(width, height, |x, y| Rgb(ramp.color(self.unit(x, y))))
+    }
+
+    // Panics unless the block is a single xy plane
+    fn image_dimensions(&self) -> (u32, u32) {
+        let [width, height, depth, time] = self.shape();
+        if depth != 1 || time != 1 {
+            panic!("images need a 2d block, take one with slice_xy");
+        }
+        (width as u32, height as u32)
+    }
+
+    // The sample at x, y scaled from min..max to 0..1
+    fn unit(&self, x: u32, y: u32) -> f32 {
+        let v = self[(x as usize, y as usize)];
+        let range = self.max() - self.min();
+        if range > 0.0 {
+            ((v - self.min()) / range).max(0.0).min(1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn images_span_the_block_range() {
+        let block = NoiseBuilder::gradient_3d(32, 16, 4).generate_block();
+        let slice = block.slice_xy(1, 0);
+        let gray = slice.to_gray_image();
+        assert_eq!(gray.dimensions(), (32, 16));
+        let gray16 = slice.to_gray16_image();
+        let (x, y) = (5, 7);
+        let expected = (slice[(x, y)] - block.min()) / (block.max() - block.min());
+        assert!((gray16.get_pixel(x as u32, y as u32)[0] as f32 / 65535.0 - expected).abs() < 1e-4);
+        assert!((gray.get_pixel(x as u32, y as u32)[0] as f32 / 255.0 - expected).abs() < 1e-2);
+
+        let ramp = ColorRamp::new(vec![(0.0, [0.0, 0.0, 1.0]), (0.5, [1.0, 0.0, 0.0])]);
+        assert_eq!(ramp.color(0.25), [0.5, 0.0, 0.5]);
+        assert_eq!(ramp.color(2.0), [1.0, 0.0, 0.0]);
+        let rgb = slice.to_rgb32f_image(&ColorRamp::grayscale());
+        assert!((rgb.get_pixel(x as u32, y as u32)[1] - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "color ramp stop positions must be finite")]
+    fn nan_stops_are_rejected() {
+        ColorRamp::new(vec![(0.0, [0.0; 3]), (f32::NAN, [1.0; 3])]);
+    }
+}
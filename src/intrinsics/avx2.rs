@@ -8,27 +8,40 @@
 //! When using the `get_` functions, you will get a performance boost when width
 //! is evenly divisble by 8, and when it is not small relative height and depth.
 
+use alloc::vec::Vec;
+
+use crate::graph::NoiseGraph;
 use crate::noise::cell_32;
 use crate::noise::cell_64;
 use crate::noise::fbm_32;
 use crate::noise::fbm_64;
+use crate::noise::gabor_32;
 use crate::noise::ridge_32;
 use crate::noise::ridge_64;
 use crate::noise::simplex_32;
 use crate::noise::simplex_64;
 use crate::noise::turbulence_32;
 use crate::noise::turbulence_64;
-use crate::shared::scale_noise;
-use crate::{CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseType};
+use crate::noise::wavelet_32::{self, WaveletTile};
+use crate::noise::white_32;
+use crate::noise::white_64;
+#[cfg(feature = "ndarray")]
+use crate::noise_helpers_32::BlockOut;
+use crate::remap::Remap;
+use crate::settings::{WaveletSettings, WhiteNoiseSettings};
+use crate::shared::{quantize_noise, scale_noise};
+use crate::{
+    CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseDimensions, NoiseSource, NoiseType,
+};
 
 use simdeez::avx2::{Avx2, F32x8, F64x4};
 
 #[cfg(target_arch = "x86")]
-use std::arch::x86::*;
+use core::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::*;
+use core::arch::x86_64::*;
 
-use std::f32;
+use core::f32;
 
 /// Get a single value of 2d cellular/voroni noise
 #[target_feature(enable = "avx2")]
@@ -225,6 +238,7 @@ pub unsafe fn get_1d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_1d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_1d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Avx2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -341,6 +355,7 @@ pub unsafe fn get_2d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_2d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_2d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Avx2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -518,6 +533,7 @@ pub unsafe fn get_3d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_3d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_3d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Avx2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -705,6 +721,283 @@ pub unsafe fn get_4d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_4d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_4d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Avx2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
+
+/// Get a single value of 1d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_1d(x: __m256, seed: i32) -> __m256 {
+    white_32::white_1d::<Avx2>(F32x8(x), seed).0
+}
+
+/// Get a single value of 1d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_1d_f64(x: __m256d, seed: i64) -> __m256d {
+    white_64::white_1d::<Avx2>(F64x4(x), seed).0
+}
+
+/// Get the raw integer hash of the 1d white noise cell.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_hash_1d(x: __m256, seed: i32) -> __m256i {
+    white_32::white_hash_1d::<Avx2>(F32x8(x), seed).0
+}
+
+/// Get a single value of 2d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_2d(x: __m256, y: __m256, seed: i32) -> __m256 {
+    white_32::white_2d::<Avx2>(F32x8(x), F32x8(y), seed).0
+}
+
+/// Get a single value of 2d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_2d_f64(x: __m256d, y: __m256d, seed: i64) -> __m256d {
+    white_64::white_2d::<Avx2>(F64x4(x), F64x4(y), seed).0
+}
+
+/// Get the raw integer hash of the 2d white noise cell.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_hash_2d(x: __m256, y: __m256, seed: i32) -> __m256i {
+    white_32::white_hash_2d::<Avx2>(F32x8(x), F32x8(y), seed).0
+}
+
+/// Get a single value of 3d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_3d(x: __m256, y: __m256, z: __m256, seed: i32) -> __m256 {
+    white_32::white_3d::<Avx2>(F32x8(x), F32x8(y), F32x8(z), seed).0
+}
+
+/// Get a single value of 3d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_3d_f64(x: __m256d, y: __m256d, z: __m256d, seed: i64) -> __m256d {
+    white_64::white_3d::<Avx2>(F64x4(x), F64x4(y), F64x4(z), seed).0
+}
+
+/// Get the raw integer hash of the 3d white noise cell.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_hash_3d(x: __m256, y: __m256, z: __m256, seed: i32) -> __m256i {
+    white_32::white_hash_3d::<Avx2>(F32x8(x), F32x8(y), F32x8(z), seed).0
+}
+
+/// Get a single value of 4d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_4d(x: __m256, y: __m256, z: __m256, w: __m256, seed: i32) -> __m256 {
+    white_32::white_4d::<Avx2>(F32x8(x), F32x8(y), F32x8(z), F32x8(w), seed).0
+}
+
+/// Get a single value of 4d white noise, results
+/// are not scaled.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_4d_f64(x: __m256d, y: __m256d, z: __m256d, w: __m256d, seed: i64) -> __m256d {
+    white_64::white_4d::<Avx2>(F64x4(x), F64x4(y), F64x4(z), F64x4(w), seed).0
+}
+
+/// Get the raw integer hash of the 4d white noise cell.
+#[target_feature(enable = "avx2")]
+pub unsafe fn white_hash_4d(x: __m256, y: __m256, z: __m256, w: __m256, seed: i32) -> __m256i {
+    white_32::white_hash_4d::<Avx2>(F32x8(x), F32x8(y), F32x8(z), F32x8(w), seed).0
+}
+
+/// Gets the raw integer hashes of a block of white noise, laid out
+/// the same way as the noise values returned by the `get_` functions.
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_white_hash(settings: &WhiteNoiseSettings) -> Vec<i32> {
+    crate::noise_helpers_32::get_white_hash::<Avx2>(settings)
+}
+
+/// Gets a block of noise from a composition graph, unscaled, along with
+/// its min and max values.
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_graph_noise(graph: &NoiseGraph) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_graph_noise::<Avx2>(graph)
+}
+
+/// Gets a block of noise from a composition graph, scaled to `scaled_min..=scaled_max`.
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_graph_scaled_noise(
+    graph: &NoiseGraph,
+    scaled_min: f32,
+    scaled_max: f32,
+) -> Vec<f32> {
+    let (mut noise, min, max) = get_graph_noise(graph);
+    let (min, max) = graph.scaling_range(min, max);
+    scale_noise::<Avx2>(scaled_min, scaled_max, min, max, &mut noise);
+    noise
+}
+
+/// Writes a block of noise through a strided destination, returning the min and max.
+#[cfg(feature = "ndarray")]
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn get_noise_into(noise_type: &NoiseType, out: &BlockOut) -> (f32, f32) {
+    crate::noise_helpers_32::get_noise_into::<Avx2>(noise_type, out)
+}
+
+/// Remaps a buffer of noise values in place.
+#[target_feature(enable = "avx2")]
+pub unsafe fn remap_noise(remap: &Remap, data: &mut [f32]) {
+    crate::shared::remap_noise::<Avx2>(remap, data)
+}
+
+/// Gets a block of noise scaled from its min and max to the full u8 range, optionally
+/// with ordered dithering.
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_u8_noise(noise_type: &NoiseType, dither: bool) -> Vec<u8> {
+    let (noise, min, max) = crate::noise_helpers_32::get_noise::<Avx2>(noise_type);
+    let (min, max) = noise_type.scaling_range(min, max);
+    quantize_noise::<Avx2, u8>(&noise, &noise_type.get_dimensions(), min, max, dither)
+}
+
+/// Gets a block of noise scaled from its min and max to the full u16 range, optionally
+/// with ordered dithering.
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_u16_noise(noise_type: &NoiseType, dither: bool) -> Vec<u16> {
+    let (noise, min, max) = crate::noise_helpers_32::get_noise::<Avx2>(noise_type);
+    let (min, max) = noise_type.scaling_range(min, max);
+    quantize_noise::<Avx2, u16>(&noise, &noise_type.get_dimensions(), min, max, dither)
+}
+
+/// Gets a block of noise from a user defined `NoiseSource`, see `generate_source`
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_source_noise<N: NoiseSource<Avx2> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_source_noise::<Avx2, N>(source, dim)
+}
+
+/// Gets a block of wavelet noise sampling `tile`, see `WaveletSettings::generate_with_tile`
+#[target_feature(enable = "avx2")]
+pub unsafe fn get_wavelet_noise(
+    settings: &WaveletSettings,
+    tile: &WaveletTile,
+) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_wavelet_noise::<Avx2>(settings, tile)
+}
+
+/// Gets `dim` dimensional noise at 8 points, one array of coordinates per axis, see `point`
+#[target_feature(enable = "avx2")]
+pub unsafe fn sample_lanes(
+    noise_type: &NoiseType,
+    tile: Option<&WaveletTile>,
+    dim: usize,
+    coords: &[[f32; 8]; 4],
+    len: usize,
+) -> [f32; 8] {
+    crate::noise_helpers_32::sample_lanes::<Avx2>(noise_type, tile, dim, coords, len)
+}
+
+/// Generates a block of noise a row at a time, see `StreamSettings`
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn get_noise_rows<E>(
+    noise_type: &NoiseType,
+    f: &mut dyn FnMut(&[f32]) -> Result<(), E>,
+) -> Result<(f32, f32), E> {
+    crate::noise_helpers_32::get_noise_rows::<Avx2, E>(noise_type, f)
+}
+
+/// Get a single value of 2d gabor noise
+#[target_feature(enable = "avx2")]
+pub unsafe fn gabor_2d(
+    x: __m256,
+    y: __m256,
+    frequency: __m256,
+    orientation: f32,
+    bandwidth: __m256,
+    impulse_density: u8,
+    seed: i32,
+) -> __m256 {
+    gabor_32::gabor_2d::<Avx2>(
+        F32x8(x),
+        F32x8(y),
+        F32x8(frequency),
+        orientation,
+        F32x8(bandwidth),
+        impulse_density,
+        seed,
+    )
+    .0
+}
+
+/// Get a single value of 3d gabor noise
+#[target_feature(enable = "avx2")]
+pub unsafe fn gabor_3d(
+    x: __m256,
+    y: __m256,
+    z: __m256,
+    frequency: __m256,
+    orientation: f32,
+    elevation: f32,
+    bandwidth: __m256,
+    impulse_density: u8,
+    seed: i32,
+) -> __m256 {
+    gabor_32::gabor_3d::<Avx2>(
+        F32x8(x),
+        F32x8(y),
+        F32x8(z),
+        F32x8(frequency),
+        orientation,
+        elevation,
+        F32x8(bandwidth),
+        impulse_density,
+        seed,
+    )
+    .0
+}
+
+/// Get a single band of 2d wavelet noise from a 2d tile
+#[target_feature(enable = "avx2")]
+pub unsafe fn wavelet_2d(x: __m256, y: __m256, tile: &WaveletTile) -> __m256 {
+    wavelet_32::wavelet_2d::<Avx2>(F32x8(x), F32x8(y), tile).0
+}
+
+/// Get a single band of 3d wavelet noise from a 3d tile
+#[target_feature(enable = "avx2")]
+pub unsafe fn wavelet_3d(x: __m256, y: __m256, z: __m256, tile: &WaveletTile) -> __m256 {
+    wavelet_32::wavelet_3d::<Avx2>(F32x8(x), F32x8(y), F32x8(z), tile).0
+}
+
+/// Get a single band of 3d wavelet noise projected along `normal`, from a 3d tile
+#[target_feature(enable = "avx2")]
+pub unsafe fn wavelet_projected_3d(
+    x: __m256,
+    y: __m256,
+    z: __m256,
+    normal: [f32; 3],
+    tile: &WaveletTile,
+) -> __m256 {
+    wavelet_32::wavelet_projected_3d::<Avx2>(F32x8(x), F32x8(y), F32x8(z), normal, tile).0
+}
+
+/// Converts f32 values to the bits of half precision floats with F16C, rounding to
+/// nearest even. `dst` must be at least as long as `src`.
+///
+/// Use `is_x86_feature_detected!("f16c")` to detect at runtime.
+#[cfg(feature = "half")]
+#[target_feature(enable = "avx,f16c")]
+pub unsafe fn convert_f16(src: &[f32], dst: &mut [u16]) {
+    let mut i = 0;
+    while i + 8 <= src.len() {
+        let v = _mm256_loadu_ps(src.as_ptr().add(i));
+        let h = _mm256_cvtps_ph(v, _MM_FROUND_TO_NEAREST_INT);
+        _mm_storeu_si128(dst.as_mut_ptr().add(i) as *mut __m128i, h);
+        i += 8;
+    }
+    if i < src.len() {
+        let mut tail = [0.0f32; 8];
+        let mut bits = [0u16; 8];
+        let n = src.len() - i;
+        tail[..n].copy_from_slice(&src[i..]);
+        let h = _mm256_cvtps_ph(_mm256_loadu_ps(tail.as_ptr()), _MM_FROUND_TO_NEAREST_INT);
+        _mm_storeu_si128(bits.as_mut_ptr() as *mut __m128i, h);
+        dst[i..src.len()].copy_from_slice(&bits[..n]);
+    }
+}
@@ -8,3 +8,32 @@ pub mod sse41;
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod avx2;
+
+// The best instruction set enabled at compile time, used in place of runtime detection
+// when building without `std`
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx2"
+))]
+pub use self::avx2 as native;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse4.1",
+    not(target_feature = "avx2")
+))]
+pub use self::sse41 as native;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2",
+    not(any(target_feature = "sse4.1", target_feature = "avx2"))
+))]
+pub use self::sse2 as native;
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+pub use self::scalar as native;
@@ -2,23 +2,36 @@
 //!
 //! These are much slower than SIMD, and hence on capable hardware have little use but testing.
 
+use alloc::vec::Vec;
+
+use crate::graph::NoiseGraph;
 use crate::noise::cell_32;
 use crate::noise::cell_64;
 use crate::noise::fbm_32;
 use crate::noise::fbm_64;
+use crate::noise::gabor_32;
 use crate::noise::ridge_32;
 use crate::noise::ridge_64 as simplex_ridge_64;
 use crate::noise::simplex_32;
 use crate::noise::simplex_64;
 use crate::noise::turbulence_32;
 use crate::noise::turbulence_64;
-use crate::{CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseType};
-
-use crate::shared::scale_noise;
+use crate::noise::wavelet_32::{self, WaveletTile};
+use crate::noise::white_32;
+use crate::noise::white_64;
+#[cfg(feature = "ndarray")]
+use crate::noise_helpers_32::BlockOut;
+use crate::remap::Remap;
+use crate::settings::{WaveletSettings, WhiteNoiseSettings};
+use crate::{
+    CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseDimensions, NoiseSource, NoiseType,
+};
+
+use crate::shared::{quantize_noise, scale_noise};
 
 use simdeez::scalar::{F32x1, F64x1, Scalar};
 
-use std::f32;
+use core::f32;
 
 /// Get a single value of 2d cellular/voroni noise
 
@@ -179,6 +192,7 @@ pub unsafe fn get_1d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_1d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_1d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Scalar>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -278,6 +292,7 @@ pub unsafe fn get_2d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_2d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_2d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Scalar>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -432,6 +447,7 @@ pub unsafe fn get_3d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_3d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_3d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Scalar>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -621,6 +637,313 @@ pub unsafe fn get_4d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_4d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_4d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Scalar>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
+
+/// Get a single value of 1d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_1d(x: f32, seed: i32) -> f32 {
+    white_32::white_1d::<Scalar>(F32x1(x), seed).0
+}
+
+/// Get a single value of 1d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_1d_f64(x: f64, seed: i64) -> f64 {
+    white_64::white_1d::<Scalar>(F64x1(x), seed).0
+}
+
+/// Get the raw integer hash of the 1d white noise cell.
+
+pub unsafe fn white_hash_1d(x: f32, seed: i32) -> i32 {
+    white_32::white_hash_1d::<Scalar>(F32x1(x), seed).0
+}
+
+/// Get a single value of 2d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_2d(x: f32, y: f32, seed: i32) -> f32 {
+    white_32::white_2d::<Scalar>(F32x1(x), F32x1(y), seed).0
+}
+
+/// Get a single value of 2d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_2d_f64(x: f64, y: f64, seed: i64) -> f64 {
+    white_64::white_2d::<Scalar>(F64x1(x), F64x1(y), seed).0
+}
+
+/// Get the raw integer hash of the 2d white noise cell.
+
+pub unsafe fn white_hash_2d(x: f32, y: f32, seed: i32) -> i32 {
+    white_32::white_hash_2d::<Scalar>(F32x1(x), F32x1(y), seed).0
+}
+
+/// Get a single value of 3d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_3d(x: f32, y: f32, z: f32, seed: i32) -> f32 {
+    white_32::white_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0
+}
+
+/// Get a single value of 3d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_3d_f64(x: f64, y: f64, z: f64, seed: i64) -> f64 {
+    white_64::white_3d::<Scalar>(F64x1(x), F64x1(y), F64x1(z), seed).0
+}
+
+/// Get the raw integer hash of the 3d white noise cell.
+
+pub unsafe fn white_hash_3d(x: f32, y: f32, z: f32, seed: i32) -> i32 {
+    white_32::white_hash_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), seed).0
+}
+
+/// Get a single value of 4d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_4d(x: f32, y: f32, z: f32, w: f32, seed: i32) -> f32 {
+    white_32::white_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed).0
+}
+
+/// Get a single value of 4d white noise, results
+/// are not scaled.
+
+pub unsafe fn white_4d_f64(x: f64, y: f64, z: f64, w: f64, seed: i64) -> f64 {
+    white_64::white_4d::<Scalar>(F64x1(x), F64x1(y), F64x1(z), F64x1(w), seed).0
+}
+
+/// Get the raw integer hash of the 4d white noise cell.
+
+pub unsafe fn white_hash_4d(x: f32, y: f32, z: f32, w: f32, seed: i32) -> i32 {
+    white_32::white_hash_4d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), F32x1(w), seed).0
+}
+
+/// Gets the raw integer hashes of a block of white noise, laid out
+/// the same way as the noise values returned by the `get_` functions.
+
+pub unsafe fn get_white_hash(settings: &WhiteNoiseSettings) -> Vec<i32> {
+    crate::noise_helpers_32::get_white_hash::<Scalar>(settings)
+}
+
+/// Gets a block of noise from a composition graph, unscaled, along with
+/// its min and max values.
+
+pub unsafe fn get_graph_noise(graph: &NoiseGraph) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_graph_noise::<Scalar>(graph)
+}
+
+/// Gets a block of noise from a composition graph, scaled to `scaled_min..=scaled_max`.
+
+pub unsafe fn get_graph_scaled_noise(
+    graph: &NoiseGraph,
+    scaled_min: f32,
+    scaled_max: f32,
+) -> Vec<f32> {
+    let (mut noise, min, max) = get_graph_noise(graph);
+    let (min, max) = graph.scaling_range(min, max);
+    scale_noise::<Scalar>(scaled_min, scaled_max, min, max, &mut noise);
+    noise
+}
+
+/// Writes a block of noise through a strided destination, returning the min and max.
+#[cfg(feature = "ndarray")]
+pub(crate) unsafe fn get_noise_into(noise_type: &NoiseType, out: &BlockOut) -> (f32, f32) {
+    crate::noise_helpers_32::get_noise_into::<Scalar>(noise_type, out)
+}
+
+/// Remaps a buffer of noise values in place.
+
+pub unsafe fn remap_noise(remap: &Remap, data: &mut [f32]) {
+    crate::shared::remap_noise::<Scalar>(remap, data)
+}
+
+/// Gets a block of noise scaled from its min and max to the full u8 range, optionally
+/// with ordered dithering.
+
+pub unsafe fn get_u8_noise(noise_type: &NoiseType, dither: bool) -> Vec<u8> {
+    let (noise, min, max) = crate::noise_helpers_32::get_noise::<Scalar>(noise_type);
+    let (min, max) = noise_type.scaling_range(min, max);
+    quantize_noise::<Scalar, u8>(&noise, &noise_type.get_dimensions(), min, max, dither)
+}
+
+/// Gets a block of noise scaled from its min and max to the full u16 range, optionally
+/// with ordered dithering.
+
+pub unsafe fn get_u16_noise(noise_type: &NoiseType, dither: bool) -> Vec<u16> {
+    let (noise, min, max) = crate::noise_helpers_32::get_noise::<Scalar>(noise_type);
+    let (min, max) = noise_type.scaling_range(min, max);
+    quantize_noise::<Scalar, u16>(&noise, &noise_type.get_dimensions(), min, max, dither)
+}
+
+/// Gets a block of noise from a user defined `NoiseSource`, see `generate_source`
+
+pub unsafe fn get_source_noise<N: NoiseSource<Scalar> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_source_noise::<Scalar, N>(source, dim)
+}
+
+/// Gets a block of wavelet noise sampling `tile`, see `WaveletSettings::generate_with_tile`
+
+pub unsafe fn get_wavelet_noise(
+    settings: &WaveletSettings,
+    tile: &WaveletTile,
+) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_wavelet_noise::<Scalar>(settings, tile)
+}
+
+/// Gets `dim` dimensional noise at 8 points, one array of coordinates per axis, see `point`
+
+pub unsafe fn sample_lanes(
+    noise_type: &NoiseType,
+    tile: Option<&WaveletTile>,
+    dim: usize,
+    coords: &[[f32; 8]; 4],
+    len: usize,
+) -> [f32; 8] {
+    crate::noise_helpers_32::sample_lanes::<Scalar>(noise_type, tile, dim, coords, len)
+}
+
+/// Generates a block of noise a row at a time, see `StreamSettings`
+
+pub(crate) unsafe fn get_noise_rows<E>(
+    noise_type: &NoiseType,
+    f: &mut dyn FnMut(&[f32]) -> Result<(), E>,
+) -> Result<(f32, f32), E> {
+    crate::noise_helpers_32::get_noise_rows::<Scalar, E>(noise_type, f)
+}
+
+/// Get a single value of 2d gabor noise
+
+pub unsafe fn gabor_2d(
+    x: f32,
+    y: f32,
+    frequency: f32,
+    orientation: f32,
+    bandwidth: f32,
+    impulse_density: u8,
+    seed: i32,
+) -> f32 {
+    gabor_32::gabor_2d::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(frequency),
+        orientation,
+        F32x1(bandwidth),
+        impulse_density,
+        seed,
+    )
+    .0
+}
+
+/// Get a single value of 3d gabor noise
+
+pub unsafe fn gabor_3d(
+    x: f32,
+    y: f32,
+    z: f32,
+    frequency: f32,
+    orientation: f32,
+    elevation: f32,
+    bandwidth: f32,
+    impulse_density: u8,
+    seed: i32,
+) -> f32 {
+    gabor_32::gabor_3d::<Scalar>(
+        F32x1(x),
+        F32x1(y),
+        F32x1(z),
+        F32x1(frequency),
+        orientation,
+        elevation,
+        F32x1(bandwidth),
+        impulse_density,
+        seed,
+    )
+    .0
+}
+
+/// Get a single band of 2d wavelet noise from a 2d tile
+
+pub unsafe fn wavelet_2d(x: f32, y: f32, tile: &WaveletTile) -> f32 {
+    wavelet_32::wavelet_2d::<Scalar>(F32x1(x), F32x1(y), tile).0
+}
+
+/// Get a single band of 3d wavelet noise from a 3d tile
+
+pub unsafe fn wavelet_3d(x: f32, y: f32, z: f32, tile: &WaveletTile) -> f32 {
+    wavelet_32::wavelet_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), tile).0
+}
+
+/// Get a single band of 3d wavelet noise projected along `normal`, from a 3d tile
+
+pub unsafe fn wavelet_projected_3d(
+    x: f32,
+    y: f32,
+    z: f32,
+    normal: [f32; 3],
+    tile: &WaveletTile,
+) -> f32 {
+    wavelet_32::wavelet_projected_3d::<Scalar>(F32x1(x), F32x1(y), F32x1(z), normal, tile).0
+}
+
+/// Converts f32 values to the bits of half precision floats in software, rounding to
+/// nearest even. `dst` must be at least as long as `src`.
+#[cfg(feature = "half")]
+pub fn convert_f16(src: &[f32], dst: &mut [u16]) {
+    for (s, d) in src.iter().zip(dst.iter_mut()) {
+        *d = f32_to_f16_bits(*s);
+    }
+}
+
+#[cfg(feature = "half")]
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let x = value.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xff) as i32;
+    let man = x & 0x7f_ffff;
+
+    // Infinity, or NaN kept quiet
+    if exp == 0xff {
+        let nan = if man != 0 {
+            0x0200 | (man >> 13) as u16
+        } else {
+            0
+        };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    // Subnormal or zero
+    if half_exp <= 0 {
+        if 14 - half_exp > 24 {
+            return sign;
+        }
+        let man = man | 0x80_0000;
+        let shift = (14 - half_exp) as u32;
+        let mut half_man = man >> shift;
+        let round_bit = 1 << (shift - 1);
+        if man & round_bit != 0 && man & (3 * round_bit - 1) != 0 {
+            half_man += 1;
+        }
+        return sign | half_man as u16;
+    }
+
+    let bits = ((half_exp as u16) << 10) | (man >> 13) as u16;
+    let round_bit = 0x1000;
+    if man & round_bit != 0 && man & (3 * round_bit - 1) != 0 {
+        // A carry out of the mantissa correctly bumps the exponent
+        sign | (bits + 1)
+    } else {
+        sign | bits
+    }
+}
@@ -6,28 +6,41 @@
 //! When using the `get_` functions, you will get a performance boost when width
 //! is evenly divisble by 4, and when it is not small relative height and depth.
 
+use alloc::vec::Vec;
+
+use crate::graph::NoiseGraph;
 use crate::noise::cell_32;
 use crate::noise::cell_64;
 use crate::noise::fbm_32;
 use crate::noise::fbm_64;
+use crate::noise::gabor_32;
 use crate::noise::ridge_32;
 use crate::noise::ridge_64;
 use crate::noise::simplex_32;
 use crate::noise::simplex_64;
 use crate::noise::turbulence_32;
 use crate::noise::turbulence_64;
-use crate::{CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseType};
-
-use crate::shared::scale_noise;
+use crate::noise::wavelet_32::{self, WaveletTile};
+use crate::noise::white_32;
+use crate::noise::white_64;
+#[cfg(feature = "ndarray")]
+use crate::noise_helpers_32::BlockOut;
+use crate::remap::Remap;
+use crate::settings::{WaveletSettings, WhiteNoiseSettings};
+use crate::{
+    CellDistanceFunction, CellReturnType, DimensionalBeing, NoiseDimensions, NoiseSource, NoiseType,
+};
+
+use crate::shared::{quantize_noise, scale_noise};
 
 use simdeez::sse2::{F32x4, F64x2, Sse2};
 
 #[cfg(target_arch = "x86")]
-use std::arch::x86::*;
+use core::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::*;
+use core::arch::x86_64::*;
 
-use std::f32;
+use core::f32;
 
 /// Get a single value of 2d cellular/voroni noise
 #[target_feature(enable = "sse2")]
@@ -222,6 +235,7 @@ pub unsafe fn get_1d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_1d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_1d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Sse2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -339,6 +353,7 @@ pub unsafe fn get_2d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_2d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_2d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Sse2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -516,6 +531,7 @@ pub unsafe fn get_3d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_3d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_3d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Sse2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
@@ -703,6 +719,258 @@ pub unsafe fn get_4d_noise_64(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
 pub unsafe fn get_4d_scaled_noise(noise_type: &NoiseType) -> Vec<f32> {
     let (mut noise, min, max) = get_4d_noise(noise_type);
     let dim = noise_type.get_dimensions();
+    let (min, max) = noise_type.scaling_range(min, max);
     scale_noise::<Sse2>(dim.min, dim.max, min, max, &mut noise);
     noise
 }
+
+/// Get a single value of 1d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_1d(x: __m128, seed: i32) -> __m128 {
+    white_32::white_1d::<Sse2>(F32x4(x), seed).0
+}
+
+/// Get a single value of 1d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_1d_f64(x: __m128d, seed: i64) -> __m128d {
+    white_64::white_1d::<Sse2>(F64x2(x), seed).0
+}
+
+/// Get the raw integer hash of the 1d white noise cell.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_hash_1d(x: __m128, seed: i32) -> __m128i {
+    white_32::white_hash_1d::<Sse2>(F32x4(x), seed).0
+}
+
+/// Get a single value of 2d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_2d(x: __m128, y: __m128, seed: i32) -> __m128 {
+    white_32::white_2d::<Sse2>(F32x4(x), F32x4(y), seed).0
+}
+
+/// Get a single value of 2d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_2d_f64(x: __m128d, y: __m128d, seed: i64) -> __m128d {
+    white_64::white_2d::<Sse2>(F64x2(x), F64x2(y), seed).0
+}
+
+/// Get the raw integer hash of the 2d white noise cell.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_hash_2d(x: __m128, y: __m128, seed: i32) -> __m128i {
+    white_32::white_hash_2d::<Sse2>(F32x4(x), F32x4(y), seed).0
+}
+
+/// Get a single value of 3d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_3d(x: __m128, y: __m128, z: __m128, seed: i32) -> __m128 {
+    white_32::white_3d::<Sse2>(F32x4(x), F32x4(y), F32x4(z), seed).0
+}
+
+/// Get a single value of 3d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_3d_f64(x: __m128d, y: __m128d, z: __m128d, seed: i64) -> __m128d {
+    white_64::white_3d::<Sse2>(F64x2(x), F64x2(y), F64x2(z), seed).0
+}
+
+/// Get the raw integer hash of the 3d white noise cell.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_hash_3d(x: __m128, y: __m128, z: __m128, seed: i32) -> __m128i {
+    white_32::white_hash_3d::<Sse2>(F32x4(x), F32x4(y), F32x4(z), seed).0
+}
+
+/// Get a single value of 4d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_4d(x: __m128, y: __m128, z: __m128, w: __m128, seed: i32) -> __m128 {
+    white_32::white_4d::<Sse2>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed).0
+}
+
+/// Get a single value of 4d white noise, results
+/// are not scaled.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_4d_f64(x: __m128d, y: __m128d, z: __m128d, w: __m128d, seed: i64) -> __m128d {
+    white_64::white_4d::<Sse2>(F64x2(x), F64x2(y), F64x2(z), F64x2(w), seed).0
+}
+
+/// Get the raw integer hash of the 4d white noise cell.
+#[target_feature(enable = "sse2")]
+pub unsafe fn white_hash_4d(x: __m128, y: __m128, z: __m128, w: __m128, seed: i32) -> __m128i {
+    white_32::white_hash_4d::<Sse2>(F32x4(x), F32x4(y), F32x4(z), F32x4(w), seed).0
+}
+
+/// Gets the raw integer hashes of a block of white noise, laid out
+/// the same way as the noise values returned by the `get_` functions.
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_white_hash(settings: &WhiteNoiseSettings) -> Vec<i32> {
+    crate::noise_helpers_32::get_white_hash::<Sse2>(settings)
+}
+
+/// Gets a block of noise from a composition graph, unscaled, along with
+/// its min and max values.
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_graph_noise(graph: &NoiseGraph) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_graph_noise::<Sse2>(graph)
+}
+
+/// Gets a block of noise from a composition graph, scaled to `scaled_min..=scaled_max`.
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_graph_scaled_noise(
+    graph: &NoiseGraph,
+    scaled_min: f32,
+    scaled_max: f32,
+) -> Vec<f32> {
+    let (mut noise, min, max) = get_graph_noise(graph);
+    let (min, max) = graph.scaling_range(min, max);
+    scale_noise::<Sse2>(scaled_min, scaled_max, min, max, &mut noise);
+    noise
+}
+
+/// Writes a block of noise through a strided destination, returning the min and max.
+#[cfg(feature = "ndarray")]
+#[target_feature(enable = "sse2")]
+pub(crate) unsafe fn get_noise_into(noise_type: &NoiseType, out: &BlockOut) -> (f32, f32) {
+    crate::noise_helpers_32::get_noise_into::<Sse2>(noise_type, out)
+}
+
+/// Remaps a buffer of noise values in place.
+#[target_feature(enable = "sse2")]
+pub unsafe fn remap_noise(remap: &Remap, data: &mut [f32]) {
+    crate::shared::remap_noise::<Sse2>(remap, data)
+}
+
+/// Gets a block of noise scaled from its min and max to the full u8 range, optionally
+/// with ordered dithering.
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_u8_noise(noise_type: &NoiseType, dither: bool) -> Vec<u8> {
+    let (noise, min, max) = crate::noise_helpers_32::get_noise::<Sse2>(noise_type);
+    let (min, max) = noise_type.scaling_range(min, max);
+    quantize_noise::<Sse2, u8>(&noise, &noise_type.get_dimensions(), min, max, dither)
+}
+
+/// Gets a block of noise scaled from its min and max to the full u16 range, optionally
+/// with ordered dithering.
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_u16_noise(noise_type: &NoiseType, dither: bool) -> Vec<u16> {
+    let (noise, min, max) = crate::noise_helpers_32::get_noise::<Sse2>(noise_type);
+    let (min, max) = noise_type.scaling_range(min, max);
+    quantize_noise::<Sse2, u16>(&noise, &noise_type.get_dimensions(), min, max, dither)
+}
+
+/// Gets a block of noise from a user defined `NoiseSource`, see `generate_source`
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_source_noise<N: NoiseSource<Sse2> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_source_noise::<Sse2, N>(source, dim)
+}
+
+/// Gets a block of wavelet noise sampling `tile`, see `WaveletSettings::generate_with_tile`
+#[target_feature(enable = "sse2")]
+pub unsafe fn get_wavelet_noise(
+    settings: &WaveletSettings,
+    tile: &WaveletTile,
+) -> (Vec<f32>, f32, f32) {
+    crate::noise_helpers_32::get_wavelet_noise::<Sse2>(settings, tile)
+}
+
+/// Gets `dim` dimensional noise at 8 points, one array of coordinates per axis, see `point`
+#[target_feature(enable = "sse2")]
+pub unsafe fn sample_lanes(
+    noise_type: &NoiseType,
+    tile: Option<&WaveletTile>,
+    dim: usize,
+    coords: &[[f32; 8]; 4],
+    len: usize,
+) -> [f32; 8] {
+    crate::noise_helpers_32::sample_lanes::<Sse2>(noise_type, tile, dim, coords, len)
+}
+
+/// Generates a block of noise a row at a time, see `StreamSettings`
+#[target_feature(enable = "sse2")]
+pub(crate) unsafe fn get_noise_rows<E>(
+    noise_type: &NoiseType,
+    f: &mut dyn FnMut(&[f32]) -> Result<(), E>,
+) -> Result<(f32, f32), E> {
+    crate::noise_helpers_32::get_noise_rows::<Sse2, E>(noise_type, f)
+}
+
+/// Get a single value of 2d gabor noise
+#[target_feature(enable = "sse2")]
+pub unsafe fn gabor_2d(
+    x: __m128,
+    y: __m128,
+    frequency: __m128,
+    orientation: f32,
+    bandwidth: __m128,
+    impulse_density: u8,
+    seed: i32,
+) -> __m128 {
+    gabor_32::gabor_2d::<Sse2>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(frequency),
+        orientation,
+        F32x4(bandwidth),
+        impulse_density,
+        seed,
+    )
+    .0
+}
+
+/// Get a single value of 3d gabor noise
+#[target_feature(enable = "sse2")]
+pub unsafe fn gabor_3d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    frequency: __m128,
+    orientation: f32,
+    elevation: f32,
+    bandwidth: __m128,
+    impulse_density: u8,
+    seed: i32,
+) -> __m128 {
+    gabor_32::gabor_3d::<Sse2>(
+        F32x4(x),
+        F32x4(y),
+        F32x4(z),
+        F32x4(frequency),
+        orientation,
+        elevation,
+        F32x4(bandwidth),
+        impulse_density,
+        seed,
+    )
+    .0
+}
+
+/// Get a single band of 2d wavelet noise from a 2d tile
+#[target_feature(enable = "sse2")]
+pub unsafe fn wavelet_2d(x: __m128, y: __m128, tile: &WaveletTile) -> __m128 {
+    wavelet_32::wavelet_2d::<Sse2>(F32x4(x), F32x4(y), tile).0
+}
+
+/// Get a single band of 3d wavelet noise from a 3d tile
+#[target_feature(enable = "sse2")]
+pub unsafe fn wavelet_3d(x: __m128, y: __m128, z: __m128, tile: &WaveletTile) -> __m128 {
+    wavelet_32::wavelet_3d::<Sse2>(F32x4(x), F32x4(y), F32x4(z), tile).0
+}
+
+/// Get a single band of 3d wavelet noise projected along `normal`, from a 3d tile
+#[target_feature(enable = "sse2")]
+pub unsafe fn wavelet_projected_3d(
+    x: __m128,
+    y: __m128,
+    z: __m128,
+    normal: [f32; 3],
+    tile: &WaveletTile,
+) -> __m128 {
+    wavelet_32::wavelet_projected_3d::<Sse2>(F32x4(x), F32x4(y), F32x4(z), normal, tile).0
+}
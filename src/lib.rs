@@ -6,10 +6,34 @@
 //!## Features
 //!
 //!* SSE2, SSE41, and AVX2 instruction sets, along with non SIMD fallback
-//!* Runtime detection picks the best available instruction set
+//!* Runtime detection picks the best available instruction set, or compile time selection without std
 //!* Simplex noise, fractal brownian motion, turbulence, and ridge
 //!* 1D, 2D, 3D, and 4D
 //!* Cellular / Voroni Noise  2D and 3D
+//!* Gabor noise 2D and 3D, for oriented, band-limited textures
+//!* Wavelet noise 2D, 3D, and 3D projected onto surfaces, with band-limited octaves
+//!* White noise 1D, 2D, 3D, and 4D, including the raw per-cell integer hashes
+//!* Blue noise threshold masks and scattered points
+//!* Matrix or quaternion domain transforms, and per-octave rotation for fractal noise
+//!* Composition graphs combining noise types, evaluated per SIMD vector
+//!* Curve, spline, terrace, and power remapping of noise values
+//!* Analytic output ranges, for consistent scaling of separately generated chunks
+//!* Chunk coordinates with exact, seamless borders for infinite worlds
+//!* Per-axis sample spacing, for coarser levels of detail over the same points
+//!* `NoiseBlock` results that know their shape, with coordinate indexing and row iterators
+//!* Optional `ndarray` support, generating straight into arrays of any memory layout
+//!* Optional `image` support, exporting blocks as 8 and 16 bit grayscale or colour ramped images
+//!* u8 and u16 output quantized in SIMD, with optional ordered dithering
+//!* Optional `half` support for f16 output, converted with F16C where available
+//!* `try_generate` and `try_wrap`, returning a `NoiseError` for invalid settings
+//!* Dimension typed settings in `typed`, rejecting invalid dimension and noise combinations at compile time
+//!* Safe single point and 8 lane functions in `point`, for every noise type
+//!* Streaming generation a row at a time with `StreamSettings`, for blocks too big for memory
+//!* `NoiseSource` for user defined SIMD noise, generated like the built in types
+//!* Optional `serde` support for all settings, saved as versioned `NoisePreset`s
+//!* Algorithm versions, pinning the output of saved worlds across releases
+//!* Default `std` feature, without it only `core` and `alloc` are needed
+//!* `AlgorithmVersion::V3_1Deterministic`, giving bit-identical noise on every backend and CPU
 //!
 //!## Benchmarks
 //! See [Github](https://github.com/verpeteren/rust-simd-noise)
@@ -42,6 +66,19 @@
 //!
 //! ```
 //!
+//! ## Get single points of noise
+//! The functions in `point` evaluate one point, or 8 at once, with runtime SIMD detection
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let v = point::simplex_2d(5.0, 10.0, 42);
+//!
+//! let settings = NoiseBuilder::cellular_2d(1, 1).with_freq(0.05).with_seed(42).clone();
+//! let v = point::cellular_2d(5.0, 10.0, &settings);
+//! let lanes = point::cellular_2d_lanes([5.0; point::LANES], [10.0; point::LANES], &settings);
+//! ```
+//!
 //! ## Call noise functions directly
 //! Sometimes you need something other than a block, like the points on the surface of a sphere.
 //! Sometimes you may want to use SSE41 even with AVX2 is available
@@ -84,28 +121,53 @@
 //!
 //! }
 //! ```
+//!
+//! ## Without std
+//! Disable the default `std` feature to build with only `core` and `alloc`. The instruction
+//! set is then chosen at compile time, from the target features enabled with
+//! `-C target-feature` or `-C target-cpu`, instead of detected at runtime. The `image`,
+//! `half` and `serde` features need std.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
 extern crate simdeez;
 
+mod algorithm_version;
+pub mod blue_noise;
+mod chunk;
 mod dimensional_being;
+mod float;
 pub mod intrinsics;
 pub mod noise;
+mod noise_block;
 mod noise_builder;
 mod noise_dimensions;
+mod noise_error;
 mod noise_helpers_32;
 mod noise_helpers_64;
 mod noise_type;
+mod range_mode;
+mod seed;
 mod shared;
+mod transform;
 
+pub use algorithm_version::AlgorithmVersion;
 use dimensional_being::DimensionalBeing;
 pub use noise::cell2_return_type::Cell2ReturnType;
 pub use noise::cell_distance_function::CellDistanceFunction;
 pub use noise::cell_return_type::CellReturnType;
+pub use noise::octave_params::OctaveSeeding;
+pub use noise_block::NoiseBlock;
 pub use noise_builder::NoiseBuilder;
 pub use noise_dimensions::NoiseDimensions;
+pub use noise_error::NoiseError;
 pub use noise_type::NoiseType;
+pub use range_mode::RangeMode;
+pub use seed::{seed_from_bytes, seed_from_hash, seed_from_u64, SeedHasher};
+pub use transform::Transform;
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_1d_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -120,14 +182,14 @@ macro_rules! get_1d_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_1d_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_1d_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_1d_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_2d_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -142,14 +204,14 @@ macro_rules! get_2d_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_2d_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_2d_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_2d_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_3d_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -164,14 +226,14 @@ macro_rules! get_3d_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_3d_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_3d_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_3d_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_4d_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -186,14 +248,14 @@ macro_rules! get_4d_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_4d_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_4d_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_4d_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_1d_scaled_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -208,14 +270,14 @@ macro_rules! get_1d_scaled_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_1d_scaled_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_1d_scaled_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_1d_scaled_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_2d_scaled_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -230,14 +292,14 @@ macro_rules! get_2d_scaled_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_2d_scaled_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_2d_scaled_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_2d_scaled_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_3d_scaled_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -252,14 +314,14 @@ macro_rules! get_3d_scaled_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_3d_scaled_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_3d_scaled_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_3d_scaled_noise($setting) }
     };
 }
 
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 macro_rules! get_4d_scaled_noise {
     ($setting:expr) => {
         if is_x86_feature_detected!("avx2") {
@@ -274,19 +336,305 @@ macro_rules! get_4d_scaled_noise {
     };
 }
 
-#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
 macro_rules! get_4d_scaled_noise {
     ($setting:expr) => {
-        unsafe { scalar::get_4d_scaled_noise($setting) }
+        unsafe { $crate::intrinsics::native::get_4d_scaled_noise($setting) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_white_hash {
+    ($setting:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_white_hash($setting) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_white_hash($setting) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_white_hash($setting) }
+        } else {
+            unsafe { scalar::get_white_hash($setting) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_white_hash {
+    ($setting:expr) => {
+        unsafe { $crate::intrinsics::native::get_white_hash($setting) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_graph_noise {
+    ($graph:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_graph_noise($graph) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_graph_noise($graph) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_graph_noise($graph) }
+        } else {
+            unsafe { scalar::get_graph_noise($graph) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_graph_noise {
+    ($graph:expr) => {
+        unsafe { $crate::intrinsics::native::get_graph_noise($graph) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_graph_scaled_noise {
+    ($graph:expr, $min:expr, $max:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_graph_scaled_noise($graph, $min, $max) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_graph_scaled_noise($graph, $min, $max) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_graph_scaled_noise($graph, $min, $max) }
+        } else {
+            unsafe { scalar::get_graph_scaled_noise($graph, $min, $max) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_graph_scaled_noise {
+    ($graph:expr, $min:expr, $max:expr) => {
+        unsafe { $crate::intrinsics::native::get_graph_scaled_noise($graph, $min, $max) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! remap_noise {
+    ($remap:expr, $data:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::remap_noise($remap, $data) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::remap_noise($remap, $data) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::remap_noise($remap, $data) }
+        } else {
+            unsafe { scalar::remap_noise($remap, $data) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! remap_noise {
+    ($remap:expr, $data:expr) => {
+        unsafe { $crate::intrinsics::native::remap_noise($remap, $data) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_u8_noise {
+    ($setting:expr, $dither:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_u8_noise($setting, $dither) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_u8_noise($setting, $dither) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_u8_noise($setting, $dither) }
+        } else {
+            unsafe { scalar::get_u8_noise($setting, $dither) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_u8_noise {
+    ($setting:expr, $dither:expr) => {
+        unsafe { $crate::intrinsics::native::get_u8_noise($setting, $dither) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_u16_noise {
+    ($setting:expr, $dither:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_u16_noise($setting, $dither) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_u16_noise($setting, $dither) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_u16_noise($setting, $dither) }
+        } else {
+            unsafe { scalar::get_u16_noise($setting, $dither) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_u16_noise {
+    ($setting:expr, $dither:expr) => {
+        unsafe { $crate::intrinsics::native::get_u16_noise($setting, $dither) }
+    };
+}
+
+#[cfg(all(
+    feature = "ndarray",
+    feature = "std",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+macro_rules! get_noise_into {
+    ($setting:expr, $out:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_noise_into($setting, $out) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_noise_into($setting, $out) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_noise_into($setting, $out) }
+        } else {
+            unsafe { scalar::get_noise_into($setting, $out) }
+        }
+    };
+}
+
+#[cfg(all(
+    feature = "ndarray",
+    not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))
+))]
+macro_rules! get_noise_into {
+    ($setting:expr, $out:expr) => {
+        unsafe { $crate::intrinsics::native::get_noise_into($setting, $out) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_source_noise {
+    ($source:expr, $dim:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_source_noise($source, $dim) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_source_noise($source, $dim) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_source_noise($source, $dim) }
+        } else {
+            unsafe { scalar::get_source_noise($source, $dim) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_source_noise {
+    ($source:expr, $dim:expr) => {
+        unsafe { $crate::intrinsics::native::get_source_noise($source, $dim) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_wavelet_noise {
+    ($setting:expr, $tile:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_wavelet_noise($setting, $tile) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_wavelet_noise($setting, $tile) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_wavelet_noise($setting, $tile) }
+        } else {
+            unsafe { scalar::get_wavelet_noise($setting, $tile) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_wavelet_noise {
+    ($setting:expr, $tile:expr) => {
+        unsafe { $crate::intrinsics::native::get_wavelet_noise($setting, $tile) }
+    };
+}
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! sample_lanes {
+    ($noise_type:expr, $tile:expr, $dim:expr, $coords:expr, $len:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::sample_lanes($noise_type, $tile, $dim, $coords, $len) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::sample_lanes($noise_type, $tile, $dim, $coords, $len) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::sample_lanes($noise_type, $tile, $dim, $coords, $len) }
+        } else {
+            unsafe { scalar::sample_lanes($noise_type, $tile, $dim, $coords, $len) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! sample_lanes {
+    ($noise_type:expr, $tile:expr, $dim:expr, $coords:expr, $len:expr) => {
+        unsafe { $crate::intrinsics::native::sample_lanes($noise_type, $tile, $dim, $coords, $len) }
     };
 }
 
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+macro_rules! get_noise_rows {
+    ($setting:expr, $f:expr) => {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::get_noise_rows($setting, $f) }
+        } else if is_x86_feature_detected!("sse4.1") {
+            unsafe { sse41::get_noise_rows($setting, $f) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::get_noise_rows($setting, $f) }
+        } else {
+            unsafe { scalar::get_noise_rows($setting, $f) }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64"))))]
+macro_rules! get_noise_rows {
+    ($setting:expr, $f:expr) => {
+        unsafe { $crate::intrinsics::native::get_noise_rows($setting, $f) }
+    };
+}
+
+mod graph;
+pub use graph::{Node, NoiseGraph};
+
+mod noise_source;
+pub use noise_source::{generate_source, NoiseSource, SimdNoiseSource};
+
+mod remap;
+pub use remap::Remap;
+
 mod settings;
 pub use settings::{
-    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, RidgeSettings, Settings,
-    SimplexSettings, TurbulenceSettings,
+    Cellular2Settings, CellularSettings, FbmSettings, GaborSettings, GradientSettings,
+    RidgeSettings, Settings, SimplexSettings, TurbulenceSettings, WaveletSettings,
+    WhiteNoiseSettings,
 };
 
+pub mod point;
+
+mod stream;
+pub use stream::StreamSettings;
+
+pub mod typed;
+
+#[cfg(feature = "ndarray")]
+mod array;
+#[cfg(feature = "ndarray")]
+pub use array::ArraySettings;
+
+#[cfg(feature = "image")]
+mod image_export;
+#[cfg(feature = "image")]
+pub use image_export::ColorRamp;
+
+#[cfg(feature = "half")]
+mod half_output;
+#[cfg(feature = "half")]
+pub use half_output::{convert_f16, HalfSettings};
+
+#[cfg(feature = "serde")]
+mod preset;
+#[cfg(feature = "serde")]
+pub use preset::{NoisePreset, PRESET_VERSION};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,4 +744,355 @@ mod tests {
             assert_delta!(sse41[i], avx2[i], 0.1);
         }
     }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    macro_rules! assert_backends_bit_identical {
+        ($get:ident, $setting:expr) => {
+            let results = unsafe {
+                [
+                    scalar::$get($setting),
+                    sse2::$get($setting),
+                    sse41::$get($setting),
+                    avx2::$get($setting),
+                ]
+            };
+            let (noise, min, max) = &results[0];
+            for (other, other_min, other_max) in results[1..].iter() {
+                assert_eq!(noise.len(), other.len());
+                for (a, b) in noise.iter().zip(other.iter()) {
+                    assert_eq!(a.to_bits(), b.to_bits());
+                }
+                assert_eq!(min.to_bits(), other_min.to_bits());
+                assert_eq!(max.to_bits(), other_max.to_bits());
+            }
+        };
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn deterministic_consistency() {
+        let version = AlgorithmVersion::V3_1Deterministic;
+        // Cellular offsets put samples exactly halfway between cells
+        let settings = [
+            NoiseBuilder::gradient_1d_offset(-20.5, 100)
+                .with_freq(0.5)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::fbm_1d(1000)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::ridge_2d(233, 33)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::fbm_2d_offset(-8.5, 37, 3.5, 19)
+                .with_freq(0.5)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::turbulence_3d(23, 23, 23)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::ridge_4d(10, 10, 10, 10)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::cellular_2d_offset(-10.0, 100, 4.0, 100)
+                .with_freq(0.25)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::cellular_3d(17, 9, 5)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::cellular2_2d_offset(-10.0, 37, 4.0, 19)
+                .with_freq(0.25)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::cellular2_3d(32, 32, 32)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::gabor_2d(37, 19)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::wavelet_3d(17, 9, 5)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::white_noise_4d(9, 7, 5, 3)
+                .with_algorithm_version(version)
+                .wrap(),
+        ];
+        for setting in settings.iter() {
+            match setting.get_dimensions().dim {
+                1 => {
+                    assert_backends_bit_identical!(get_1d_noise, setting);
+                }
+                2 => {
+                    assert_backends_bit_identical!(get_2d_noise, setting);
+                }
+                3 => {
+                    assert_backends_bit_identical!(get_3d_noise, setting);
+                }
+                _ => {
+                    assert_backends_bit_identical!(get_4d_noise, setting);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn deterministic_consistency_64() {
+        let version = AlgorithmVersion::V3_1Deterministic;
+        let settings = [
+            NoiseBuilder::ridge_1d(1000)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::fbm_2d_offset(-8.5, 37, 3.5, 19)
+                .with_freq(0.5)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::turbulence_3d(23, 23, 23)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::gradient_4d(10, 10, 10, 10)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::cellular_2d_offset(-10.0, 100, 4.0, 100)
+                .with_freq(0.25)
+                .with_algorithm_version(version)
+                .wrap(),
+            NoiseBuilder::cellular2_3d(32, 32, 32)
+                .with_algorithm_version(version)
+                .wrap(),
+        ];
+        for setting in settings.iter() {
+            match setting.get_dimensions().dim {
+                1 => {
+                    assert_backends_bit_identical!(get_1d_noise_64, setting);
+                }
+                2 => {
+                    assert_backends_bit_identical!(get_2d_noise_64, setting);
+                }
+                3 => {
+                    assert_backends_bit_identical!(get_3d_noise_64, setting);
+                }
+                _ => {
+                    assert_backends_bit_identical!(get_4d_noise_64, setting);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    fn white_hash_consistency_3d() {
+        let settings = NoiseBuilder::white_noise_3d(13, 7, 5)
+            .with_freq(0.5)
+            .with_seed(99)
+            .clone();
+        let scalar_hash = unsafe { scalar::get_white_hash(&settings) };
+        let sse2_hash = unsafe { sse2::get_white_hash(&settings) };
+        let sse41_hash = unsafe { sse41::get_white_hash(&settings) };
+        let avx2_hash = unsafe { avx2::get_white_hash(&settings) };
+
+        assert_eq!(scalar_hash.len(), 13 * 7 * 5);
+        assert_eq!(scalar_hash, sse2_hash);
+        assert_eq!(sse2_hash, sse41_hash);
+        assert_eq!(sse41_hash, avx2_hash);
+    }
+
+    #[test]
+    fn transform_translation_matches_offset() {
+        let translated = NoiseBuilder::gradient_2d(16, 16)
+            .with_freq(0.5)
+            .with_transform(Transform::identity().with_translation_2d(2.5, -4.0))
+            .generate()
+            .0;
+        let offset = NoiseBuilder::gradient_2d_offset(5.0, 16, -8.0, 16)
+            .with_freq(0.5)
+            .generate()
+            .0;
+        for i in 0..translated.len() {
+            assert_delta!(translated[i], offset[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn octave_rotation_changes_upper_octaves_only() {
+        let plain = NoiseBuilder::fbm_2d(32, 32).with_octaves(1).generate().0;
+        let rotated = NoiseBuilder::fbm_2d(32, 32)
+            .with_octaves(1)
+            .with_octave_rotation(Transform::rotation_2d(0.5))
+            .generate()
+            .0;
+        assert_eq!(plain, rotated);
+
+        let plain = NoiseBuilder::fbm_2d(32, 32).generate().0;
+        let rotated = NoiseBuilder::fbm_2d(32, 32)
+            .with_octave_rotation(Transform::rotation_2d(0.5))
+            .generate()
+            .0;
+        assert_ne!(plain, rotated);
+    }
+
+    #[test]
+    fn analytic_range_is_chunk_independent() {
+        let left = NoiseBuilder::fbm_2d_offset(0.0, 16, 0.0, 16)
+            .with_range_mode(RangeMode::Analytic)
+            .generate_scaled(0.0, 1.0);
+        let right = NoiseBuilder::fbm_2d_offset(15.0, 16, 0.0, 16)
+            .with_range_mode(RangeMode::Analytic)
+            .generate_scaled(0.0, 1.0);
+        for y in 0..16 {
+            assert_eq!(left[y * 16 + 15], right[y * 16]);
+        }
+        assert!(left.iter().all(|&v| v >= 0.0 && v <= 1.0));
+
+        let (_, min, max) = NoiseBuilder::gradient_2d(8, 8)
+            .with_range_mode(RangeMode::Untracked)
+            .generate();
+        assert!(min.is_nan() && max.is_nan());
+
+        let mut swapped = NoiseBuilder::cellular2_2d(8, 8)
+            .with_return_type(Cell2ReturnType::Distance2Div)
+            .clone();
+        swapped.index0 = 2;
+        swapped.index1 = 1;
+        assert_eq!(
+            NoiseType::Cellular2(swapped).analytic_range(),
+            (1.0, f32::INFINITY)
+        );
+    }
+
+    #[test]
+    fn quantized_output_matches_scaled() {
+        let scaled = NoiseBuilder::fbm_2d(37, 23).generate_scaled(0.0, 255.0);
+        let plain = NoiseBuilder::fbm_2d(37, 23).generate_u8(false);
+        let dithered = NoiseBuilder::fbm_2d(37, 23).generate_u8(true);
+        assert_eq!(plain.len(), scaled.len());
+        for i in 0..scaled.len() {
+            assert_eq!(plain[i], (scaled[i] + 0.5).floor() as u8);
+            assert!((dithered[i] as f32 - scaled[i]).abs() < 1.0);
+        }
+        assert!(plain.contains(&0) && plain.contains(&255));
+
+        let wide = NoiseBuilder::fbm_2d(37, 23).generate_u16(false);
+        assert!(wide.contains(&0) && wide.contains(&u16::MAX));
+    }
+
+    #[test]
+    fn chunk_borders_are_bit_identical() {
+        // Far enough out that f32 offsets can no longer represent every sample position
+        for &cx in &[0, -1, 1_000_000] {
+            let (left, _, _) = NoiseBuilder::fbm_3d(33, 33, 33)
+                .with_freq(0.013)
+                .with_chunk(&[cx, 2, -3], 32)
+                .generate();
+            let (right, _, _) = NoiseBuilder::fbm_3d(33, 33, 33)
+                .with_freq(0.013)
+                .with_chunk(&[cx + 1, 2, -3], 32)
+                .generate();
+            for z in 0..33 {
+                for y in 0..33 {
+                    let row = (z * 33 + y) * 33;
+                    assert_eq!(left[row + 32].to_bits(), right[row].to_bits());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stepped_block_matches_every_kth_sample() {
+        let (fine, _, _) = NoiseBuilder::fbm_2d_offset(3.5, 61, -7.25, 61)
+            .with_freq(0.05)
+            .generate();
+        let (coarse, _, _) = NoiseBuilder::fbm_2d_offset(3.5, 16, -7.25, 16)
+            .with_freq(0.05)
+            .with_step(&[4.0, 4.0])
+            .generate();
+        for y in 0..16 {
+            for x in 0..16 {
+                assert_eq!(coarse[y * 16 + x], fine[y * 4 * 61 + x * 4]);
+            }
+        }
+    }
+
+    #[test]
+    fn octave_seeding_keeps_shared_output() {
+        let shared = NoiseBuilder::ridge_3d(16, 8, 4).with_seed(42).generate().0;
+        let explicit = NoiseBuilder::ridge_3d(16, 8, 4)
+            .with_seed(42)
+            .with_octave_seeding(OctaveSeeding::Shared)
+            .generate()
+            .0;
+        assert_eq!(shared, explicit);
+
+        // Only octaves after the first change
+        let first = |seeding| {
+            NoiseBuilder::ridge_3d(16, 8, 4)
+                .with_seed(42)
+                .with_octaves(1)
+                .with_octave_seeding(seeding)
+                .generate()
+                .0
+        };
+        assert_eq!(first(OctaveSeeding::Derived), first(OctaveSeeding::Shared));
+        assert_eq!(first(OctaveSeeding::Offset), first(OctaveSeeding::Shared));
+        assert_eq!(noise::octave_params::octave_seed(42, 0), 42);
+
+        for seeding in [OctaveSeeding::Derived, OctaveSeeding::Offset].iter() {
+            let settings = *NoiseBuilder::fbm_2d(16, 8)
+                .with_seed_hash("octaves")
+                .with_octave_seeding(*seeding);
+            assert_ne!(
+                settings.generate().0,
+                NoiseBuilder::fbm_2d(16, 8)
+                    .with_seed_hash("octaves")
+                    .generate()
+                    .0
+            );
+            let (f32_noise, _, _) = unsafe { scalar::get_2d_noise(&settings.wrap()) };
+            let (f64_noise, _, _) = unsafe { scalar::get_2d_noise_64(&settings.wrap()) };
+            for (a, b) in f32_noise.iter().zip(f64_noise.iter()) {
+                assert_delta!(*a as f64, *b, 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn wide_seeds_are_kept() {
+        let wide = 0x9e37_79b9_7f4a_7c15;
+        let settings = *NoiseBuilder::gradient_2d(16, 8).with_seed_u64(wide);
+        assert_eq!(settings.get_dimensions().seed, seed_from_u64(wide));
+        assert_eq!(settings.get_dimensions().seed_u64(), wide);
+        let mut reseeded = settings;
+        reseeded.with_seed(-7);
+        assert_eq!(reseeded.get_dimensions().seed_u64(), -7i64 as u64);
+
+        let generate = |seed: u64, version| {
+            let settings = *NoiseBuilder::cellular_2d(16, 8)
+                .with_seed_u64(seed)
+                .with_algorithm_version(version);
+            unsafe {
+                (
+                    scalar::get_2d_noise(&settings.wrap()).0,
+                    scalar::get_2d_noise_64(&settings.wrap()).0,
+                )
+            }
+        };
+        // Seeds that fit an i32, and every seed in 3.1, keep their noise
+        assert_eq!(
+            generate(-7i64 as u64, AlgorithmVersion::V3_2),
+            generate(-7i64 as u64, AlgorithmVersion::V3_1)
+        );
+        let mixed = *NoiseBuilder::cellular_2d(16, 8)
+            .with_seed(seed_from_u64(wide))
+            .with_algorithm_version(AlgorithmVersion::V3_1);
+        assert_eq!(generate(wide, AlgorithmVersion::V3_1).0, unsafe {
+            scalar::get_2d_noise(&mixed.wrap()).0
+        });
+
+        let (f32_noise, f64_noise) = generate(wide, AlgorithmVersion::V3_2);
+        assert_ne!(f32_noise, generate(wide, AlgorithmVersion::V3_1).0);
+        for (a, b) in f32_noise.iter().zip(f64_noise.iter()) {
+            assert_delta!(*a as f64, *b, 0.001);
+        }
+    }
 }
@@ -1,5 +1,6 @@
-use super::cellular_32::{hash_2d, hash_3d, BIT_10_MASK_32, X_PRIME_32, Y_PRIME_32, Z_PRIME_32};
-use crate::{Cell2ReturnType, CellDistanceFunction};
+use super::cellular_32::{hash_2d, hash_3d, BIT_10_MASK_32};
+use super::deterministic::{nearest_epi32, rsqrt_ps};
+use crate::{AlgorithmVersion, Cell2ReturnType, CellDistanceFunction};
 
 use simdeez::Simd;
 
@@ -14,16 +15,42 @@ pub unsafe fn cellular2_2d<S: Simd>(
     index1: usize,
     seed: i32,
 ) -> S::Vf32 {
+    cellular2_2d_versioned::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_2d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    let [x_prime, y_prime, _] = version.hash_primes_32();
     let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
 
-    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
-    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let mut xc = S::sub_epi32(nearest_epi32::<S>(x, version), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(nearest_epi32::<S>(y, version), S::set1_epi32(1));
 
     let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
     let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
 
-    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME_32));
-    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME_32));
+    xc = S::mullo_epi32(xc, S::set1_epi32(x_prime));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(y_prime));
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -43,7 +70,7 @@ pub unsafe fn cellular2_2d<S: Simd>(
             );
             let inv_mag = S::mul_ps(
                 jitter,
-                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+                rsqrt_ps::<S>(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)), version),
             );
             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -64,10 +91,10 @@ pub unsafe fn cellular2_2d<S: Simd>(
                 i -= 1;
             }
             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
         }
         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
     }
 
     match return_type {
@@ -91,19 +118,47 @@ pub unsafe fn cellular2_3d<S: Simd>(
     index1: usize,
     seed: i32,
 ) -> S::Vf32 {
+    cellular2_3d_versioned::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_3d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf32,
+    index0: usize,
+    index1: usize,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    let [x_prime, y_prime, z_prime] = version.hash_primes_32();
     let mut distance: [S::Vf32; 4] = [S::set1_ps(999999.0); 4];
 
-    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
-    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
-    let mut zc_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+    let mut xc = S::sub_epi32(nearest_epi32::<S>(x, version), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(nearest_epi32::<S>(y, version), S::set1_epi32(1));
+    let mut zc_base = S::sub_epi32(nearest_epi32::<S>(z, version), S::set1_epi32(1));
 
     let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
     let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
     let zcf_base = S::sub_ps(S::cvtepi32_ps(zc_base), z);
 
-    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME_32));
-    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME_32));
-    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(Z_PRIME_32));
+    xc = S::mullo_epi32(xc, S::set1_epi32(x_prime));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(y_prime));
+    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(z_prime));
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -133,10 +188,13 @@ pub unsafe fn cellular2_3d<S: Simd>(
                 );
                 let inv_mag = S::mul_ps(
                     jitter,
-                    S::rsqrt_ps(S::add_ps(
-                        S::mul_ps(xd, xd),
-                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
-                    )),
+                    rsqrt_ps::<S>(
+                        S::add_ps(
+                            S::mul_ps(xd, xd),
+                            S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                        ),
+                        version,
+                    ),
                 );
                 xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                 yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -166,13 +224,13 @@ pub unsafe fn cellular2_3d<S: Simd>(
                     i -= 1;
                 }
                 zcf = S::add_ps(ycf, S::set1_ps(1.0));
-                zc = S::add_epi32(yc, S::set1_epi32(Z_PRIME_32));
+                zc = S::add_epi32(yc, S::set1_epi32(z_prime));
             }
             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
         }
         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
     }
 
     match return_type {
@@ -1,6 +1,7 @@
-use super::cellular_32::{BIT_10_MASK_64, X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
+use super::cellular_32::BIT_10_MASK_64;
 use super::cellular_64::{hash_2d, hash_3d};
-use crate::{Cell2ReturnType, CellDistanceFunction};
+use super::deterministic::{nearest_epi64, rsqrt_pd};
+use crate::{AlgorithmVersion, Cell2ReturnType, CellDistanceFunction};
 
 use simdeez::Simd;
 
@@ -15,16 +16,42 @@ pub unsafe fn cellular2_2d<S: Simd>(
     index1: usize,
     seed: i64,
 ) -> S::Vf64 {
+    cellular2_2d_versioned::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_2d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    let [x_prime, y_prime, _] = version.hash_primes_64();
     let mut distance: [S::Vf64; 4] = [S::set1_pd(999999.0); 4];
 
-    let mut xc = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
-    let mut yc_base = S::sub_epi64(S::cvtpd_epi64(y), S::set1_epi64(1));
+    let mut xc = S::sub_epi64(nearest_epi64::<S>(x, version), S::set1_epi64(1));
+    let mut yc_base = S::sub_epi64(nearest_epi64::<S>(y, version), S::set1_epi64(1));
 
     let mut xcf = S::sub_pd(S::cvtepi64_pd(xc), x);
     let ycf_base = S::sub_pd(S::cvtepi64_pd(yc_base), y);
 
-    xc = S::mullo_epi64(xc, S::set1_epi64(X_PRIME_64));
-    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(Y_PRIME_64));
+    xc = S::mullo_epi64(xc, S::set1_epi64(x_prime));
+    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(y_prime));
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -44,7 +71,7 @@ pub unsafe fn cellular2_2d<S: Simd>(
             );
             let inv_mag = S::mul_pd(
                 jitter,
-                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                rsqrt_pd::<S>(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd)), version),
             );
             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -65,10 +92,10 @@ pub unsafe fn cellular2_2d<S: Simd>(
                 i -= 1;
             }
             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
         }
         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
     }
 
     match return_type {
@@ -92,19 +119,47 @@ pub unsafe fn cellular2_3d<S: Simd>(
     index1: usize,
     seed: i64,
 ) -> S::Vf64 {
+    cellular2_3d_versioned::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        index0,
+        index1,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular2_3d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: Cell2ReturnType,
+    jitter: S::Vf64,
+    index0: usize,
+    index1: usize,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    let [x_prime, y_prime, z_prime] = version.hash_primes_64();
     let mut distance: [S::Vf64; 4] = [S::set1_pd(999999.0); 4];
 
-    let mut xc = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
-    let mut yc_base = S::sub_epi64(S::cvtpd_epi64(y), S::set1_epi64(1));
-    let mut zc_base = S::sub_epi64(S::cvtpd_epi64(z), S::set1_epi64(1));
+    let mut xc = S::sub_epi64(nearest_epi64::<S>(x, version), S::set1_epi64(1));
+    let mut yc_base = S::sub_epi64(nearest_epi64::<S>(y, version), S::set1_epi64(1));
+    let mut zc_base = S::sub_epi64(nearest_epi64::<S>(z, version), S::set1_epi64(1));
 
     let mut xcf = S::sub_pd(S::cvtepi64_pd(xc), x);
     let ycf_base = S::sub_pd(S::cvtepi64_pd(yc_base), y);
     let zcf_base = S::sub_pd(S::cvtepi64_pd(zc_base), z);
 
-    xc = S::mullo_epi64(xc, S::set1_epi64(X_PRIME_64));
-    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(Y_PRIME_64));
-    zc_base = S::mullo_epi64(zc_base, S::set1_epi64(Z_PRIME_64));
+    xc = S::mullo_epi64(xc, S::set1_epi64(x_prime));
+    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(y_prime));
+    zc_base = S::mullo_epi64(zc_base, S::set1_epi64(z_prime));
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -134,10 +189,13 @@ pub unsafe fn cellular2_3d<S: Simd>(
                 );
                 let inv_mag = S::mul_pd(
                     jitter,
-                    S::rsqrt_pd(S::add_pd(
-                        S::mul_pd(xd, xd),
-                        S::add_pd(S::mul_pd(yd, yd), S::mul_pd(zd, zd)),
-                    )),
+                    rsqrt_pd::<S>(
+                        S::add_pd(
+                            S::mul_pd(xd, xd),
+                            S::add_pd(S::mul_pd(yd, yd), S::mul_pd(zd, zd)),
+                        ),
+                        version,
+                    ),
                 );
                 xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                 yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -167,13 +225,13 @@ pub unsafe fn cellular2_3d<S: Simd>(
                     i -= 1;
                 }
                 zcf = S::add_pd(ycf, S::set1_pd(1.0));
-                zc = S::add_epi64(yc, S::set1_epi64(Z_PRIME_64));
+                zc = S::add_epi64(yc, S::set1_epi64(z_prime));
             }
             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
         }
         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
     }
 
     match return_type {
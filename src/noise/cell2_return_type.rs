@@ -1,4 +1,5 @@
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Determines what final value is returned for the cell2 noise
 pub enum Cell2ReturnType {
     Distance2,
@@ -1,7 +1,6 @@
-use super::cellular_32::{
-    hash_2d, hash_3d, BIT_10_MASK_32, HASH_2_FLOAT_32, X_PRIME_32, Y_PRIME_32, Z_PRIME_32,
-};
-use crate::{CellDistanceFunction, CellReturnType};
+use super::cellular_32::{hash_2d, hash_3d, BIT_10_MASK_32, HASH_2_FLOAT_32};
+use super::deterministic::{nearest_epi32, rsqrt_ps};
+use crate::{AlgorithmVersion, CellDistanceFunction, CellReturnType};
 
 use simdeez::Simd;
 
@@ -14,15 +13,37 @@ pub unsafe fn cellular_2d<S: Simd>(
     jitter: S::Vf32,
     seed: i32,
 ) -> S::Vf32 {
+    cellular_2d_versioned::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular_2d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    let [x_prime, y_prime, _] = version.hash_primes_32();
     let mut distance = S::set1_ps(999999.0);
-    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
-    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
+    let mut xc = S::sub_epi32(nearest_epi32::<S>(x, version), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(nearest_epi32::<S>(y, version), S::set1_epi32(1));
 
     let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
     let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
 
-    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME_32));
-    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME_32));
+    xc = S::mullo_epi32(xc, S::set1_epi32(x_prime));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(y_prime));
     match return_type {
         CellReturnType::Distance => {
             match distance_function {
@@ -44,8 +65,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                                 S::set1_ps(511.5),
                             );
                             let mut xd2 = S::mul_ps(xd, xd);
-                            let inv_mag =
-                                S::mul_ps(jitter, S::rsqrt_ps(S::add_ps(xd2, S::mul_ps(yd, yd))));
+                            let inv_mag = S::mul_ps(
+                                jitter,
+                                rsqrt_ps::<S>(S::add_ps(xd2, S::mul_ps(yd, yd)), version),
+                            );
                             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
                             xd2 = S::mul_ps(xd, xd);
@@ -53,10 +76,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             distance = S::min_ps(new_distance, distance);
 
                             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+                            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
                         }
                         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+                        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
                     }
                 }
                 CellDistanceFunction::Manhattan => {
@@ -78,7 +101,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_ps(
                                 jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+                                rsqrt_ps::<S>(
+                                    S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -87,10 +113,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             distance = S::min_ps(new_distance, distance);
 
                             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+                            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
                         }
                         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+                        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
                     }
                 }
                 CellDistanceFunction::Natural => {
@@ -112,7 +138,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_ps(
                                 jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+                                rsqrt_ps::<S>(
+                                    S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -125,10 +154,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             distance = S::min_ps(new_distance, distance);
 
                             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+                            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
                         }
                         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+                        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
                     }
                 }
             }
@@ -156,7 +185,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_ps(
                                 jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+                                rsqrt_ps::<S>(
+                                    S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -169,10 +201,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
 
                             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+                            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
                         }
                         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+                        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
                     }
                 }
                 CellDistanceFunction::Manhattan => {
@@ -194,7 +226,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_ps(
                                 jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+                                rsqrt_ps::<S>(
+                                    S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -207,10 +242,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
 
                             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+                            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
                         }
                         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+                        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
                     }
                 }
                 CellDistanceFunction::Natural => {
@@ -232,7 +267,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_ps(
                                 jitter,
-                                S::rsqrt_ps(S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd))),
+                                rsqrt_ps::<S>(
+                                    S::add_ps(S::mul_ps(xd, xd), S::mul_ps(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                             yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -249,10 +287,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
 
                             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-                            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+                            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
                         }
                         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-                        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+                        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
                     }
                 }
             }
@@ -271,20 +309,44 @@ pub unsafe fn cellular_3d<S: Simd>(
     jitter: S::Vf32,
     seed: i32,
 ) -> S::Vf32 {
+    cellular_3d_versioned::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular_3d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    let [x_prime, y_prime, z_prime] = version.hash_primes_32();
     let mut distance = S::set1_ps(999999.0);
     let mut cell_value = S::setzero_ps();
 
-    let mut xc = S::sub_epi32(S::cvtps_epi32(x), S::set1_epi32(1));
-    let mut yc_base = S::sub_epi32(S::cvtps_epi32(y), S::set1_epi32(1));
-    let mut zc_base = S::sub_epi32(S::cvtps_epi32(z), S::set1_epi32(1));
+    let mut xc = S::sub_epi32(nearest_epi32::<S>(x, version), S::set1_epi32(1));
+    let mut yc_base = S::sub_epi32(nearest_epi32::<S>(y, version), S::set1_epi32(1));
+    let mut zc_base = S::sub_epi32(nearest_epi32::<S>(z, version), S::set1_epi32(1));
 
     let mut xcf = S::sub_ps(S::cvtepi32_ps(xc), x);
     let ycf_base = S::sub_ps(S::cvtepi32_ps(yc_base), y);
     let zcf_base = S::sub_ps(S::cvtepi32_ps(zc_base), z);
 
-    xc = S::mullo_epi32(xc, S::set1_epi32(X_PRIME_32));
-    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(Y_PRIME_32));
-    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(Z_PRIME_32));
+    xc = S::mullo_epi32(xc, S::set1_epi32(x_prime));
+    yc_base = S::mullo_epi32(yc_base, S::set1_epi32(y_prime));
+    zc_base = S::mullo_epi32(zc_base, S::set1_epi32(z_prime));
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -314,10 +376,13 @@ pub unsafe fn cellular_3d<S: Simd>(
                 );
                 let inv_mag = S::mul_ps(
                     jitter,
-                    S::rsqrt_ps(S::add_ps(
-                        S::mul_ps(xd, xd),
-                        S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
-                    )),
+                    rsqrt_ps::<S>(
+                        S::add_ps(
+                            S::mul_ps(xd, xd),
+                            S::add_ps(S::mul_ps(yd, yd), S::mul_ps(zd, zd)),
+                        ),
+                        version,
+                    ),
                 );
                 xd = S::add_ps(S::mul_ps(xd, inv_mag), xcf);
                 yd = S::add_ps(S::mul_ps(yd, inv_mag), ycf);
@@ -345,13 +410,13 @@ pub unsafe fn cellular_3d<S: Simd>(
                 distance = S::min_ps(new_distance, distance);
                 cell_value = S::blendv_ps(cell_value, new_cell_value, closer);
                 zcf = S::add_ps(ycf, S::set1_ps(1.0));
-                zc = S::add_epi32(yc, S::set1_epi32(Z_PRIME_32));
+                zc = S::add_epi32(yc, S::set1_epi32(z_prime));
             }
             ycf = S::add_ps(ycf, S::set1_ps(1.0));
-            yc = S::add_epi32(yc, S::set1_epi32(Y_PRIME_32));
+            yc = S::add_epi32(yc, S::set1_epi32(y_prime));
         }
         xcf = S::add_ps(xcf, S::set1_ps(1.0));
-        xc = S::add_epi32(xc, S::set1_epi32(X_PRIME_32));
+        xc = S::add_epi32(xc, S::set1_epi32(x_prime));
     }
 
     match return_type {
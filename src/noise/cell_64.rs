@@ -1,6 +1,7 @@
-use super::cellular_32::{BIT_10_MASK_64, HASH_2_FLOAT_64, X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
+use super::cellular_32::{BIT_10_MASK_64, HASH_2_FLOAT_64};
 use super::cellular_64::{hash_2d, hash_3d};
-use crate::{CellDistanceFunction, CellReturnType};
+use super::deterministic::{nearest_epi64, rsqrt_pd};
+use crate::{AlgorithmVersion, CellDistanceFunction, CellReturnType};
 
 use simdeez::Simd;
 
@@ -13,15 +14,37 @@ pub unsafe fn cellular_2d<S: Simd>(
     jitter: S::Vf64,
     seed: i64,
 ) -> S::Vf64 {
+    cellular_2d_versioned::<S>(
+        x,
+        y,
+        distance_function,
+        return_type,
+        jitter,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular_2d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    let [x_prime, y_prime, _] = version.hash_primes_64();
     let mut distance = S::set1_pd(999999.0);
-    let mut xc = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
-    let mut yc_base = S::sub_epi64(S::cvtpd_epi64(y), S::set1_epi64(1));
+    let mut xc = S::sub_epi64(nearest_epi64::<S>(x, version), S::set1_epi64(1));
+    let mut yc_base = S::sub_epi64(nearest_epi64::<S>(y, version), S::set1_epi64(1));
 
     let mut xcf = S::sub_pd(S::cvtepi64_pd(xc), x);
     let ycf_base = S::sub_pd(S::cvtepi64_pd(yc_base), y);
 
-    xc = S::mullo_epi64(xc, S::set1_epi64(X_PRIME_64));
-    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(Y_PRIME_64));
+    xc = S::mullo_epi64(xc, S::set1_epi64(x_prime));
+    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(y_prime));
     match return_type {
         CellReturnType::Distance => {
             match distance_function {
@@ -43,8 +66,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                                 S::set1_pd(511.5),
                             );
                             let mut xd2 = S::mul_pd(xd, xd);
-                            let inv_mag =
-                                S::mul_pd(jitter, S::rsqrt_pd(S::add_pd(xd2, S::mul_pd(yd, yd))));
+                            let inv_mag = S::mul_pd(
+                                jitter,
+                                rsqrt_pd::<S>(S::add_pd(xd2, S::mul_pd(yd, yd)), version),
+                            );
                             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
                             xd2 = S::mul_pd(xd, xd);
@@ -52,10 +77,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             distance = S::min_pd(new_distance, distance);
 
                             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+                            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
                         }
                         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+                        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
                     }
                 }
                 CellDistanceFunction::Manhattan => {
@@ -77,7 +102,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_pd(
                                 jitter,
-                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                                rsqrt_pd::<S>(
+                                    S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -86,10 +114,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             distance = S::min_pd(new_distance, distance);
 
                             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+                            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
                         }
                         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+                        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
                     }
                 }
                 CellDistanceFunction::Natural => {
@@ -111,7 +139,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_pd(
                                 jitter,
-                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                                rsqrt_pd::<S>(
+                                    S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -124,10 +155,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             distance = S::min_pd(new_distance, distance);
 
                             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+                            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
                         }
                         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+                        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
                     }
                 }
             }
@@ -155,7 +186,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_pd(
                                 jitter,
-                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                                rsqrt_pd::<S>(
+                                    S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -168,10 +202,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             cell_value = S::blendv_pd(cell_value, new_cell_value, closer);
 
                             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+                            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
                         }
                         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+                        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
                     }
                 }
                 CellDistanceFunction::Manhattan => {
@@ -193,7 +227,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_pd(
                                 jitter,
-                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                                rsqrt_pd::<S>(
+                                    S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -206,10 +243,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             cell_value = S::blendv_pd(cell_value, new_cell_value, closer);
 
                             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+                            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
                         }
                         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+                        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
                     }
                 }
                 CellDistanceFunction::Natural => {
@@ -231,7 +268,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             );
                             let inv_mag = S::mul_pd(
                                 jitter,
-                                S::rsqrt_pd(S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd))),
+                                rsqrt_pd::<S>(
+                                    S::add_pd(S::mul_pd(xd, xd), S::mul_pd(yd, yd)),
+                                    version,
+                                ),
                             );
                             xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                             yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -248,10 +288,10 @@ pub unsafe fn cellular_2d<S: Simd>(
                             cell_value = S::blendv_pd(cell_value, new_cell_value, closer);
 
                             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-                            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+                            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
                         }
                         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-                        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+                        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
                     }
                 }
             }
@@ -270,20 +310,44 @@ pub unsafe fn cellular_3d<S: Simd>(
     jitter: S::Vf64,
     seed: i64,
 ) -> S::Vf64 {
+    cellular_3d_versioned::<S>(
+        x,
+        y,
+        z,
+        distance_function,
+        return_type,
+        jitter,
+        seed,
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn cellular_3d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    distance_function: CellDistanceFunction,
+    return_type: CellReturnType,
+    jitter: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    let [x_prime, y_prime, z_prime] = version.hash_primes_64();
     let mut distance = S::set1_pd(999999.0);
     let mut cell_value = S::setzero_pd();
 
-    let mut xc = S::sub_epi64(S::cvtpd_epi64(x), S::set1_epi64(1));
-    let mut yc_base = S::sub_epi64(S::cvtpd_epi64(y), S::set1_epi64(1));
-    let mut zc_base = S::sub_epi64(S::cvtpd_epi64(z), S::set1_epi64(1));
+    let mut xc = S::sub_epi64(nearest_epi64::<S>(x, version), S::set1_epi64(1));
+    let mut yc_base = S::sub_epi64(nearest_epi64::<S>(y, version), S::set1_epi64(1));
+    let mut zc_base = S::sub_epi64(nearest_epi64::<S>(z, version), S::set1_epi64(1));
 
     let mut xcf = S::sub_pd(S::cvtepi64_pd(xc), x);
     let ycf_base = S::sub_pd(S::cvtepi64_pd(yc_base), y);
     let zcf_base = S::sub_pd(S::cvtepi64_pd(zc_base), z);
 
-    xc = S::mullo_epi64(xc, S::set1_epi64(X_PRIME_64));
-    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(Y_PRIME_64));
-    zc_base = S::mullo_epi64(zc_base, S::set1_epi64(Z_PRIME_64));
+    xc = S::mullo_epi64(xc, S::set1_epi64(x_prime));
+    yc_base = S::mullo_epi64(yc_base, S::set1_epi64(y_prime));
+    zc_base = S::mullo_epi64(zc_base, S::set1_epi64(z_prime));
 
     for _x in 0..3 {
         let mut ycf = ycf_base;
@@ -313,10 +377,13 @@ pub unsafe fn cellular_3d<S: Simd>(
                 );
                 let inv_mag = S::mul_pd(
                     jitter,
-                    S::rsqrt_pd(S::add_pd(
-                        S::mul_pd(xd, xd),
-                        S::add_pd(S::mul_pd(yd, yd), S::mul_pd(zd, zd)),
-                    )),
+                    rsqrt_pd::<S>(
+                        S::add_pd(
+                            S::mul_pd(xd, xd),
+                            S::add_pd(S::mul_pd(yd, yd), S::mul_pd(zd, zd)),
+                        ),
+                        version,
+                    ),
                 );
                 xd = S::add_pd(S::mul_pd(xd, inv_mag), xcf);
                 yd = S::add_pd(S::mul_pd(yd, inv_mag), ycf);
@@ -344,13 +411,13 @@ pub unsafe fn cellular_3d<S: Simd>(
                 distance = S::min_pd(new_distance, distance);
                 cell_value = S::blendv_pd(cell_value, new_cell_value, closer);
                 zcf = S::add_pd(ycf, S::set1_pd(1.0));
-                zc = S::add_epi64(yc, S::set1_epi64(Z_PRIME_64));
+                zc = S::add_epi64(yc, S::set1_epi64(z_prime));
             }
             ycf = S::add_pd(ycf, S::set1_pd(1.0));
-            yc = S::add_epi64(yc, S::set1_epi64(Y_PRIME_64));
+            yc = S::add_epi64(yc, S::set1_epi64(y_prime));
         }
         xcf = S::add_pd(xcf, S::set1_pd(1.0));
-        xc = S::add_epi64(xc, S::set1_epi64(X_PRIME_64));
+        xc = S::add_epi64(xc, S::set1_epi64(x_prime));
     }
 
     match return_type {
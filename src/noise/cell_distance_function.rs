@@ -1,4 +1,5 @@
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The function to use to compute distance between cells
 pub enum CellDistanceFunction {
     /// The actual straight line distance
@@ -1,4 +1,5 @@
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Determines what final value is returned for the cell noise
 pub enum CellReturnType {
     /// Will return solid colors in each cell
@@ -3,7 +3,7 @@
 //! Useful for writing your own SIMD-generic code for use cases not covered by the higher level
 //! interfaces.
 
-use std::f32;
+use core::f32;
 
 use simdeez::Simd;
 
@@ -21,9 +21,14 @@ pub const Y_PRIME_64: i64 = 31337;
 // It seems that b47708e459 had a typo and set this value to 6791.
 // That seems to be a type (not 100 % sure though), because it is also a prime.
 // This went unnoticed untill cce07cdc.
+// Changing any of these primes changes the output, so new primes are returned by
+// `AlgorithmVersion::hash_primes_32` for a new version only.
 pub const Z_PRIME_32: i32 = 6971;
 pub const Z_PRIME_64: i64 = 6971;
 
+pub const W_PRIME_32: i32 = 1013;
+pub const W_PRIME_64: i64 = 1013;
+
 #[inline(always)]
 pub unsafe fn hash_2d<S: Simd>(seed: i32, x: S::Vi32, y: S::Vi32) -> S::Vi32 {
     let mut hash = S::xor_epi32(x, S::set1_epi32(seed));
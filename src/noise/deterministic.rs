@@ -0,0 +1,96 @@
+//! Operations whose results differ between backends, and their exact replacements
+//!
+//! SSE and AVX2 approximate `rsqrt`, AVX2 fuses `fnmadd` into a single rounding, and the
+//! conversions to integers round ties differently in the scalar backend. For a
+//! deterministic `AlgorithmVersion` these are computed with correctly rounded operations
+//! only, so every backend, and every CPU, gives bit-identical noise. Other versions use
+//! the fastest instruction available.
+
+use crate::AlgorithmVersion;
+use simdeez::Simd;
+
+#[inline(always)]
+pub unsafe fn rsqrt_ps<S: Simd>(a: S::Vf32, version: AlgorithmVersion) -> S::Vf32 {
+    if version.is_deterministic() {
+        S::div_ps(S::set1_ps(1.0), S::sqrt_ps(a))
+    } else {
+        S::rsqrt_ps(a)
+    }
+}
+
+#[inline(always)]
+pub unsafe fn rsqrt_pd<S: Simd>(a: S::Vf64, version: AlgorithmVersion) -> S::Vf64 {
+    if version.is_deterministic() {
+        S::div_pd(S::set1_pd(1.0), S::sqrt_pd(a))
+    } else {
+        S::rsqrt_pd(a)
+    }
+}
+
+/// c - a * b
+#[inline(always)]
+pub unsafe fn fnmadd_ps<S: Simd>(
+    a: S::Vf32,
+    b: S::Vf32,
+    c: S::Vf32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    if version.is_deterministic() {
+        S::sub_ps(c, S::mul_ps(a, b))
+    } else {
+        S::fnmadd_ps(a, b, c)
+    }
+}
+
+/// c - a * b
+#[inline(always)]
+pub unsafe fn fnmadd_pd<S: Simd>(
+    a: S::Vf64,
+    b: S::Vf64,
+    c: S::Vf64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    if version.is_deterministic() {
+        S::sub_pd(c, S::mul_pd(a, b))
+    } else {
+        S::fnmadd_pd(a, b, c)
+    }
+}
+
+#[inline(always)]
+pub unsafe fn fast_floor_ps<S: Simd>(a: S::Vf32, version: AlgorithmVersion) -> S::Vf32 {
+    if version.is_deterministic() {
+        S::floor_ps(a)
+    } else {
+        S::fast_floor_ps(a)
+    }
+}
+
+#[inline(always)]
+pub unsafe fn fast_floor_pd<S: Simd>(a: S::Vf64, version: AlgorithmVersion) -> S::Vf64 {
+    if version.is_deterministic() {
+        S::floor_pd(a)
+    } else {
+        S::fast_floor_pd(a)
+    }
+}
+
+/// The nearest integer, with ties rounded up when deterministic
+#[inline(always)]
+pub unsafe fn nearest_epi32<S: Simd>(a: S::Vf32, version: AlgorithmVersion) -> S::Vi32 {
+    if version.is_deterministic() {
+        S::cvtps_epi32(S::floor_ps(S::add_ps(a, S::set1_ps(0.5))))
+    } else {
+        S::cvtps_epi32(a)
+    }
+}
+
+/// The nearest integer, with ties rounded up when deterministic
+#[inline(always)]
+pub unsafe fn nearest_epi64<S: Simd>(a: S::Vf64, version: AlgorithmVersion) -> S::Vi64 {
+    if version.is_deterministic() {
+        S::cvtpd_epi64(S::floor_pd(S::add_pd(a, S::set1_pd(0.5))))
+    } else {
+        S::cvtpd_epi64(a)
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
 use crate::noise::hash3d_32::hash3d;
 
 use simdeez::Simd;
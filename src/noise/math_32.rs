@@ -0,0 +1,130 @@
+//! SIMD approximations of transcendental functions that simdeez does not provide
+//!
+//! Accurate to a few ulps over the ranges the noise kernels use them for, not general purpose
+//! replacements for the std functions.
+
+use core::f32;
+
+use simdeez::Simd;
+
+/// e^x, inputs below -87 flush to 0
+#[inline(always)]
+pub unsafe fn exp_ps<S: Simd>(x: S::Vf32) -> S::Vf32 {
+    let x = S::max_ps(S::min_ps(x, S::set1_ps(88.0)), S::set1_ps(-87.0));
+    // e^x = 2^n * e^r with |r| <= ln(2) / 2
+    let n = S::floor_ps(S::add_ps(
+        S::mul_ps(x, S::set1_ps(f32::consts::LOG2_E)),
+        S::set1_ps(0.5),
+    ));
+    let r = S::sub_ps(x, S::mul_ps(n, S::set1_ps(f32::consts::LN_2)));
+
+    let mut p = S::set1_ps(1.0 / 720.0);
+    p = S::add_ps(S::mul_ps(p, r), S::set1_ps(1.0 / 120.0));
+    p = S::add_ps(S::mul_ps(p, r), S::set1_ps(1.0 / 24.0));
+    p = S::add_ps(S::mul_ps(p, r), S::set1_ps(1.0 / 6.0));
+    p = S::add_ps(S::mul_ps(p, r), S::set1_ps(0.5));
+    p = S::add_ps(S::mul_ps(p, r), S::set1_ps(1.0));
+    p = S::add_ps(S::mul_ps(p, r), S::set1_ps(1.0));
+
+    let pow2n = S::castepi32_ps(S::slli_epi32(
+        S::add_epi32(S::cvtps_epi32(n), S::set1_epi32(127)),
+        23,
+    ));
+    S::mul_ps(p, pow2n)
+}
+
+/// cos(2πx), for any finite x
+#[inline(always)]
+pub unsafe fn cos_2pi_ps<S: Simd>(x: S::Vf32) -> S::Vf32 {
+    // Reduce to a quarter period: t in [0, 0.5], and mirror [0.25, 0.5] onto [0, 0.25]
+    let t = S::abs_ps(S::sub_ps(x, S::floor_ps(S::add_ps(x, S::set1_ps(0.5)))));
+    let upper = S::cmpgt_ps(t, S::set1_ps(0.25));
+    let t = S::blendv_ps(t, S::sub_ps(S::set1_ps(0.5), t), upper);
+
+    let u = S::mul_ps(t, S::set1_ps(2.0 * f32::consts::PI));
+    let u2 = S::mul_ps(u, u);
+    let mut p = S::set1_ps(-1.0 / 3628800.0);
+    p = S::add_ps(S::mul_ps(p, u2), S::set1_ps(1.0 / 40320.0));
+    p = S::add_ps(S::mul_ps(p, u2), S::set1_ps(-1.0 / 720.0));
+    p = S::add_ps(S::mul_ps(p, u2), S::set1_ps(1.0 / 24.0));
+    p = S::add_ps(S::mul_ps(p, u2), S::set1_ps(-0.5));
+    p = S::add_ps(S::mul_ps(p, u2), S::set1_ps(1.0));
+
+    // cos(π - u) = -cos(u)
+    S::blendv_ps(p, S::sub_ps(S::setzero_ps(), p), upper)
+}
+
+/// Natural log, for positive finite x
+#[inline(always)]
+pub unsafe fn ln_ps<S: Simd>(x: S::Vf32) -> S::Vf32 {
+    // x = m * 2^e with m in [sqrt(1/2), sqrt(2))
+    let bits = S::castps_epi32(x);
+    let e = S::sub_epi32(S::srai_epi32(bits, 23), S::set1_epi32(127));
+    let m = S::castepi32_ps(S::or_epi32(
+        S::and_epi32(bits, S::set1_epi32(0x007f_ffff)),
+        S::set1_epi32(0x3f80_0000),
+    ));
+    let upper = S::cmpgt_ps(m, S::set1_ps(f32::consts::SQRT_2));
+    let m = S::blendv_ps(m, S::mul_ps(m, S::set1_ps(0.5)), upper);
+    let e = S::add_ps(S::cvtepi32_ps(e), S::and_ps(upper, S::set1_ps(1.0)));
+
+    // ln(m) = 2 atanh(s), s = (m - 1) / (m + 1), |s| <= 0.172
+    let s = S::div_ps(S::sub_ps(m, S::set1_ps(1.0)), S::add_ps(m, S::set1_ps(1.0)));
+    let s2 = S::mul_ps(s, s);
+    let mut p = S::set1_ps(2.0 / 9.0);
+    p = S::add_ps(S::mul_ps(p, s2), S::set1_ps(2.0 / 7.0));
+    p = S::add_ps(S::mul_ps(p, s2), S::set1_ps(2.0 / 5.0));
+    p = S::add_ps(S::mul_ps(p, s2), S::set1_ps(2.0 / 3.0));
+    p = S::add_ps(S::mul_ps(p, s2), S::set1_ps(2.0));
+    S::add_ps(S::mul_ps(p, s), S::mul_ps(e, S::set1_ps(f32::consts::LN_2)))
+}
+
+/// x^y for x >= 0, with 0^y = 0
+#[inline(always)]
+pub unsafe fn pow_ps<S: Simd>(x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+    let zero = S::cmpgt_ps(S::set1_ps(f32::MIN_POSITIVE), x);
+    let p = exp_ps::<S>(S::mul_ps(y, ln_ps::<S>(x)));
+    S::andnot_ps(zero, p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simdeez::scalar::{F32x1, Scalar};
+
+    #[test]
+    fn exp_matches_std() {
+        for i in -200..40 {
+            let x = i as f32 * 0.1;
+            let e = unsafe { exp_ps::<Scalar>(F32x1(x)).0 };
+            assert!((e - x.exp()).abs() <= x.exp() * 1e-5, "exp({}) = {}", x, e);
+        }
+    }
+
+    #[test]
+    fn ln_and_pow_match_std() {
+        for i in 1..400 {
+            let x = i as f32 * 0.037;
+            let l = unsafe { ln_ps::<Scalar>(F32x1(x)).0 };
+            assert!((l - x.ln()).abs() < 1e-5, "ln({}) = {}", x, l);
+            let p = unsafe { pow_ps::<Scalar>(F32x1(x), F32x1(1.7)).0 };
+            assert!(
+                (p - x.powf(1.7)).abs() <= x.powf(1.7) * 1e-4,
+                "{}^1.7 = {}",
+                x,
+                p
+            );
+        }
+        assert_eq!(unsafe { pow_ps::<Scalar>(F32x1(0.0), F32x1(2.0)).0 }, 0.0);
+    }
+
+    #[test]
+    fn cos_matches_std() {
+        for i in -500..500 {
+            let x = i as f32 * 0.0137;
+            let c = unsafe { cos_2pi_ps::<Scalar>(F32x1(x)).0 };
+            let expected = (2.0 * std::f64::consts::PI * x as f64).cos();
+            assert!((c as f64 - expected).abs() < 1e-5, "cos(2π{}) = {}", x, c);
+        }
+    }
+}
@@ -7,15 +7,22 @@ pub mod cell_distance_function;
 pub mod cell_return_type;
 mod cellular_32;
 mod cellular_64;
+pub mod deterministic;
 pub mod fbm_32;
 pub mod fbm_64;
+pub mod gabor_32;
 mod gradient_32;
 mod gradient_64;
 mod hash3d_32;
 mod hash3d_64;
+pub(crate) mod math_32;
+pub mod octave_params;
 pub mod ridge_32;
 pub mod ridge_64;
 pub mod simplex_32;
 pub mod simplex_64;
 pub mod turbulence_32;
 pub mod turbulence_64;
+pub mod wavelet_32;
+pub mod white_32;
+pub mod white_64;
@@ -0,0 +1,75 @@
+use crate::seed::mix64;
+use crate::transform::Transform;
+
+/// How the octaves of fractal noise are decorrelated from each other
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OctaveSeeding {
+    /// Every octave uses the seed of the settings, as in earlier releases. Octaves are
+    /// correlated, which shows near the origin where they all share lattice points.
+    Shared,
+    /// Octave n uses the seed `octave_seed(seed, n)`, the first octave keeps the seed
+    Derived,
+    /// Every octave uses the seed of the settings, but octaves after the first are
+    /// shifted by a pseudo random offset derived from the seed and octave
+    Offset,
+}
+
+impl Default for OctaveSeeding {
+    fn default() -> OctaveSeeding {
+        OctaveSeeding::Shared
+    }
+}
+
+/// Per-octave options for the fractal kernels (fbm, ridge and turbulence).
+/// The default leaves every octave exactly as the plain kernels compute it.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct OctaveParams {
+    /// Linear part is applied to the coordinates before each octave after the first,
+    /// so octave n is sampled in a domain rotated n times. This stops the lattice
+    /// artifacts of successive octaves from lining up. Translation is ignored.
+    pub rotation: Option<Transform>,
+    /// Seed or offset of each octave, see `OctaveSeeding`
+    pub seeding: OctaveSeeding,
+}
+
+impl OctaveParams {
+    /// The seed of octave `octave` of noise seeded with `seed`
+    #[inline(always)]
+    pub fn octave_seed(&self, seed: i32, octave: u8) -> i32 {
+        match self.seeding {
+            OctaveSeeding::Derived => octave_seed(seed, octave),
+            _ => seed,
+        }
+    }
+
+    /// Like `octave_seed`, for the 64 bit kernels, which take the same seeds widened
+    #[inline(always)]
+    pub fn octave_seed_64(&self, seed: i64, octave: u8) -> i64 {
+        match self.seeding {
+            OctaveSeeding::Derived => octave_seed(seed as i32, octave) as i64,
+            _ => seed,
+        }
+    }
+
+    /// The offset added to the x, y, z and w coordinates of an octave, in 0..256
+    #[inline(always)]
+    pub fn octave_offset(&self, seed: i64, octave: u8) -> Option<[f32; 4]> {
+        match self.seeding {
+            OctaveSeeding::Offset => {
+                let h = mix64((seed as u64) ^ ((octave as u64) << 56) ^ 0x6f66_6673_6574);
+                let axis = |i: u32| ((h >> (16 * i)) & 0xffff) as f32 * (256.0 / 65536.0);
+                Some([axis(0), axis(1), axis(2), axis(3)])
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The seed `OctaveSeeding::Derived` gives octave `octave` of noise seeded with `seed`
+pub fn octave_seed(seed: i32, octave: u8) -> i32 {
+    if octave == 0 {
+        return seed;
+    }
+    (mix64((seed as u32 as u64) | ((octave as u64) << 32)) >> 32) as i32
+}
@@ -1,24 +1,60 @@
-use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::deterministic::fnmadd_ps;
+use crate::noise::octave_params::OctaveParams;
+use crate::noise::simplex_32::{
+    simplex_1d_versioned, simplex_2d_versioned, simplex_3d_versioned, simplex_4d_versioned,
+};
+use crate::AlgorithmVersion;
 
 use simdeez::Simd;
 
 #[inline(always)]
 pub unsafe fn ridge_1d<S: Simd>(
+    x: S::Vf32,
+    lacunarity: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    ridge_1d_ext::<S>(
+        x,
+        lacunarity,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn ridge_1d_ext<S: Simd>(
     mut x: S::Vf32,
     lacunarity: S::Vf32,
     gain: S::Vf32,
     octaves: u8,
     seed: i32,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf32 {
     let mut amp = S::set1_ps(1.0);
-    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_1d::<S>(x, seed)));
+    let mut result = S::sub_ps(
+        S::set1_ps(1.0),
+        S::abs_ps(simplex_1d_versioned::<S>(x, seed, version)),
+    );
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed(seed, octave);
+        if let Some(o) = params.octave_offset(seed as i64, octave) {
+            x = S::add_ps(x, S::set1_ps(o[0]));
+        }
         x = S::mul_ps(x, lacunarity);
         amp = S::mul_ps(amp, gain);
         result = S::add_ps(
             result,
-            S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_1d::<S>(x, seed))),
+            S::sub_ps(
+                S::set1_ps(1.0),
+                S::abs_ps(simplex_1d_versioned::<S>(x, seed, version)),
+            ),
         );
     }
 
@@ -26,24 +62,44 @@ pub unsafe fn ridge_1d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn ridge_2d<S: Simd>(
+pub unsafe fn ridge_2d_ext<S: Simd>(
     mut x: S::Vf32,
     mut y: S::Vf32,
     lac: S::Vf32,
     gain: S::Vf32,
     octaves: u8,
     seed: i32,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf32 {
-    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_2d::<S>(x, y, seed)));
+    let mut result = S::sub_ps(
+        S::set1_ps(1.0),
+        S::abs_ps(simplex_2d_versioned::<S>(x, y, seed, version)),
+    );
     let mut amp = S::set1_ps(1.0);
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed(seed, octave);
+        if let Some(o) = params.octave_offset(seed as i64, octave) {
+            x = S::add_ps(x, S::set1_ps(o[0]));
+            y = S::add_ps(y, S::set1_ps(o[1]));
+        }
+        if let Some(r) = &params.rotation {
+            let (rx, ry) = r.rotate_2d::<S>(x, y);
+            x = rx;
+            y = ry;
+        }
         x = S::mul_ps(x, lac);
         y = S::mul_ps(y, lac);
         amp = S::mul_ps(amp, gain);
         result = S::add_ps(
             result,
-            S::fnmadd_ps(S::abs_ps(simplex_2d::<S>(x, y, seed)), amp, S::set1_ps(1.0)),
+            fnmadd_ps::<S>(
+                S::abs_ps(simplex_2d_versioned::<S>(x, y, seed, version)),
+                amp,
+                S::set1_ps(1.0),
+                version,
+            ),
         );
     }
 
@@ -51,7 +107,7 @@ pub unsafe fn ridge_2d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn ridge_3d<S: Simd>(
+pub unsafe fn ridge_3d_ext<S: Simd>(
     mut x: S::Vf32,
     mut y: S::Vf32,
     mut z: S::Vf32,
@@ -59,21 +115,39 @@ pub unsafe fn ridge_3d<S: Simd>(
     gain: S::Vf32,
     octaves: u8,
     seed: i32,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf32 {
-    let mut result = S::sub_ps(S::set1_ps(1.0), S::abs_ps(simplex_3d::<S>(x, y, z, seed)));
+    let mut result = S::sub_ps(
+        S::set1_ps(1.0),
+        S::abs_ps(simplex_3d_versioned::<S>(x, y, z, seed, version)),
+    );
     let mut amp = S::set1_ps(1.0);
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed(seed, octave);
+        if let Some(o) = params.octave_offset(seed as i64, octave) {
+            x = S::add_ps(x, S::set1_ps(o[0]));
+            y = S::add_ps(y, S::set1_ps(o[1]));
+            z = S::add_ps(z, S::set1_ps(o[2]));
+        }
+        if let Some(r) = &params.rotation {
+            let (rx, ry, rz) = r.rotate_3d::<S>(x, y, z);
+            x = rx;
+            y = ry;
+            z = rz;
+        }
         x = S::mul_ps(x, lac);
         y = S::mul_ps(y, lac);
         z = S::mul_ps(z, lac);
         amp = S::mul_ps(amp, gain);
         result = S::add_ps(
             result,
-            S::fnmadd_ps(
-                S::abs_ps(simplex_3d::<S>(x, y, z, seed)),
+            fnmadd_ps::<S>(
+                S::abs_ps(simplex_3d_versioned::<S>(x, y, z, seed, version)),
                 amp,
                 S::set1_ps(1.0),
+                version,
             ),
         );
     }
@@ -82,7 +156,7 @@ pub unsafe fn ridge_3d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn ridge_4d<S: Simd>(
+pub unsafe fn ridge_4d_ext<S: Simd>(
     mut x: S::Vf32,
     mut y: S::Vf32,
     mut z: S::Vf32,
@@ -91,14 +165,30 @@ pub unsafe fn ridge_4d<S: Simd>(
     gain: S::Vf32,
     octaves: u8,
     seed: i32,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf32 {
     let mut result = S::sub_ps(
         S::set1_ps(1.0),
-        S::abs_ps(simplex_4d::<S>(x, y, z, w, seed)),
+        S::abs_ps(simplex_4d_versioned::<S>(x, y, z, w, seed, version)),
     );
     let mut amp = S::set1_ps(1.0);
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed(seed, octave);
+        if let Some(o) = params.octave_offset(seed as i64, octave) {
+            x = S::add_ps(x, S::set1_ps(o[0]));
+            y = S::add_ps(y, S::set1_ps(o[1]));
+            z = S::add_ps(z, S::set1_ps(o[2]));
+            w = S::add_ps(w, S::set1_ps(o[3]));
+        }
+        if let Some(r) = &params.rotation {
+            let (rx, ry, rz, rw) = r.rotate_4d::<S>(x, y, z, w);
+            x = rx;
+            y = ry;
+            z = rz;
+            w = rw;
+        }
         x = S::mul_ps(x, lac);
         y = S::mul_ps(y, lac);
         z = S::mul_ps(z, lac);
@@ -108,10 +198,82 @@ pub unsafe fn ridge_4d<S: Simd>(
             result,
             S::sub_ps(
                 S::set1_ps(1.0),
-                S::abs_ps(S::mul_ps(simplex_4d::<S>(x, y, z, w, seed), amp)),
+                S::abs_ps(S::mul_ps(
+                    simplex_4d_versioned::<S>(x, y, z, w, seed, version),
+                    amp,
+                )),
             ),
         );
     }
 
     result
 }
+
+#[inline(always)]
+pub unsafe fn ridge_2d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    ridge_2d_ext::<S>(
+        x,
+        y,
+        lac,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn ridge_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    ridge_3d_ext::<S>(
+        x,
+        y,
+        z,
+        lac,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn ridge_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    lac: S::Vf32,
+    gain: S::Vf32,
+    octaves: u8,
+    seed: i32,
+) -> S::Vf32 {
+    ridge_4d_ext::<S>(
+        x,
+        y,
+        z,
+        w,
+        lac,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
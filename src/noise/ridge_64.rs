@@ -1,24 +1,60 @@
-use crate::noise::simplex_64::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
+use crate::noise::deterministic::fnmadd_pd;
+use crate::noise::octave_params::OctaveParams;
+use crate::noise::simplex_64::{
+    simplex_1d_versioned, simplex_2d_versioned, simplex_3d_versioned, simplex_4d_versioned,
+};
+use crate::AlgorithmVersion;
 
 use simdeez::Simd;
 
 #[inline(always)]
 pub unsafe fn ridge_1d<S: Simd>(
+    x: S::Vf64,
+    lacunarity: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_1d_ext::<S>(
+        x,
+        lacunarity,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn ridge_1d_ext<S: Simd>(
     mut x: S::Vf64,
     lacunarity: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf64 {
     let mut amp = S::set1_pd(1.0);
-    let mut result = S::sub_pd(S::set1_pd(1.0), S::abs_pd(simplex_1d::<S>(x, seed)));
+    let mut result = S::sub_pd(
+        S::set1_pd(1.0),
+        S::abs_pd(simplex_1d_versioned::<S>(x, seed, version)),
+    );
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed_64(seed, octave);
+        if let Some(o) = params.octave_offset(seed, octave) {
+            x = S::add_pd(x, S::set1_pd(o[0] as f64));
+        }
         x = S::mul_pd(x, lacunarity);
         amp = S::mul_pd(amp, gain);
         result = S::add_pd(
             result,
-            S::sub_pd(S::set1_pd(1.0), S::abs_pd(simplex_1d::<S>(x, seed))),
+            S::sub_pd(
+                S::set1_pd(1.0),
+                S::abs_pd(simplex_1d_versioned::<S>(x, seed, version)),
+            ),
         );
     }
 
@@ -26,24 +62,44 @@ pub unsafe fn ridge_1d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn ridge_2d<S: Simd>(
+pub unsafe fn ridge_2d_ext<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     lac: S::Vf64,
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf64 {
-    let mut result = S::sub_pd(S::set1_pd(1.0), S::abs_pd(simplex_2d::<S>(x, y, seed)));
+    let mut result = S::sub_pd(
+        S::set1_pd(1.0),
+        S::abs_pd(simplex_2d_versioned::<S>(x, y, seed, version)),
+    );
     let mut amp = S::set1_pd(1.0);
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed_64(seed, octave);
+        if let Some(o) = params.octave_offset(seed, octave) {
+            x = S::add_pd(x, S::set1_pd(o[0] as f64));
+            y = S::add_pd(y, S::set1_pd(o[1] as f64));
+        }
+        if let Some(r) = &params.rotation {
+            let (rx, ry) = r.rotate_2d_f64::<S>(x, y);
+            x = rx;
+            y = ry;
+        }
         x = S::mul_pd(x, lac);
         y = S::mul_pd(y, lac);
         amp = S::mul_pd(amp, gain);
         result = S::add_pd(
             result,
-            S::fnmadd_pd(S::abs_pd(simplex_2d::<S>(x, y, seed)), amp, S::set1_pd(1.0)),
+            fnmadd_pd::<S>(
+                S::abs_pd(simplex_2d_versioned::<S>(x, y, seed, version)),
+                amp,
+                S::set1_pd(1.0),
+                version,
+            ),
         );
     }
 
@@ -51,7 +107,7 @@ pub unsafe fn ridge_2d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn ridge_3d<S: Simd>(
+pub unsafe fn ridge_3d_ext<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     mut z: S::Vf64,
@@ -59,21 +115,39 @@ pub unsafe fn ridge_3d<S: Simd>(
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf64 {
-    let mut result = S::sub_pd(S::set1_pd(1.0), S::abs_pd(simplex_3d::<S>(x, y, z, seed)));
+    let mut result = S::sub_pd(
+        S::set1_pd(1.0),
+        S::abs_pd(simplex_3d_versioned::<S>(x, y, z, seed, version)),
+    );
     let mut amp = S::set1_pd(1.0);
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed_64(seed, octave);
+        if let Some(o) = params.octave_offset(seed, octave) {
+            x = S::add_pd(x, S::set1_pd(o[0] as f64));
+            y = S::add_pd(y, S::set1_pd(o[1] as f64));
+            z = S::add_pd(z, S::set1_pd(o[2] as f64));
+        }
+        if let Some(r) = &params.rotation {
+            let (rx, ry, rz) = r.rotate_3d_f64::<S>(x, y, z);
+            x = rx;
+            y = ry;
+            z = rz;
+        }
         x = S::mul_pd(x, lac);
         y = S::mul_pd(y, lac);
         z = S::mul_pd(z, lac);
         amp = S::mul_pd(amp, gain);
         result = S::add_pd(
             result,
-            S::fnmadd_pd(
-                S::abs_pd(simplex_3d::<S>(x, y, z, seed)),
+            fnmadd_pd::<S>(
+                S::abs_pd(simplex_3d_versioned::<S>(x, y, z, seed, version)),
                 amp,
                 S::set1_pd(1.0),
+                version,
             ),
         );
     }
@@ -82,7 +156,7 @@ pub unsafe fn ridge_3d<S: Simd>(
 }
 
 #[inline(always)]
-pub unsafe fn ridge_4d<S: Simd>(
+pub unsafe fn ridge_4d_ext<S: Simd>(
     mut x: S::Vf64,
     mut y: S::Vf64,
     mut z: S::Vf64,
@@ -91,14 +165,30 @@ pub unsafe fn ridge_4d<S: Simd>(
     gain: S::Vf64,
     octaves: u8,
     seed: i64,
+    params: &OctaveParams,
+    version: AlgorithmVersion,
 ) -> S::Vf64 {
     let mut result = S::sub_pd(
         S::set1_pd(1.0),
-        S::abs_pd(simplex_4d::<S>(x, y, z, w, seed)),
+        S::abs_pd(simplex_4d_versioned::<S>(x, y, z, w, seed, version)),
     );
     let mut amp = S::set1_pd(1.0);
 
-    for _ in 1..octaves {
+    for octave in 1..octaves {
+        let seed = params.octave_seed_64(seed, octave);
+        if let Some(o) = params.octave_offset(seed, octave) {
+            x = S::add_pd(x, S::set1_pd(o[0] as f64));
+            y = S::add_pd(y, S::set1_pd(o[1] as f64));
+            z = S::add_pd(z, S::set1_pd(o[2] as f64));
+            w = S::add_pd(w, S::set1_pd(o[3] as f64));
+        }
+        if let Some(r) = &params.rotation {
+            let (rx, ry, rz, rw) = r.rotate_4d_f64::<S>(x, y, z, w);
+            x = rx;
+            y = ry;
+            z = rz;
+            w = rw;
+        }
         x = S::mul_pd(x, lac);
         y = S::mul_pd(y, lac);
         z = S::mul_pd(z, lac);
@@ -108,10 +198,82 @@ pub unsafe fn ridge_4d<S: Simd>(
             result,
             S::sub_pd(
                 S::set1_pd(1.0),
-                S::abs_pd(S::mul_pd(simplex_4d::<S>(x, y, z, w, seed), amp)),
+                S::abs_pd(S::mul_pd(
+                    simplex_4d_versioned::<S>(x, y, z, w, seed, version),
+                    amp,
+                )),
             ),
         );
     }
 
     result
 }
+
+#[inline(always)]
+pub unsafe fn ridge_2d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_2d_ext::<S>(
+        x,
+        y,
+        lac,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn ridge_3d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_3d_ext::<S>(
+        x,
+        y,
+        z,
+        lac,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
+
+#[inline(always)]
+pub unsafe fn ridge_4d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    w: S::Vf64,
+    lac: S::Vf64,
+    gain: S::Vf64,
+    octaves: u8,
+    seed: i64,
+) -> S::Vf64 {
+    ridge_4d_ext::<S>(
+        x,
+        y,
+        z,
+        w,
+        lac,
+        gain,
+        octaves,
+        seed,
+        &OctaveParams::default(),
+        AlgorithmVersion::LATEST,
+    )
+}
@@ -3,13 +3,14 @@
 //! Useful for writing your own SIMD-generic code for use cases not covered by the higher level
 //! interfaces.
 
-use crate::noise::cellular_32::{X_PRIME_32, Y_PRIME_32, Z_PRIME_32};
+use crate::noise::deterministic::{fast_floor_ps, fnmadd_ps};
 use crate::noise::gradient_32::{grad1, grad2, grad3d, grad3d_dot, grad4};
+use crate::AlgorithmVersion;
 
 use simdeez::Simd;
 
-use std::f32;
-use std::f64;
+use core::f32;
+use core::f64;
 
 /// Skew factor for 2D simplex noise
 const F2_32: f32 = 0.36602540378;
@@ -71,8 +72,18 @@ const PERM: [i32; 512] = [
 /// Like `simplex_1d`, but also computes the derivative
 #[inline(always)]
 pub unsafe fn simplex_1d_deriv<S: Simd>(x: S::Vf32, seed: i32) -> (S::Vf32, S::Vf32) {
+    simplex_1d_deriv_versioned::<S>(x, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_1d_deriv`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_1d_deriv_versioned<S: Simd>(
+    x: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> (S::Vf32, S::Vf32) {
     // Gradients are selected deterministically based on the whole part of `x`
-    let ips = S::fast_floor_ps(x);
+    let ips = fast_floor_ps::<S>(x, version);
     let mut i0 = S::cvtps_epi32(ips);
     let i1 = S::and_epi32(S::add_epi32(i0, S::set1_epi32(1)), S::set1_epi32(0xff));
 
@@ -127,7 +138,17 @@ pub unsafe fn simplex_1d_deriv<S: Simd>(x: S::Vf32, seed: i32) -> (S::Vf32, S::V
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub unsafe fn simplex_1d<S: Simd>(x: S::Vf32, seed: i32) -> S::Vf32 {
-    simplex_1d_deriv::<S>(x, seed).0
+    simplex_1d_versioned::<S>(x, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_1d`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_1d_versioned<S: Simd>(
+    x: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    simplex_1d_deriv_versioned::<S>(x, seed, version).0
 }
 
 /// Samples 2-dimensional simplex noise
@@ -135,7 +156,18 @@ pub unsafe fn simplex_1d<S: Simd>(x: S::Vf32, seed: i32) -> S::Vf32 {
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub unsafe fn simplex_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
-    simplex_2d_deriv::<S>(x, y, seed).0
+    simplex_2d_versioned::<S>(x, y, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_2d`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_2d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    simplex_2d_deriv_versioned::<S>(x, y, seed, version).0
 }
 
 /// Like `simplex_2d`, but also computes the derivative
@@ -144,6 +176,17 @@ pub unsafe fn simplex_2d_deriv<S: Simd>(
     x: S::Vf32,
     y: S::Vf32,
     seed: i32,
+) -> (S::Vf32, [S::Vf32; 2]) {
+    simplex_2d_deriv_versioned::<S>(x, y, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_2d_deriv`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_2d_deriv_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
 ) -> (S::Vf32, [S::Vf32; 2]) {
     // Skew to distort simplexes with side length sqrt(2)/sqrt(3) until they make up
     // squares
@@ -194,9 +237,24 @@ pub unsafe fn simplex_2d_deriv<S: Simd>(
 
     // Weights associated with the gradients at each corner
     // These FMA operations are equivalent to: let t = 0.5 - x*x - y*y
-    let mut t0 = S::fnmadd_ps(y0, y0, S::fnmadd_ps(x0, x0, S::set1_ps(0.5)));
-    let mut t1 = S::fnmadd_ps(y1, y1, S::fnmadd_ps(x1, x1, S::set1_ps(0.5)));
-    let mut t2 = S::fnmadd_ps(y2, y2, S::fnmadd_ps(x2, x2, S::set1_ps(0.5)));
+    let mut t0 = fnmadd_ps::<S>(
+        y0,
+        y0,
+        fnmadd_ps::<S>(x0, x0, S::set1_ps(0.5), version),
+        version,
+    );
+    let mut t1 = fnmadd_ps::<S>(
+        y1,
+        y1,
+        fnmadd_ps::<S>(x1, x1, S::set1_ps(0.5), version),
+        version,
+    );
+    let mut t2 = fnmadd_ps::<S>(
+        y2,
+        y2,
+        fnmadd_ps::<S>(x2, x2, S::set1_ps(0.5), version),
+        version,
+    );
 
     // Zero out negative weights
     t0 &= S::cmpge_ps(t0, S::setzero_ps());
@@ -249,7 +307,19 @@ pub unsafe fn simplex_2d_deriv<S: Simd>(
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub unsafe fn simplex_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
-    simplex_3d_deriv::<S>(x, y, z, seed).0
+    simplex_3d_versioned::<S>(x, y, z, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_3d`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_3d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> S::Vf32 {
+    simplex_3d_deriv_versioned::<S>(x, y, z, seed, version).0
 }
 
 /// Like `simplex_3d`, but also computes the derivative
@@ -260,16 +330,29 @@ pub unsafe fn simplex_3d_deriv<S: Simd>(
     z: S::Vf32,
     seed: i32,
 ) -> (S::Vf32, [S::Vf32; 3]) {
+    simplex_3d_deriv_versioned::<S>(x, y, z, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_3d_deriv`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_3d_deriv_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    seed: i32,
+    version: AlgorithmVersion,
+) -> (S::Vf32, [S::Vf32; 3]) {
+    let [x_prime, y_prime, z_prime] = version.hash_primes_32();
     // Find skewed simplex grid coordinates associated with the input coordinates
     let f = S::mul_ps(S::set1_ps(F3_32), S::add_ps(S::add_ps(x, y), z));
-    let mut x0 = S::fast_floor_ps(S::add_ps(x, f));
-    let mut y0 = S::fast_floor_ps(S::add_ps(y, f));
-    let mut z0 = S::fast_floor_ps(S::add_ps(z, f));
+    let mut x0 = fast_floor_ps::<S>(S::add_ps(x, f), version);
+    let mut y0 = fast_floor_ps::<S>(S::add_ps(y, f), version);
+    let mut z0 = fast_floor_ps::<S>(S::add_ps(z, f), version);
 
     // Integer grid coordinates
-    let i = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(X_PRIME_32));
-    let j = S::mullo_epi32(S::cvtps_epi32(y0), S::set1_epi32(Y_PRIME_32));
-    let k = S::mullo_epi32(S::cvtps_epi32(z0), S::set1_epi32(Z_PRIME_32));
+    let i = S::mullo_epi32(S::cvtps_epi32(x0), S::set1_epi32(x_prime));
+    let j = S::mullo_epi32(S::cvtps_epi32(y0), S::set1_epi32(y_prime));
+    let k = S::mullo_epi32(S::cvtps_epi32(z0), S::set1_epi32(z_prime));
 
     // Compute distance from first simplex vertex to input coordinates
     let g = S::mul_ps(S::set1_ps(G3_32), S::add_ps(S::add_ps(x0, y0), z0));
@@ -359,40 +442,22 @@ pub unsafe fn simplex_3d_deriv<S: Simd>(
     let g0 = grad3d_dot::<S>(seed, i, j, k, x0, y0, z0);
     let v0 = t40 * g0;
 
-    let v1x = S::add_epi32(
-        i,
-        S::and_epi32(S::castps_epi32(i1), S::set1_epi32(X_PRIME_32)),
-    );
-    let v1y = S::add_epi32(
-        j,
-        S::and_epi32(S::castps_epi32(j1), S::set1_epi32(Y_PRIME_32)),
-    );
-    let v1z = S::add_epi32(
-        k,
-        S::and_epi32(S::castps_epi32(k1), S::set1_epi32(Z_PRIME_32)),
-    );
+    let v1x = S::add_epi32(i, S::and_epi32(S::castps_epi32(i1), S::set1_epi32(x_prime)));
+    let v1y = S::add_epi32(j, S::and_epi32(S::castps_epi32(j1), S::set1_epi32(y_prime)));
+    let v1z = S::add_epi32(k, S::and_epi32(S::castps_epi32(k1), S::set1_epi32(z_prime)));
     let g1 = grad3d_dot::<S>(seed, v1x, v1y, v1z, x1, y1, z1);
     let v1 = t41 * g1;
 
-    let v2x = S::add_epi32(
-        i,
-        S::and_epi32(S::castps_epi32(i2), S::set1_epi32(X_PRIME_32)),
-    );
-    let v2y = S::add_epi32(
-        j,
-        S::and_epi32(S::castps_epi32(j2), S::set1_epi32(Y_PRIME_32)),
-    );
-    let v2z = S::add_epi32(
-        k,
-        S::and_epi32(S::castps_epi32(k2), S::set1_epi32(Z_PRIME_32)),
-    );
+    let v2x = S::add_epi32(i, S::and_epi32(S::castps_epi32(i2), S::set1_epi32(x_prime)));
+    let v2y = S::add_epi32(j, S::and_epi32(S::castps_epi32(j2), S::set1_epi32(y_prime)));
+    let v2z = S::add_epi32(k, S::and_epi32(S::castps_epi32(k2), S::set1_epi32(z_prime)));
     let g2 = grad3d_dot::<S>(seed, v2x, v2y, v2z, x2, y2, z2);
     let v2 = t42 * g2;
 
     //SIMDf v3 = SIMDf_MASK(n3, SIMDf_MUL(SIMDf_MUL(t3, t3), FUNC(GradCoord)(seed, SIMDi_ADD(i, SIMDi_NUM(xPrime)), SIMDi_ADD(j, SIMDi_NUM(yPrime)), SIMDi_ADD(k, SIMDi_NUM(zPrime)), x3, y3, z3)));
-    let v3x = S::add_epi32(i, S::set1_epi32(X_PRIME_32));
-    let v3y = S::add_epi32(j, S::set1_epi32(Y_PRIME_32));
-    let v3z = S::add_epi32(k, S::set1_epi32(Z_PRIME_32));
+    let v3x = S::add_epi32(i, S::set1_epi32(x_prime));
+    let v3y = S::add_epi32(j, S::set1_epi32(y_prime));
+    let v3z = S::add_epi32(k, S::set1_epi32(z_prime));
     //define SIMDf_MASK(m,a) SIMDf_AND(SIMDf_CAST_TO_FLOAT(m),a)
     let g3 = grad3d_dot::<S>(seed, v3x, v3y, v3z, x3, y3, z3);
     let v3 = t43 * g3;
@@ -665,6 +730,20 @@ pub unsafe fn simplex_4d<S: Simd>(
     S::add_ps(n0, S::add_ps(n1, S::add_ps(n2, S::add_ps(n3, n4)))) * S::set1_ps(62.77772078955791)
 }
 
+/// Like `simplex_4d`, taking the version to use like the other dimensions. No
+/// operation of 4d simplex noise differs between versions.
+#[inline(always)]
+pub unsafe fn simplex_4d_versioned<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+    _version: AlgorithmVersion,
+) -> S::Vf32 {
+    simplex_4d::<S>(x, y, z, w, seed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
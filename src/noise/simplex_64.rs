@@ -1,8 +1,9 @@
+use crate::noise::deterministic::{fast_floor_pd, fnmadd_pd};
 use crate::noise::gradient_64::{grad1, grad2, grad3d, grad4};
+use crate::AlgorithmVersion;
 
 use simdeez::Simd;
 
-use crate::noise::cellular_32::{X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
 use crate::noise::gradient_64::grad3d_dot;
 use crate::noise::simplex_32::{
     F2_64, F3_64, F4_64, G22_64, G24_64, G2_64, G33_64, G34_64, G3_64, G44_64, G4_64,
@@ -39,8 +40,18 @@ const PERM64: [i64; 512] = [
 /// Like `simplex_1d`, but also computes the derivative
 #[inline(always)]
 pub unsafe fn simplex_1d_deriv<S: Simd>(x: S::Vf64, seed: i64) -> (S::Vf64, S::Vf64) {
+    simplex_1d_deriv_versioned::<S>(x, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_1d_deriv`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_1d_deriv_versioned<S: Simd>(
+    x: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> (S::Vf64, S::Vf64) {
     // Gradients are selected deterministically based on the whole part of `x`
-    let ips = S::fast_floor_pd(x);
+    let ips = fast_floor_pd::<S>(x, version);
     let mut i0 = S::cvtpd_epi64(ips);
     let i1 = S::and_epi64(S::add_epi64(i0, S::set1_epi64(1)), S::set1_epi64(0xff));
 
@@ -95,7 +106,17 @@ pub unsafe fn simplex_1d_deriv<S: Simd>(x: S::Vf64, seed: i64) -> (S::Vf64, S::V
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub unsafe fn simplex_1d<S: Simd>(x: S::Vf64, seed: i64) -> S::Vf64 {
-    simplex_1d_deriv::<S>(x, seed).0
+    simplex_1d_versioned::<S>(x, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_1d`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_1d_versioned<S: Simd>(
+    x: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    simplex_1d_deriv_versioned::<S>(x, seed, version).0
 }
 
 /// Samples 2-dimensional simplex noise
@@ -103,7 +124,18 @@ pub unsafe fn simplex_1d<S: Simd>(x: S::Vf64, seed: i64) -> S::Vf64 {
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub unsafe fn simplex_2d<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> S::Vf64 {
-    simplex_2d_deriv::<S>(x, y, seed).0
+    simplex_2d_versioned::<S>(x, y, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_2d`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_2d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    simplex_2d_deriv_versioned::<S>(x, y, seed, version).0
 }
 
 /// Like `simplex_2d`, but also computes the derivative
@@ -112,6 +144,17 @@ pub unsafe fn simplex_2d_deriv<S: Simd>(
     x: S::Vf64,
     y: S::Vf64,
     seed: i64,
+) -> (S::Vf64, [S::Vf64; 2]) {
+    simplex_2d_deriv_versioned::<S>(x, y, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_2d_deriv`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_2d_deriv_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
 ) -> (S::Vf64, [S::Vf64; 2]) {
     // Skew to distort simplexes with side length sqrt(2)/sqrt(3) until they make up
     // squares
@@ -162,9 +205,24 @@ pub unsafe fn simplex_2d_deriv<S: Simd>(
 
     // Weights associated with the gradients at each corner
     // These FMA operations are equivalent to: let t = 0.5 - x*x - y*y
-    let mut t0 = S::fnmadd_pd(y0, y0, S::fnmadd_pd(x0, x0, S::set1_pd(0.5)));
-    let mut t1 = S::fnmadd_pd(y1, y1, S::fnmadd_pd(x1, x1, S::set1_pd(0.5)));
-    let mut t2 = S::fnmadd_pd(y2, y2, S::fnmadd_pd(x2, x2, S::set1_pd(0.5)));
+    let mut t0 = fnmadd_pd::<S>(
+        y0,
+        y0,
+        fnmadd_pd::<S>(x0, x0, S::set1_pd(0.5), version),
+        version,
+    );
+    let mut t1 = fnmadd_pd::<S>(
+        y1,
+        y1,
+        fnmadd_pd::<S>(x1, x1, S::set1_pd(0.5), version),
+        version,
+    );
+    let mut t2 = fnmadd_pd::<S>(
+        y2,
+        y2,
+        fnmadd_pd::<S>(x2, x2, S::set1_pd(0.5), version),
+        version,
+    );
 
     // Zero out negative weights
     t0 &= S::cmpge_pd(t0, S::setzero_pd());
@@ -217,7 +275,19 @@ pub unsafe fn simplex_2d_deriv<S: Simd>(
 /// Produces a value -1 ≤ n ≤ 1.
 #[inline(always)]
 pub unsafe fn simplex_3d<S: Simd>(x: S::Vf64, y: S::Vf64, z: S::Vf64, seed: i64) -> S::Vf64 {
-    simplex_3d_deriv::<S>(x, y, z, seed).0
+    simplex_3d_versioned::<S>(x, y, z, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_3d`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_3d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> S::Vf64 {
+    simplex_3d_deriv_versioned::<S>(x, y, z, seed, version).0
 }
 
 /// Like `simplex_3d`, but also computes the derivative
@@ -228,16 +298,29 @@ pub unsafe fn simplex_3d_deriv<S: Simd>(
     z: S::Vf64,
     seed: i64,
 ) -> (S::Vf64, [S::Vf64; 3]) {
+    simplex_3d_deriv_versioned::<S>(x, y, z, seed, AlgorithmVersion::LATEST)
+}
+
+/// Like `simplex_3d_deriv`, but with the operations of `version`
+#[inline(always)]
+pub unsafe fn simplex_3d_deriv_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    seed: i64,
+    version: AlgorithmVersion,
+) -> (S::Vf64, [S::Vf64; 3]) {
+    let [x_prime, y_prime, z_prime] = version.hash_primes_64();
     // Find skewed simplex grid coordinates associated with the input coordinates
     let f = S::mul_pd(S::set1_pd(F3_64), S::add_pd(S::add_pd(x, y), z));
-    let mut x0 = S::fast_floor_pd(S::add_pd(x, f));
-    let mut y0 = S::fast_floor_pd(S::add_pd(y, f));
-    let mut z0 = S::fast_floor_pd(S::add_pd(z, f));
+    let mut x0 = fast_floor_pd::<S>(S::add_pd(x, f), version);
+    let mut y0 = fast_floor_pd::<S>(S::add_pd(y, f), version);
+    let mut z0 = fast_floor_pd::<S>(S::add_pd(z, f), version);
 
     // Integer grid coordinates
-    let i = S::mullo_epi64(S::cvtpd_epi64(x0), S::set1_epi64(X_PRIME_64));
-    let j = S::mullo_epi64(S::cvtpd_epi64(y0), S::set1_epi64(Y_PRIME_64));
-    let k = S::mullo_epi64(S::cvtpd_epi64(z0), S::set1_epi64(Z_PRIME_64));
+    let i = S::mullo_epi64(S::cvtpd_epi64(x0), S::set1_epi64(x_prime));
+    let j = S::mullo_epi64(S::cvtpd_epi64(y0), S::set1_epi64(y_prime));
+    let k = S::mullo_epi64(S::cvtpd_epi64(z0), S::set1_epi64(z_prime));
 
     // Compute distance from first simplex vertex to input coordinates
     let g = S::mul_pd(S::set1_pd(G3_64), S::add_pd(S::add_pd(x0, y0), z0));
@@ -327,40 +410,22 @@ pub unsafe fn simplex_3d_deriv<S: Simd>(
     let g0 = grad3d_dot::<S>(seed, i, j, k, x0, y0, z0);
     let v0 = t40 * g0;
 
-    let v1x = S::add_epi64(
-        i,
-        S::and_epi64(S::castpd_epi64(i1), S::set1_epi64(X_PRIME_64)),
-    );
-    let v1y = S::add_epi64(
-        j,
-        S::and_epi64(S::castpd_epi64(j1), S::set1_epi64(Y_PRIME_64)),
-    );
-    let v1z = S::add_epi64(
-        k,
-        S::and_epi64(S::castpd_epi64(k1), S::set1_epi64(Z_PRIME_64)),
-    );
+    let v1x = S::add_epi64(i, S::and_epi64(S::castpd_epi64(i1), S::set1_epi64(x_prime)));
+    let v1y = S::add_epi64(j, S::and_epi64(S::castpd_epi64(j1), S::set1_epi64(y_prime)));
+    let v1z = S::add_epi64(k, S::and_epi64(S::castpd_epi64(k1), S::set1_epi64(z_prime)));
     let g1 = grad3d_dot::<S>(seed, v1x, v1y, v1z, x1, y1, z1);
     let v1 = t41 * g1;
 
-    let v2x = S::add_epi64(
-        i,
-        S::and_epi64(S::castpd_epi64(i2), S::set1_epi64(X_PRIME_64)),
-    );
-    let v2y = S::add_epi64(
-        j,
-        S::and_epi64(S::castpd_epi64(j2), S::set1_epi64(Y_PRIME_64)),
-    );
-    let v2z = S::add_epi64(
-        k,
-        S::and_epi64(S::castpd_epi64(k2), S::set1_epi64(Z_PRIME_64)),
-    );
+    let v2x = S::add_epi64(i, S::and_epi64(S::castpd_epi64(i2), S::set1_epi64(x_prime)));
+    let v2y = S::add_epi64(j, S::and_epi64(S::castpd_epi64(j2), S::set1_epi64(y_prime)));
+    let v2z = S::add_epi64(k, S::and_epi64(S::castpd_epi64(k2), S::set1_epi64(z_prime)));
     let g2 = grad3d_dot::<S>(seed, v2x, v2y, v2z, x2, y2, z2);
     let v2 = t42 * g2;
 
     //SIMDf v3 = SIMDf_MASK(n3, SIMDf_MUL(SIMDf_MUL(t3, t3), FUNC(GradCoord)(seed, SIMDi_ADD(i, SIMDi_NUM(xPrime)), SIMDi_ADD(j, SIMDi_NUM(yPrime)), SIMDi_ADD(k, SIMDi_NUM(zPrime)), x3, y3, z3)));
-    let v3x = S::add_epi64(i, S::set1_epi64(X_PRIME_64));
-    let v3y = S::add_epi64(j, S::set1_epi64(Y_PRIME_64));
-    let v3z = S::add_epi64(k, S::set1_epi64(Z_PRIME_64));
+    let v3x = S::add_epi64(i, S::set1_epi64(x_prime));
+    let v3y = S::add_epi64(j, S::set1_epi64(y_prime));
+    let v3z = S::add_epi64(k, S::set1_epi64(z_prime));
     //define SIMDf_MASK(m,a) SIMDf_AND(SIMDf_CAST_TO_FLOAT(m),a)
     let g3 = grad3d_dot::<S>(seed, v3x, v3y, v3z, x3, y3, z3);
     let v3 = t43 * g3;
@@ -632,6 +697,20 @@ pub unsafe fn simplex_4d<S: Simd>(
     S::add_pd(n0, S::add_pd(n1, S::add_pd(n2, S::add_pd(n3, n4)))) * S::set1_pd(62.77772078955791)
 }
 
+/// Like `simplex_4d`, taking the version to use like the other dimensions. No
+/// operation of 4d simplex noise differs between versions.
+#[inline(always)]
+pub unsafe fn simplex_4d_versioned<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    w: S::Vf64,
+    seed: i64,
+    _version: AlgorithmVersion,
+) -> S::Vf64 {
+    simplex_4d::<S>(x, y, z, w, seed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
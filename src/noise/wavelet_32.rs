@@ -0,0 +1,501 @@
+//! Low-level wavelet noise primitives
+//!
+//! Wavelet noise as described by Cook and DeRose, "Wavelet Noise" (SIGGRAPH 2005). A tile of
+//! random coefficients is split into its coarse and fine halves with a wavelet transform and
+//! only the fine half is kept, so every band of noise built from it stays within one octave.
+//! Fractal sums of such bands do not alias when rendered at low resolution, unlike sums of
+//! simplex octaves.
+//!
+//! The tile is periodic, evaluation wraps around its edges. Coefficients are stored as the
+//! bits of `f32`s in an `i32` buffer so lanes can fetch them with a gather.
+//!
+//! Useful for writing your own SIMD-generic code for use cases not covered by the higher level
+//! interfaces.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::float::Float;
+use crate::noise::white_32::white_hash_2d;
+
+use core::f32;
+
+use simdeez::scalar::{F32x1, Scalar};
+use simdeez::Simd;
+
+/// Radius of the downsampling filter
+const ARAD: i64 = 16;
+
+const DOWN_COEFFS: [f32; 2 * ARAD as usize] = [
+    0.000334, -0.001528, 0.000410, 0.003545, -0.000938, -0.008233, 0.002172, 0.019120, -0.004898,
+    -0.044345, 0.010867, 0.102847, -0.023925, -0.238530, 0.052005, 0.553200, 0.553200, 0.052005,
+    -0.238530, -0.023925, 0.102847, 0.010867, -0.044345, -0.004898, 0.019120, 0.002172, -0.008233,
+    -0.000938, 0.003546, 0.000410, -0.001528, 0.000334,
+];
+
+const UP_COEFFS: [f32; 4] = [0.25, 0.75, 0.75, 0.25];
+
+/// Variance of a single band, measured empirically, used to normalize fractal sums
+const BAND_VARIANCE_2D: f32 = 0.30;
+const BAND_VARIANCE_3D: f32 = 0.22;
+const BAND_VARIANCE_PROJECTED: f32 = 0.34;
+
+/// A periodic tile of band-limited noise coefficients
+#[derive(Clone)]
+pub struct WaveletTile {
+    size: usize,
+    dimensions: usize,
+    seed: i32,
+    coefficients: Vec<i32>,
+}
+
+impl WaveletTile {
+    /// Generates a `size`^`dimensions` tile from `seed`
+    ///
+    /// `size` must be a power of two of at least 8 and `dimensions` either 2 or 3. 3d tiles are
+    /// also used for projected evaluation.
+    pub fn new(size: usize, dimensions: usize, seed: i32) -> WaveletTile {
+        if size < 8 || !size.is_power_of_two() {
+            panic!("wavelet tile size must be a power of two of at least 8");
+        }
+        if dimensions != 2 && dimensions != 3 {
+            panic!("wavelet tiles are 2 or 3 dimensional");
+        }
+        let n = size;
+        let len = n.pow(dimensions as u32);
+
+        // Gaussian white noise, from pairs of hashes with Box-Muller
+        let unit = |hash: i32| ((hash as u32 >> 8) + 1) as f32 / (1u32 << 24) as f32;
+        let mut noise: Vec<f32> = (0..len)
+            .map(|i| unsafe {
+                let h1 = white_hash_2d::<Scalar>(F32x1(i as f32), F32x1(0.0), seed).0;
+                let h2 = white_hash_2d::<Scalar>(F32x1(i as f32), F32x1(1.0), seed).0;
+                (-2.0 * Float::ln(unit(h1))).sqrt() * Float::cos(2.0 * f32::consts::PI * unit(h2))
+            })
+            .collect();
+
+        // Remove the coarse half: downsample and upsample along every axis in turn,
+        // then subtract the result
+        let mut half = vec![0.0; len];
+        let mut coarse = noise.clone();
+        for axis in 0..dimensions {
+            let stride = n.pow(axis as u32);
+            for start in 0..len {
+                if (start / stride) % n != 0 {
+                    continue;
+                }
+                downsample(&coarse, &mut half, start, stride, n);
+                upsample(&half, &mut coarse, start, stride, n);
+            }
+        }
+        for i in 0..len {
+            noise[i] -= coarse[i];
+        }
+
+        // Adding a copy shifted by an odd offset evens out the variance between even and odd
+        // coefficients
+        let mut offset = n / 2;
+        if offset % 2 == 0 {
+            offset += 1;
+        }
+        let mut coefficients = Vec::with_capacity(len);
+        for i in 0..len {
+            let mut j = 0;
+            for axis in 0..dimensions {
+                let stride = n.pow(axis as u32);
+                j += ((i / stride + offset) % n) * stride;
+            }
+            coefficients.push((noise[i] + noise[j]).to_bits() as i32);
+        }
+
+        WaveletTile {
+            size,
+            dimensions,
+            seed,
+            coefficients,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// The seed the tile was generated from
+    pub fn seed(&self) -> i32 {
+        self.seed
+    }
+
+    /// The coefficient at the given tile coordinates, wrapping around
+    pub fn coefficient(&self, x: i64, y: i64, z: i64) -> f32 {
+        let n = self.size as i64;
+        let mut i = x.rem_euclid(n) + y.rem_euclid(n) * n;
+        if self.dimensions == 3 {
+            i += z.rem_euclid(n) * n * n;
+        }
+        f32::from_bits(self.coefficients[i as usize] as u32)
+    }
+
+    #[inline(always)]
+    unsafe fn gather<S: Simd>(&self, index: S::Vi32) -> S::Vf32 {
+        S::castepi32_ps(S::i32gather_epi32(&self.coefficients, index))
+    }
+}
+
+fn downsample(from: &[f32], to: &mut [f32], start: usize, stride: usize, n: usize) {
+    let n = n as i64;
+    for i in 0..n / 2 {
+        let mut sum = 0.0;
+        for k in 2 * i - ARAD..2 * i + ARAD {
+            let j = start + k.rem_euclid(n) as usize * stride;
+            sum += DOWN_COEFFS[(k - 2 * i + ARAD) as usize] * from[j];
+        }
+        to[start + i as usize * stride] = sum;
+    }
+}
+
+fn upsample(from: &[f32], to: &mut [f32], start: usize, stride: usize, n: usize) {
+    let n = n as i64;
+    for i in 0..n {
+        let mut sum = 0.0;
+        for k in i / 2..=i / 2 + 1 {
+            let j = start + k.rem_euclid(n / 2) as usize * stride;
+            sum += UP_COEFFS[(i - 2 * k + 2) as usize] * from[j];
+        }
+        to[start + i as usize * stride] = sum;
+    }
+}
+
+/// The first tile coordinate and the three quadratic B-spline weights covering `p`
+#[inline(always)]
+unsafe fn quadratic_weights<S: Simd>(p: S::Vf32) -> (S::Vi32, [S::Vf32; 3]) {
+    let shifted = S::sub_ps(p, S::set1_ps(0.5));
+    let mid = S::sub_ps(
+        S::setzero_ps(),
+        S::floor_ps(S::sub_ps(S::setzero_ps(), shifted)),
+    );
+    let t = S::sub_ps(mid, shifted);
+    let t1 = S::sub_ps(S::set1_ps(1.0), t);
+    let w0 = S::mul_ps(S::set1_ps(0.5), S::mul_ps(t, t));
+    let w2 = S::mul_ps(S::set1_ps(0.5), S::mul_ps(t1, t1));
+    let w1 = S::sub_ps(S::sub_ps(S::set1_ps(1.0), w0), w2);
+    (
+        S::sub_epi32(S::cvtps_epi32(mid), S::set1_epi32(1)),
+        [w0, w1, w2],
+    )
+}
+
+/// The quadratic B-spline basis function, nonzero for 0 < t < 3
+#[inline(always)]
+unsafe fn quadratic_basis<S: Simd>(t: S::Vf32) -> S::Vf32 {
+    let t1 = S::sub_ps(t, S::set1_ps(1.0));
+    let t2 = S::sub_ps(S::set1_ps(2.0), t);
+    let t3 = S::sub_ps(S::set1_ps(3.0), t);
+    let left = S::mul_ps(S::set1_ps(0.5), S::mul_ps(t, t));
+    let middle = S::sub_ps(
+        S::set1_ps(1.0),
+        S::mul_ps(
+            S::set1_ps(0.5),
+            S::add_ps(S::mul_ps(t1, t1), S::mul_ps(t2, t2)),
+        ),
+    );
+    let right = S::mul_ps(S::set1_ps(0.5), S::mul_ps(t3, t3));
+    let mut w = S::blendv_ps(right, middle, S::cmplt_ps(t, S::set1_ps(2.0)));
+    w = S::blendv_ps(w, left, S::cmplt_ps(t, S::set1_ps(1.0)));
+    let inside = S::and_ps(
+        S::cmpgt_ps(t, S::setzero_ps()),
+        S::cmplt_ps(t, S::set1_ps(3.0)),
+    );
+    S::and_ps(w, inside)
+}
+
+/// Samples a single band of 2-dimensional wavelet noise from a 2d tile
+#[inline(always)]
+pub unsafe fn wavelet_2d<S: Simd>(x: S::Vf32, y: S::Vf32, tile: &WaveletTile) -> S::Vf32 {
+    let mask = S::set1_epi32(tile.size as i32 - 1);
+    let n = S::set1_epi32(tile.size as i32);
+    let (fx, wx) = quadratic_weights::<S>(x);
+    let (fy, wy) = quadratic_weights::<S>(y);
+
+    let mut result = S::setzero_ps();
+    for j in 0..3 {
+        let row = S::mullo_epi32(S::and_epi32(S::add_epi32(fy, S::set1_epi32(j)), mask), n);
+        for i in 0..3 {
+            let index = S::add_epi32(row, S::and_epi32(S::add_epi32(fx, S::set1_epi32(i)), mask));
+            let weight = S::mul_ps(wx[i as usize], wy[j as usize]);
+            result = S::add_ps(result, S::mul_ps(weight, tile.gather::<S>(index)));
+        }
+    }
+    result
+}
+
+/// Samples a single band of 3-dimensional wavelet noise from a 3d tile
+#[inline(always)]
+pub unsafe fn wavelet_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    tile: &WaveletTile,
+) -> S::Vf32 {
+    let mask = S::set1_epi32(tile.size as i32 - 1);
+    let n = S::set1_epi32(tile.size as i32);
+    let (fx, wx) = quadratic_weights::<S>(x);
+    let (fy, wy) = quadratic_weights::<S>(y);
+    let (fz, wz) = quadratic_weights::<S>(z);
+
+    let mut result = S::setzero_ps();
+    for k in 0..3 {
+        let layer = S::mullo_epi32(S::and_epi32(S::add_epi32(fz, S::set1_epi32(k)), mask), n);
+        for j in 0..3 {
+            let row = S::mullo_epi32(
+                S::add_epi32(
+                    layer,
+                    S::and_epi32(S::add_epi32(fy, S::set1_epi32(j)), mask),
+                ),
+                n,
+            );
+            let wyz = S::mul_ps(wy[j as usize], wz[k as usize]);
+            for i in 0..3 {
+                let index =
+                    S::add_epi32(row, S::and_epi32(S::add_epi32(fx, S::set1_epi32(i)), mask));
+                let weight = S::mul_ps(wx[i as usize], wyz);
+                result = S::add_ps(result, S::mul_ps(weight, tile.gather::<S>(index)));
+            }
+        }
+    }
+    result
+}
+
+/// Samples a single band of 3-dimensional wavelet noise projected along `normal`
+///
+/// This is the noise a surface with that normal would see: the 3d noise integrated along the
+/// normal, which keeps it band-limited in the 2d parameterization of the surface. `normal` must
+/// be of unit length and `tile` a 3d tile.
+#[inline(always)]
+pub unsafe fn wavelet_projected_3d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    normal: [f32; 3],
+    tile: &WaveletTile,
+) -> S::Vf32 {
+    let mask = S::set1_epi32(tile.size as i32 - 1);
+    let n = S::set1_epi32(tile.size as i32);
+    let p = [x, y, z];
+
+    // Bound the support of the basis functions for this projection direction
+    let mut base = [S::setzero_epi32(); 3];
+    let mut count = [0; 3];
+    for a in 0..3 {
+        let support = 3.0 * normal[a].abs() + 3.0 * ((1.0 - normal[a] * normal[a]) / 2.0).sqrt();
+        let low = S::sub_ps(p[a], S::set1_ps(support));
+        let ceil = S::sub_ps(
+            S::setzero_ps(),
+            S::floor_ps(S::sub_ps(S::setzero_ps(), low)),
+        );
+        base[a] = S::cvtps_epi32(ceil);
+        count[a] = (2.0 * support).floor() as i32 + 1;
+    }
+
+    let mut result = S::setzero_ps();
+    for k in 0..count[2] {
+        let cz = S::add_epi32(base[2], S::set1_epi32(k));
+        let layer = S::mullo_epi32(S::and_epi32(cz, mask), n);
+        let dz = S::sub_ps(z, S::cvtepi32_ps(cz));
+        for j in 0..count[1] {
+            let cy = S::add_epi32(base[1], S::set1_epi32(j));
+            let row = S::mullo_epi32(S::add_epi32(layer, S::and_epi32(cy, mask)), n);
+            let dy = S::sub_ps(y, S::cvtepi32_ps(cy));
+            for i in 0..count[0] {
+                let cx = S::add_epi32(base[0], S::set1_epi32(i));
+                let dx = S::sub_ps(x, S::cvtepi32_ps(cx));
+                let d = [dx, dy, dz];
+
+                // Evaluate the basis function at c moved halfway to p along the normal
+                let mut dot = S::mul_ps(S::set1_ps(normal[0]), dx);
+                dot = S::add_ps(dot, S::mul_ps(S::set1_ps(normal[1]), dy));
+                dot = S::add_ps(dot, S::mul_ps(S::set1_ps(normal[2]), dz));
+                let half_dot = S::mul_ps(dot, S::set1_ps(0.5));
+                let mut weight = S::set1_ps(1.0);
+                for a in 0..3 {
+                    // t = c + normal * dot / 2 - (p - 1.5)
+                    let t = S::add_ps(
+                        S::sub_ps(S::mul_ps(S::set1_ps(normal[a]), half_dot), d[a]),
+                        S::set1_ps(1.5),
+                    );
+                    weight = S::mul_ps(weight, quadratic_basis::<S>(t));
+                }
+
+                let index = S::add_epi32(row, S::and_epi32(cx, mask));
+                result = S::add_ps(result, S::mul_ps(weight, tile.gather::<S>(index)));
+            }
+        }
+    }
+    result
+}
+
+/// Scale that maps 3 standard deviations of a sum of bands to 1
+#[inline(always)]
+fn band_normalization(gain: f32, octaves: u8, band_variance: f32) -> f32 {
+    let mut amp = 1.0;
+    let mut variance = 0.0;
+    for _ in 0..octaves {
+        variance += amp * amp;
+        amp *= gain;
+    }
+    if variance > 0.0 {
+        1.0 / (3.0 * (variance * band_variance).sqrt())
+    } else {
+        0.0
+    }
+}
+
+/// Sums `octaves` bands of 2d wavelet noise, each twice the frequency of the previous one and
+/// weighted by `gain` relative to it
+///
+/// Produces a value roughly -1 ≤ n ≤ 1.
+#[inline(always)]
+pub unsafe fn wavelet_multiband_2d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    gain: f32,
+    octaves: u8,
+    tile: &WaveletTile,
+) -> S::Vf32 {
+    let mut amp = 1.0;
+    let mut result = S::setzero_ps();
+    for _ in 0..octaves {
+        result = S::add_ps(
+            result,
+            S::mul_ps(S::set1_ps(amp), wavelet_2d::<S>(x, y, tile)),
+        );
+        x = S::add_ps(x, x);
+        y = S::add_ps(y, y);
+        amp *= gain;
+    }
+    S::mul_ps(
+        result,
+        S::set1_ps(band_normalization(gain, octaves, BAND_VARIANCE_2D)),
+    )
+}
+
+/// Sums `octaves` bands of 3d wavelet noise, see [`wavelet_multiband_2d`]
+#[inline(always)]
+pub unsafe fn wavelet_multiband_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    gain: f32,
+    octaves: u8,
+    tile: &WaveletTile,
+) -> S::Vf32 {
+    let mut amp = 1.0;
+    let mut result = S::setzero_ps();
+    for _ in 0..octaves {
+        result = S::add_ps(
+            result,
+            S::mul_ps(S::set1_ps(amp), wavelet_3d::<S>(x, y, z, tile)),
+        );
+        x = S::add_ps(x, x);
+        y = S::add_ps(y, y);
+        z = S::add_ps(z, z);
+        amp *= gain;
+    }
+    S::mul_ps(
+        result,
+        S::set1_ps(band_normalization(gain, octaves, BAND_VARIANCE_3D)),
+    )
+}
+
+/// Sums `octaves` bands of projected 3d wavelet noise, see [`wavelet_multiband_2d`] and
+/// [`wavelet_projected_3d`]
+#[inline(always)]
+pub unsafe fn wavelet_multiband_projected_3d<S: Simd>(
+    mut x: S::Vf32,
+    mut y: S::Vf32,
+    mut z: S::Vf32,
+    normal: [f32; 3],
+    gain: f32,
+    octaves: u8,
+    tile: &WaveletTile,
+) -> S::Vf32 {
+    let mut amp = 1.0;
+    let mut result = S::setzero_ps();
+    for _ in 0..octaves {
+        result = S::add_ps(
+            result,
+            S::mul_ps(
+                S::set1_ps(amp),
+                wavelet_projected_3d::<S>(x, y, z, normal, tile),
+            ),
+        );
+        x = S::add_ps(x, x);
+        y = S::add_ps(y, y);
+        z = S::add_ps(z, z);
+        amp *= gain;
+    }
+    S::mul_ps(
+        result,
+        S::set1_ps(band_normalization(gain, octaves, BAND_VARIANCE_PROJECTED)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variance(values: &[f32]) -> f32 {
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        values.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / values.len() as f32
+    }
+
+    #[test]
+    fn tile_is_reproducible() {
+        let a = WaveletTile::new(16, 2, 5);
+        let b = WaveletTile::new(16, 2, 5);
+        let c = WaveletTile::new(16, 2, 6);
+        assert_eq!(a.coefficients, b.coefficients);
+        assert_ne!(a.coefficients, c.coefficients);
+        assert_eq!(a.coefficient(-1, 3, 0), a.coefficient(15, 19, 0));
+    }
+
+    #[test]
+    fn band_variance_2d() {
+        let tile = WaveletTile::new(64, 2, 1);
+        let values: Vec<f32> = (0..64 * 64 * 4)
+            .map(|i| unsafe {
+                let x = (i % 128) as f32 * 0.5 + 0.25;
+                let y = (i / 128) as f32 * 0.5 + 0.25;
+                wavelet_2d::<Scalar>(F32x1(x), F32x1(y), &tile).0
+            })
+            .collect();
+        let v = variance(&values);
+        assert!((v - BAND_VARIANCE_2D).abs() < 0.05, "variance {}", v);
+    }
+
+    #[test]
+    fn projected_along_axis_is_continuous() {
+        let tile = WaveletTile::new(16, 3, 2);
+        let normal = [0.0, 0.0, 1.0];
+        for i in 0..100 {
+            let x = i as f32 * 0.13;
+            let a = unsafe {
+                wavelet_projected_3d::<Scalar>(F32x1(x), F32x1(1.7), F32x1(0.0), normal, &tile).0
+            };
+            let b = unsafe {
+                wavelet_projected_3d::<Scalar>(
+                    F32x1(x + 0.001),
+                    F32x1(1.7),
+                    F32x1(0.0),
+                    normal,
+                    &tile,
+                )
+                .0
+            };
+            assert!((a - b).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Low-level white noise primitives
+//!
+//! White noise assigns an independent pseudo-random value to every integer lattice cell. The
+//! coordinates are floored before hashing, so every point inside a cell gets the same value.
+//!
+//! Useful for writing your own SIMD-generic code for use cases not covered by the higher level
+//! interfaces.
+
+use crate::noise::cellular_32::{HASH_2_FLOAT_32, W_PRIME_32, X_PRIME_32, Y_PRIME_32, Z_PRIME_32};
+
+use simdeez::Simd;
+
+/// Mixes the combined lattice coordinates with the seed
+///
+/// The cubic step is the same one used by the cellular hashes, the xor-shift/multiply rounds
+/// afterwards remove the correlation between neighbouring cells that it leaves behind.
+#[inline(always)]
+unsafe fn finalize<S: Simd>(seed: i32, hash: S::Vi32) -> S::Vi32 {
+    let mut hash = S::xor_epi32(hash, S::set1_epi32(seed));
+    hash = S::mullo_epi32(
+        S::mullo_epi32(S::mullo_epi32(hash, hash), S::set1_epi32(60493)),
+        hash,
+    );
+    hash = S::xor_epi32(S::srai_epi32(hash, 16), hash);
+    hash = S::mullo_epi32(hash, S::set1_epi32(0x7feb352d));
+    hash = S::xor_epi32(S::srai_epi32(hash, 15), hash);
+    hash = S::mullo_epi32(hash, S::set1_epi32(0x846ca68b_u32 as i32));
+    S::xor_epi32(S::srai_epi32(hash, 16), hash)
+}
+
+#[inline(always)]
+unsafe fn lattice<S: Simd>(v: S::Vf32, prime: i32) -> S::Vi32 {
+    S::mullo_epi32(S::cvtps_epi32(S::floor_ps(v)), S::set1_epi32(prime))
+}
+
+#[inline(always)]
+unsafe fn hash_to_float<S: Simd>(hash: S::Vi32) -> S::Vf32 {
+    S::mul_ps(S::set1_ps(HASH_2_FLOAT_32), S::cvtepi32_ps(hash))
+}
+
+/// Hashes the cell containing `x`
+#[inline(always)]
+pub unsafe fn white_hash_1d<S: Simd>(x: S::Vf32, seed: i32) -> S::Vi32 {
+    finalize::<S>(seed, lattice::<S>(x, X_PRIME_32))
+}
+
+/// Hashes the cell containing `[x, y]`
+#[inline(always)]
+pub unsafe fn white_hash_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vi32 {
+    let hash = S::xor_epi32(lattice::<S>(x, X_PRIME_32), lattice::<S>(y, Y_PRIME_32));
+    finalize::<S>(seed, hash)
+}
+
+/// Hashes the cell containing `[x, y, z]`
+#[inline(always)]
+pub unsafe fn white_hash_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vi32 {
+    let mut hash = S::xor_epi32(lattice::<S>(x, X_PRIME_32), lattice::<S>(y, Y_PRIME_32));
+    hash = S::xor_epi32(hash, lattice::<S>(z, Z_PRIME_32));
+    finalize::<S>(seed, hash)
+}
+
+/// Hashes the cell containing `[x, y, z, w]`
+#[inline(always)]
+pub unsafe fn white_hash_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> S::Vi32 {
+    let mut hash = S::xor_epi32(lattice::<S>(x, X_PRIME_32), lattice::<S>(y, Y_PRIME_32));
+    hash = S::xor_epi32(hash, lattice::<S>(z, Z_PRIME_32));
+    hash = S::xor_epi32(hash, lattice::<S>(w, W_PRIME_32));
+    finalize::<S>(seed, hash)
+}
+
+/// Samples 1-dimensional white noise
+///
+/// Produces a value -1 ≤ n < 1.
+#[inline(always)]
+pub unsafe fn white_1d<S: Simd>(x: S::Vf32, seed: i32) -> S::Vf32 {
+    hash_to_float::<S>(white_hash_1d::<S>(x, seed))
+}
+
+/// Samples 2-dimensional white noise
+///
+/// Produces a value -1 ≤ n < 1.
+#[inline(always)]
+pub unsafe fn white_2d<S: Simd>(x: S::Vf32, y: S::Vf32, seed: i32) -> S::Vf32 {
+    hash_to_float::<S>(white_hash_2d::<S>(x, y, seed))
+}
+
+/// Samples 3-dimensional white noise
+///
+/// Produces a value -1 ≤ n < 1.
+#[inline(always)]
+pub unsafe fn white_3d<S: Simd>(x: S::Vf32, y: S::Vf32, z: S::Vf32, seed: i32) -> S::Vf32 {
+    hash_to_float::<S>(white_hash_3d::<S>(x, y, z, seed))
+}
+
+/// Samples 4-dimensional white noise
+///
+/// Produces a value -1 ≤ n < 1.
+#[inline(always)]
+pub unsafe fn white_4d<S: Simd>(
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+    seed: i32,
+) -> S::Vf32 {
+    hash_to_float::<S>(white_hash_4d::<S>(x, y, z, w, seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simdeez::scalar::{F32x1, Scalar};
+
+    #[test]
+    fn white_2d_range() {
+        let mut min = f32::INFINITY;
+        let mut max = -f32::INFINITY;
+        let mut sum = 0.0;
+        for y in 0..100 {
+            for x in 0..100 {
+                let n = unsafe { white_2d::<Scalar>(F32x1(x as f32), F32x1(y as f32), 7).0 };
+                min = min.min(n);
+                max = max.max(n);
+                sum += n;
+            }
+        }
+        assert!(min >= -1.0 && min < -0.9, "min out of range {}", min);
+        assert!(max < 1.0 && max > 0.9, "max out of range {}", max);
+        assert!((sum / 10000.0f32).abs() < 0.05, "mean is biased {}", sum);
+    }
+
+    #[test]
+    fn white_constant_within_cell() {
+        for x in -10..10 {
+            let a = unsafe { white_3d::<Scalar>(F32x1(x as f32), F32x1(2.0), F32x1(-3.0), 1).0 };
+            let b = unsafe {
+                white_3d::<Scalar>(F32x1(x as f32 + 0.99), F32x1(2.5), F32x1(-2.01), 1).0
+            };
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn white_seed_changes_output() {
+        let mut same = 0;
+        for x in 0..100 {
+            let a = unsafe { white_1d::<Scalar>(F32x1(x as f32), 1).0 };
+            let b = unsafe { white_1d::<Scalar>(F32x1(x as f32), 2).0 };
+            if a == b {
+                same += 1;
+            }
+        }
+        assert!(same < 5);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::noise::cellular_32::{HASH_2_FLOAT_64, W_PRIME_64, X_PRIME_64, Y_PRIME_64, Z_PRIME_64};
+
+use simdeez::Simd;
+
+#[inline(always)]
+unsafe fn finalize<S: Simd>(seed: i64, hash: S::Vi64) -> S::Vi64 {
+    let mut hash = S::xor_epi64(hash, S::set1_epi64(seed));
+    hash = S::mullo_epi64(
+        S::mullo_epi64(S::mullo_epi64(hash, hash), S::set1_epi64(60493)),
+        hash,
+    );
+    hash = S::xor_epi64(S::srai_epi64(hash, 32), hash);
+    hash = S::mullo_epi64(hash, S::set1_epi64(0x7feb352d));
+    hash = S::xor_epi64(S::srai_epi64(hash, 29), hash);
+    hash = S::mullo_epi64(hash, S::set1_epi64(0x846ca68b));
+    S::xor_epi64(S::srai_epi64(hash, 32), hash)
+}
+
+#[inline(always)]
+unsafe fn lattice<S: Simd>(v: S::Vf64, prime: i64) -> S::Vi64 {
+    S::mullo_epi64(S::cvtpd_epi64(S::floor_pd(v)), S::set1_epi64(prime))
+}
+
+#[inline(always)]
+unsafe fn hash_to_float<S: Simd>(hash: S::Vi64) -> S::Vf64 {
+    // Only the upper half is used, so the scale matches the 32 bit variant
+    S::mul_pd(
+        S::set1_pd(HASH_2_FLOAT_64),
+        S::cvtepi64_pd(S::srai_epi64(hash, 32)),
+    )
+}
+
+#[inline(always)]
+pub unsafe fn white_hash_1d<S: Simd>(x: S::Vf64, seed: i64) -> S::Vi64 {
+    finalize::<S>(seed, lattice::<S>(x, X_PRIME_64))
+}
+
+#[inline(always)]
+pub unsafe fn white_hash_2d<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> S::Vi64 {
+    let hash = S::xor_epi64(lattice::<S>(x, X_PRIME_64), lattice::<S>(y, Y_PRIME_64));
+    finalize::<S>(seed, hash)
+}
+
+#[inline(always)]
+pub unsafe fn white_hash_3d<S: Simd>(x: S::Vf64, y: S::Vf64, z: S::Vf64, seed: i64) -> S::Vi64 {
+    let mut hash = S::xor_epi64(lattice::<S>(x, X_PRIME_64), lattice::<S>(y, Y_PRIME_64));
+    hash = S::xor_epi64(hash, lattice::<S>(z, Z_PRIME_64));
+    finalize::<S>(seed, hash)
+}
+
+#[inline(always)]
+pub unsafe fn white_hash_4d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    w: S::Vf64,
+    seed: i64,
+) -> S::Vi64 {
+    let mut hash = S::xor_epi64(lattice::<S>(x, X_PRIME_64), lattice::<S>(y, Y_PRIME_64));
+    hash = S::xor_epi64(hash, lattice::<S>(z, Z_PRIME_64));
+    hash = S::xor_epi64(hash, lattice::<S>(w, W_PRIME_64));
+    finalize::<S>(seed, hash)
+}
+
+#[inline(always)]
+pub unsafe fn white_1d<S: Simd>(x: S::Vf64, seed: i64) -> S::Vf64 {
+    hash_to_float::<S>(white_hash_1d::<S>(x, seed))
+}
+
+#[inline(always)]
+pub unsafe fn white_2d<S: Simd>(x: S::Vf64, y: S::Vf64, seed: i64) -> S::Vf64 {
+    hash_to_float::<S>(white_hash_2d::<S>(x, y, seed))
+}
+
+#[inline(always)]
+pub unsafe fn white_3d<S: Simd>(x: S::Vf64, y: S::Vf64, z: S::Vf64, seed: i64) -> S::Vf64 {
+    hash_to_float::<S>(white_hash_3d::<S>(x, y, z, seed))
+}
+
+#[inline(always)]
+pub unsafe fn white_4d<S: Simd>(
+    x: S::Vf64,
+    y: S::Vf64,
+    z: S::Vf64,
+    w: S::Vf64,
+    seed: i64,
+) -> S::Vf64 {
+    hash_to_float::<S>(white_hash_4d::<S>(x, y, z, w, seed))
+}
@@ -0,0 +1,263 @@
+//! A generated block of noise that knows its own shape.
+//!
+//! Blocks are laid out with x varying fastest, then y, z and w. `NoiseBlock` keeps
+//! that layout in one place, so values can be looked up by coordinate instead of by
+//! hand computed offsets.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let block = NoiseBuilder::fbm_3d(16, 8, 4).generate_block();
+//! let v = block[(3, 2, 1)];
+//! assert_eq!(v, block.data()[(1 * 8 + 2) * 16 + 3]);
+//! for row in block.rows() {
+//!     assert_eq!(row.len(), 16);
+//! }
+//! let data: Vec<f32> = block.into();
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::dimensional_being::DimensionalBeing;
+use crate::noise_helpers_32::row_position;
+use crate::noise_type::NoiseType;
+
+use core::ops::{Index, IndexMut};
+
+#[derive(Clone)]
+pub struct NoiseBlock {
+    data: Vec<f32>,
+    shape: [usize; 4],
+    min: f32,
+    max: f32,
+    settings: NoiseType,
+}
+
+impl NoiseBlock {
+    /// Wrap the output of `generate` for the settings that produced it. Panics if a
+    /// dimension is zero, or the length of `data` does not match the dimensions of the
+    /// settings.
+    pub fn new(settings: NoiseType, data: Vec<f32>, min: f32, max: f32) -> NoiseBlock {
+        let dim = settings.get_dimensions();
+        let mut shape = [1; 4];
+        shape[..dim.dim].copy_from_slice(&[dim.width, dim.height, dim.depth, dim.time][..dim.dim]);
+        if shape.contains(&0) {
+            panic!("noise blocks must not be empty");
+        }
+        if data.len() != shape.iter().product::<usize>() {
+            panic!("data length does not match the block dimensions");
+        }
+        NoiseBlock {
+            data,
+            shape,
+            min,
+            max,
+            settings,
+        }
+    }
+
+    /// Width, height, depth and time of the block. Unused dimensions are 1.
+    pub fn shape(&self) -> [usize; 4] {
+        self.shape
+    }
+
+    /// Number of dimensions the noise was generated in
+    pub fn dim(&self) -> usize {
+        self.settings.get_dimensions().dim
+    }
+
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// The settings the block was generated with
+    pub fn settings(&self) -> &NoiseType {
+        &self.settings
+    }
+
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+
+    /// Offset of a sample in `data`. Panics if a coordinate is outside the block.
+    pub fn offset(&self, x: usize, y: usize, z: usize, w: usize) -> usize {
+        let [width, height, depth, time] = self.shape;
+        if x >= width || y >= height || z >= depth || w >= time {
+            panic!("coordinate outside the block");
+        }
+        ((w * depth + z) * height + y) * width + x
+    }
+
+    /// The sample at a coordinate, or None outside the block
+    pub fn get(&self, x: usize, y: usize, z: usize, w: usize) -> Option<f32> {
+        let [width, height, depth, time] = self.shape;
+        if x < width && y < height && z < depth && w < time {
+            Some(self.data[self.offset(x, y, z, w)])
+        } else {
+            None
+        }
+    }
+
+    /// Rows along x, in y, then z, then w order
+    pub fn rows(&self) -> core::slice::ChunksExact<'_, f32> {
+        self.data.chunks_exact(self.shape[0])
+    }
+
+    pub fn rows_mut(&mut self) -> core::slice::ChunksExactMut<'_, f32> {
+        let width = self.shape[0];
+        self.data.chunks_exact_mut(width)
+    }
+
+    /// xy slices, in z, then w order
+    pub fn slices(&self) -> core::slice::ChunksExact<'_, f32> {
+        self.data.chunks_exact(self.shape[0] * self.shape[1])
+    }
+
+    pub fn slices_mut(&mut self) -> core::slice::ChunksExactMut<'_, f32> {
+        let area = self.shape[0] * self.shape[1];
+        self.data.chunks_exact_mut(area)
+    }
+
+    /// Copy out the xy plane at `z` and `w` as a 2d block, keeping this block's min and
+    /// max so every slice scales the same. Panics if `z` or `w` is outside the block.
+    ///
+    /// The settings of the slice generate exactly the plane: the same noise with a depth
+    /// and time of 1, and `z` and `w` folded into the offsets or chunk origin.
+    pub fn slice_xy(&self, z: usize, w: usize) -> NoiseBlock {
+        let [width, height, _, _] = self.shape;
+        let start = self.offset(0, 0, z, w);
+        let mut dim = self.settings.get_dimensions();
+        if dim.dim > 2 {
+            match &mut dim.chunk_origin {
+                Some(origin) => {
+                    origin[2] += z as i64;
+                    origin[3] += w as i64;
+                }
+                None => {
+                    dim.z = row_position(dim.z, dim.step_z, z, dim.algorithm_version);
+                    dim.w = row_position(dim.w, dim.step_w, w, dim.algorithm_version);
+                }
+            }
+            dim.depth = 1;
+            dim.time = 1;
+        }
+        NoiseBlock {
+            data: self.data[start..start + width * height].to_vec(),
+            shape: [width, height, 1, 1],
+            min: self.min,
+            max: self.max,
+            settings: self.settings.with_dimensions(dim),
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<f32> {
+        self.data
+    }
+}
+
+impl From<NoiseBlock> for Vec<f32> {
+    fn from(block: NoiseBlock) -> Vec<f32> {
+        block.data
+    }
+}
+
+impl Index<(usize, usize)> for NoiseBlock {
+    type Output = f32;
+    fn index(&self, (x, y): (usize, usize)) -> &f32 {
+        &self.data[self.offset(x, y, 0, 0)]
+    }
+}
+
+impl Index<(usize, usize, usize)> for NoiseBlock {
+    type Output = f32;
+    fn index(&self, (x, y, z): (usize, usize, usize)) -> &f32 {
+        &self.data[self.offset(x, y, z, 0)]
+    }
+}
+
+impl Index<(usize, usize, usize, usize)> for NoiseBlock {
+    type Output = f32;
+    fn index(&self, (x, y, z, w): (usize, usize, usize, usize)) -> &f32 {
+        &self.data[self.offset(x, y, z, w)]
+    }
+}
+
+impl IndexMut<(usize, usize)> for NoiseBlock {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut f32 {
+        let i = self.offset(x, y, 0, 0);
+        &mut self.data[i]
+    }
+}
+
+impl IndexMut<(usize, usize, usize)> for NoiseBlock {
+    fn index_mut(&mut self, (x, y, z): (usize, usize, usize)) -> &mut f32 {
+        let i = self.offset(x, y, z, 0);
+        &mut self.data[i]
+    }
+}
+
+impl IndexMut<(usize, usize, usize, usize)> for NoiseBlock {
+    fn index_mut(&mut self, (x, y, z, w): (usize, usize, usize, usize)) -> &mut f32 {
+        let i = self.offset(x, y, z, w);
+        &mut self.data[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dimensional_being::DimensionalBeing;
+    use crate::*;
+
+    #[test]
+    #[should_panic(expected = "noise blocks must not be empty")]
+    fn empty_blocks_are_rejected() {
+        NoiseBuilder::gradient_2d(0, 4).generate_block();
+    }
+
+    #[test]
+    fn indexing_follows_block_layout() {
+        let (data, _, _) = NoiseBuilder::gradient_4d(5, 4, 3, 2).generate();
+        let block = NoiseBuilder::gradient_4d(5, 4, 3, 2).generate_block();
+        assert_eq!(block.shape(), [5, 4, 3, 2]);
+        assert_eq!(block[(4, 3, 2, 1)], data[((1 * 3 + 2) * 4 + 3) * 5 + 4]);
+        assert_eq!(block.get(5, 0, 0, 0), None);
+        assert_eq!(block.rows().count(), 4 * 3 * 2);
+        assert_eq!(block.slices().nth(4).unwrap()[6], block[(1, 1, 1, 1)]);
+        assert_eq!(block.into_vec(), data);
+
+        let block = NoiseBuilder::gradient_4d(5, 4, 3, 2).generate_block();
+        let slice = block.slice_xy(2, 1);
+        assert_eq!(slice.shape(), [5, 4, 1, 1]);
+        assert_eq!(slice[(3, 2)], block[(3, 2, 2, 1)]);
+
+        let offset = NoiseBuilder::gradient_4d_offset(1.0, 5, 2.0, 4, 3.0, 3, 4.0, 2)
+            .with_step(&[1.0, 1.0, 0.5, 2.0])
+            .generate_block();
+        let chunk = NoiseBuilder::fbm_3d(5, 4, 3)
+            .with_chunk(&[1, -2, 3], 4)
+            .generate_block();
+        for (block, z, w) in [(offset, 2, 1), (chunk, 1, 0)].iter() {
+            let slice = block.slice_xy(*z, *w);
+            let dim = slice.settings().get_dimensions();
+            assert_eq!((dim.depth, dim.time), (1, 1));
+            let (data, _, _) = match slice.settings() {
+                NoiseType::Gradient(s) => s.generate(),
+                NoiseType::Fbm(s) => s.generate(),
+                _ => unreachable!(),
+            };
+            assert_eq!(data, slice.data());
+        }
+
+        let block = NoiseBuilder::gradient_2d(7, 3).generate_block();
+        assert_eq!(block.shape(), [7, 3, 1, 1]);
+        assert_eq!(block.rows().nth(2).unwrap()[6], block[(6, 2)]);
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
-    Cellular2Settings, CellularSettings, FbmSettings, GradientSettings, NoiseDimensions,
-    RidgeSettings, Settings, TurbulenceSettings,
+    Cellular2Settings, CellularSettings, FbmSettings, GaborSettings, GradientSettings,
+    NoiseDimensions, RidgeSettings, Settings, TurbulenceSettings, WaveletSettings,
+    WhiteNoiseSettings,
 };
 
 pub struct NoiseBuilder {}
@@ -100,6 +101,53 @@ impl NoiseBuilder {
         Cellular2Settings::default(dim)
     }
 
+    pub fn gabor_2d(width: usize, height: usize) -> GaborSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        GaborSettings::default(dim)
+    }
+
+    pub fn gabor_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> GaborSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        GaborSettings::default(dim)
+    }
+
+    pub fn gabor_3d(width: usize, height: usize, depth: usize) -> GaborSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        GaborSettings::default(dim)
+    }
+
+    pub fn gabor_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> GaborSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        GaborSettings::default(dim)
+    }
+
     pub fn fbm_1d(width: usize) -> FbmSettings {
         let mut dim = NoiseDimensions::default(1);
         dim.width = width;
@@ -465,4 +513,149 @@ impl NoiseBuilder {
         dim.w = w_offset;
         GradientSettings::default(dim)
     }
+
+    // Wavelet Builders
+    pub fn wavelet_2d(width: usize, height: usize) -> WaveletSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        WaveletSettings::default(dim)
+    }
+
+    pub fn wavelet_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> WaveletSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        WaveletSettings::default(dim)
+    }
+
+    pub fn wavelet_3d(width: usize, height: usize, depth: usize) -> WaveletSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        WaveletSettings::default(dim)
+    }
+
+    pub fn wavelet_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> WaveletSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        WaveletSettings::default(dim)
+    }
+
+    // White Noise Builders
+    pub fn white_noise_1d(width: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_1d_offset(x_offset: f32, width: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(1);
+        dim.width = width;
+        dim.x = x_offset;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_2d(width: usize, height: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_2d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = width;
+        dim.height = height;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_3d(width: usize, height: usize, depth: usize) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_3d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(3);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_4d(
+        width: usize,
+        height: usize,
+        depth: usize,
+        time: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        WhiteNoiseSettings::default(dim)
+    }
+
+    pub fn white_noise_4d_offset(
+        x_offset: f32,
+        width: usize,
+        y_offset: f32,
+        height: usize,
+        z_offset: f32,
+        depth: usize,
+        w_offset: f32,
+        time: usize,
+    ) -> WhiteNoiseSettings {
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = width;
+        dim.height = height;
+        dim.depth = depth;
+        dim.time = time;
+        dim.x = x_offset;
+        dim.y = y_offset;
+        dim.z = z_offset;
+        dim.w = w_offset;
+        WhiteNoiseSettings::default(dim)
+    }
 }
@@ -1,10 +1,22 @@
+use crate::algorithm_version::AlgorithmVersion;
+use crate::noise_error::NoiseError;
+use crate::range_mode::RangeMode;
+use crate::seed::{mix64, seed_from_u64};
+use crate::transform::Transform;
+
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoiseDimensions {
     pub dim: usize,
     pub x: f32,
     pub y: f32,
     pub z: f32,
     pub w: f32,
+    /// Spacing between samples along each axis, 1.0 by default
+    pub step_x: f32,
+    pub step_y: f32,
+    pub step_z: f32,
+    pub step_w: f32,
     pub width: usize,
     pub height: usize,
     pub depth: usize,
@@ -12,19 +24,46 @@ pub struct NoiseDimensions {
     pub min: f32,
     pub max: f32,
     pub seed: i32,
+    /// The 64 bit seed set by `Settings::with_seed_u64`, which `seed` is mixed down
+    /// from, see `NoiseDimensions::seed_u64`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wide_seed: Option<u64>,
+    /// Applied to the frequency scaled coordinates, see `Settings::with_transform`
+    pub transform: Option<Transform>,
+    /// How the min and max of a block are found, see `RangeMode`
+    pub range_mode: RangeMode,
+    /// Integer position of the first sample on each axis, set by `Settings::with_chunk`.
+    /// When present it replaces the x, y, z and w offsets.
+    pub chunk_origin: Option<[i64; 4]>,
+    /// Algorithms the noise is generated with, see `Settings::with_algorithm_version`
+    #[cfg_attr(feature = "serde", serde(default = "AlgorithmVersion::unrecorded"))]
+    pub algorithm_version: AlgorithmVersion,
 }
 
 impl NoiseDimensions {
+    /// Dimensions of a 1x1 block of `d` dimensional noise. Panics unless `d` is 1 to 4.
     pub fn default(d: usize) -> NoiseDimensions {
+        match NoiseDimensions::try_new(d) {
+            Ok(dim) => dim,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Like `default`, but returns an error unless `d` is 1 to 4
+    pub fn try_new(d: usize) -> Result<NoiseDimensions, NoiseError> {
         if d < 1 || d > 4 {
-            panic!("dimension invalid");
+            return Err(NoiseError::InvalidDimension(d));
         }
-        NoiseDimensions {
+        Ok(NoiseDimensions {
             dim: d,
             x: 0.0,
             y: 0.0,
             z: 0.0,
             w: 0.0,
+            step_x: 1.0,
+            step_y: 1.0,
+            step_z: 1.0,
+            step_w: 1.0,
             width: 1,
             height: 1,
             depth: 1,
@@ -32,6 +71,99 @@ impl NoiseDimensions {
             min: 0.0,
             max: 1.0,
             seed: 1,
+            wide_seed: None,
+            transform: None,
+            range_mode: RangeMode::Measured,
+            chunk_origin: None,
+            algorithm_version: AlgorithmVersion::LATEST,
+        })
+    }
+
+    /// Checks that `noise` supports the dimension
+    pub(crate) fn try_validate(
+        &self,
+        noise: &'static str,
+        supported: &[usize],
+    ) -> Result<(), NoiseError> {
+        if self.dim < 1 || self.dim > 4 {
+            return Err(NoiseError::InvalidDimension(self.dim));
+        }
+        if !supported.contains(&self.dim) {
+            return Err(NoiseError::UnsupportedDimension {
+                noise,
+                dim: self.dim,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the block is not empty and its size fits in a usize. Settings used
+    /// only as sources or for single points may have an empty block, so this is only
+    /// checked before generating one.
+    pub(crate) fn try_validate_block(&self) -> Result<(), NoiseError> {
+        let extents = [self.width, self.height, self.depth, self.time];
+        if extents[..self.dim].contains(&0) {
+            return Err(NoiseError::ZeroSize);
+        }
+        extents[..self.dim]
+            .iter()
+            .try_fold(1usize, |len, e| len.checked_mul(*e))
+            .ok_or(NoiseError::SizeOverflow)?;
+        Ok(())
+    }
+    /// Set the spacing between samples, one step per dimension. Sample `i` along x
+    /// is at `x + i * step_x`. Panics if the number of steps does not match the
+    /// dimension or a step is not positive.
+    pub(crate) fn set_step(&mut self, step: &[f32]) {
+        if step.len() != self.dim {
+            panic!("steps must match the noise dimension");
         }
+        if step.iter().any(|s| !(*s > 0.0)) {
+            panic!("steps must be greater than 0");
+        }
+        let mut steps = [1.0; 4];
+        steps[..step.len()].copy_from_slice(step);
+        self.step_x = steps[0];
+        self.step_y = steps[1];
+        self.step_z = steps[2];
+        self.step_w = steps[3];
+    }
+
+    /// The full seed: the one set by `Settings::with_seed_u64`, or else `seed` sign
+    /// extended. A wide seed is ignored once `seed` is set to something it doesn't mix
+    /// down to.
+    pub fn seed_u64(&self) -> u64 {
+        match self.wide_seed {
+            Some(wide) if seed_from_u64(wide) == self.seed => wide,
+            _ => self.seed as i64 as u64,
+        }
+    }
+
+    /// These dimensions with the transform that blocks are sampled with. The kernels
+    /// only tell apart the low bits of their seed, so from `AlgorithmVersion::V3_2` on a
+    /// seed that doesn't fit an `i32` also shifts the domain, by an offset in 0..256
+    /// along each axis derived from all 64 bits. Other seeds are not shifted.
+    pub(crate) fn with_seed_offset(mut self) -> NoiseDimensions {
+        let wide = self.seed_u64();
+        if !self.algorithm_version.uses_wide_seeds() || wide as i32 as i64 as u64 == wide {
+            return self;
+        }
+        let h = mix64(wide ^ 0x7365_6564_6f66_6673);
+        let mut transform = self.transform.unwrap_or_default();
+        for (i, t) in transform.translation.iter_mut().enumerate() {
+            *t += ((h >> (16 * i)) & 0xffff) as f32 * (256.0 / 65536.0);
+        }
+        self.transform = Some(transform);
+        self
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: i32) {
+        self.seed = seed;
+        self.wide_seed = None;
+    }
+
+    pub(crate) fn set_seed_u64(&mut self, seed: u64) {
+        self.seed = seed_from_u64(seed);
+        self.wide_seed = Some(seed);
     }
 }
@@ -0,0 +1,180 @@
+//! Errors from validating noise settings, returned by `Settings::try_validate`,
+//! `Settings::try_wrap` and `Settings::try_generate`.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let result = NoiseBuilder::cellular_2d(64, 0).try_generate();
+//! assert_eq!(result.err(), Some(NoiseError::ZeroSize));
+//! ```
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NoiseError {
+    /// Dimensions must be 1 to 4
+    InvalidDimension(usize),
+    /// The noise type has no implementation for the dimension
+    UnsupportedDimension { noise: &'static str, dim: usize },
+    /// Width, height, depth or time is 0
+    ZeroSize,
+    /// width * height * depth * time does not fit in a usize
+    SizeOverflow,
+    /// A frequency is infinite or NaN
+    NonFiniteFrequency(f32),
+    /// Fractal noise needs at least 1 octave
+    InvalidOctaves(u8),
+    /// Cellular jitter must be within 0..=1
+    InvalidJitter(f32),
+    /// Cellular2 indices must satisfy index0 < index1 <= 3
+    InvalidCellIndex { index0: usize, index1: usize },
+    /// Any other out of range setting, naming the requirement
+    InvalidSetting(&'static str),
+    /// A preset was saved with a schema version this release cannot read
+    UnsupportedPresetVersion(u32),
+}
+
+impl fmt::Display for NoiseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoiseError::InvalidDimension(dim) => write!(f, "dimension {} invalid", dim),
+            NoiseError::UnsupportedDimension { noise, dim } => {
+                write!(f, "{} noise is not implemented in {}d", noise, dim)
+            }
+            NoiseError::ZeroSize => write!(f, "noise dimensions must not be 0"),
+            NoiseError::SizeOverflow => write!(f, "noise dimensions overflow a usize"),
+            NoiseError::NonFiniteFrequency(freq) => write!(f, "frequency {} is not finite", freq),
+            NoiseError::InvalidOctaves(octaves) => {
+                write!(f, "{} octaves, at least 1 is needed", octaves)
+            }
+            NoiseError::InvalidJitter(jitter) => {
+                write!(f, "jitter {} is outside 0..=1", jitter)
+            }
+            NoiseError::InvalidCellIndex { index0, index1 } => write!(
+                f,
+                "invalid index settings in cellular2 noise: {} and {}",
+                index0, index1
+            ),
+            NoiseError::InvalidSetting(requirement) => write!(f, "{}", requirement),
+            NoiseError::UnsupportedPresetVersion(version) => {
+                write!(f, "preset version {} is not supported", version)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for NoiseError {}
+
+pub(crate) fn check_frequencies(freqs: &[f32]) -> Result<(), NoiseError> {
+    match freqs.iter().find(|f| !f.is_finite()) {
+        Some(f) => Err(NoiseError::NonFiniteFrequency(*f)),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn check_octaves(octaves: u8) -> Result<(), NoiseError> {
+    if octaves == 0 {
+        return Err(NoiseError::InvalidOctaves(octaves));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_jitter(jitter: f32) -> Result<(), NoiseError> {
+    // The kernels only search the 3^dim cells around a sample, which is only guaranteed
+    // to find the nearest points while they stay within a cell width of their own cell
+    if !(jitter >= 0.0 && jitter <= 1.0) {
+        return Err(NoiseError::InvalidJitter(jitter));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn invalid_settings_are_reported() {
+        assert_eq!(
+            NoiseBuilder::cellular_2d(8, 8)
+                .with_jitter(-0.5)
+                .try_wrap()
+                .err(),
+            Some(NoiseError::InvalidJitter(-0.5))
+        );
+        assert_eq!(
+            NoiseBuilder::cellular_2d(8, 8)
+                .with_jitter(1.5)
+                .try_wrap()
+                .err(),
+            Some(NoiseError::InvalidJitter(1.5))
+        );
+        assert!(NoiseBuilder::cellular_2d(8, 8)
+            .with_jitter(1.0)
+            .try_wrap()
+            .is_ok());
+        assert_eq!(
+            NoiseBuilder::cellular2_2d(8, 8)
+                .with_index0(2)
+                .with_index1(1)
+                .try_generate()
+                .err(),
+            Some(NoiseError::InvalidCellIndex {
+                index0: 2,
+                index1: 1
+            })
+        );
+        assert_eq!(
+            NoiseBuilder::fbm_3d(8, 8, 8).with_octaves(0).try_validate(),
+            Err(NoiseError::InvalidOctaves(0))
+        );
+        assert_eq!(
+            NoiseBuilder::ridge_2d(8, 8)
+                .with_freq(f32::NAN)
+                .try_validate()
+                .map_err(|e| e.to_string()),
+            Err("frequency NaN is not finite".to_string())
+        );
+        assert_eq!(
+            NoiseBuilder::gradient_4d(usize::MAX, 2, 1, 1)
+                .try_generate()
+                .err(),
+            Some(NoiseError::SizeOverflow)
+        );
+        assert!(NoiseBuilder::gradient_2d(0, 0).try_wrap().is_ok());
+        assert_eq!(
+            NoiseDimensions::try_new(5).err(),
+            Some(NoiseError::InvalidDimension(5))
+        );
+
+        let mut dim = NoiseDimensions::default(4);
+        dim.width = 8;
+        assert_eq!(
+            CellularSettings::default(dim).try_validate(),
+            Err(NoiseError::UnsupportedDimension {
+                noise: "cellular",
+                dim: 4
+            })
+        );
+        assert_eq!(
+            GaborSettings::default(dim)
+                .with_freq_4d(0.1, 0.1, 0.1, 0.1)
+                .try_validate(),
+            Err(NoiseError::UnsupportedDimension {
+                noise: "gabor",
+                dim: 4
+            })
+        );
+        assert!(NoiseBuilder::gradient_2d(3, 2).try_generate().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "0 octaves, at least 1 is needed")]
+    fn integer_output_validates_the_settings() {
+        NoiseBuilder::fbm_2d(8, 8)
+            .with_octaves(0)
+            .generate_u8(false);
+    }
+}
@@ -1,23 +1,101 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::chunk::ChunkAxes;
 use crate::dimensional_being::DimensionalBeing;
-use crate::NoiseType;
+use crate::graph::NoiseGraph;
+use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_source::{NoiseSource, WaveletSource};
+use crate::point::wavelet_tile;
+use crate::range_mode::RangeMode;
+use crate::{AlgorithmVersion, NoiseType};
 
-use crate::noise::cell2_32::{cellular2_2d, cellular2_3d};
-use crate::noise::cell_32::{cellular_2d, cellular_3d};
-use crate::noise::fbm_32::{fbm_1d, fbm_2d, fbm_3d, fbm_4d};
-use crate::noise::ridge_32::{ridge_1d, ridge_2d, ridge_3d, ridge_4d};
-use crate::noise::simplex_32::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
-use crate::noise::turbulence_32::{turbulence_1d, turbulence_2d, turbulence_3d, turbulence_4d};
+use crate::noise::wavelet_32::WaveletTile;
+use crate::noise::white_32::{white_hash_1d, white_hash_2d, white_hash_3d, white_hash_4d};
+use crate::settings::{WaveletSettings, WhiteNoiseSettings};
+use crate::transform::{transform_1d, transform_2d, transform_3d, transform_4d};
 
 use simdeez::Simd;
 
-use std::f32;
+use core::f32;
+
+/// Where the block helpers write their samples: a pointer to the first sample and the
+/// distance in elements between neighbouring samples along x, y, z and w.
+pub(crate) struct BlockOut {
+    pub ptr: *mut f32,
+    pub strides: [isize; 4],
+}
+
+impl BlockOut {
+    /// The x fastest, then y, z and w layout returned by `get_Nd_noise`
+    pub(crate) fn contiguous(data: &mut [f32], dim: &NoiseDimensions) -> BlockOut {
+        let (w, h, d) = (dim.width as isize, dim.height as isize, dim.depth as isize);
+        BlockOut {
+            ptr: data.as_mut_ptr(),
+            strides: [1, w, w * h, w * h * d],
+        }
+    }
+}
+
+// The coordinates of the vector of samples starting at column `col`, `start + (lanes + col) *
+// step`. `lanes` holds 0, 1, 2...
+macro_rules! lanes_at_ps {
+    ($start:expr, $lanes:expr, $step:expr, $col:expr) => {
+        S::add_ps(
+            S::set1_ps($start),
+            S::mul_ps(
+                S::add_ps($lanes, S::set1_ps($col as f32)),
+                S::set1_ps($step),
+            ),
+        )
+    };
+}
+
+// The coordinates of the vector after `x`, which starts at column `col`. Accumulated
+// coordinates add the vector width like 3.1 did, see `AlgorithmVersion::accumulates_steps`.
+macro_rules! next_lanes_ps {
+    ($x:expr, $start:expr, $lanes:expr, $step:expr, $col:expr, $accumulate:expr) => {
+        if $accumulate {
+            S::add_ps($x, S::set1_ps(S::VF32_WIDTH as f32))
+        } else {
+            lanes_at_ps!($start, $lanes, $step, $col)
+        }
+    };
+}
+
+// The frequency scaled coordinates `x` of the vector of samples starting at column `col`,
+// taken from the chunk tables when generating a chunk
+macro_rules! lane_coords_ps {
+    ($chunk:expr, $col:expr, $x:expr, $freq:expr) => {
+        match &$chunk {
+            Some(c) => S::loadu_ps(&c.axes[0][$col]),
+            None => S::mul_ps($x, $freq),
+        }
+    };
+}
+
+// The frequency scaled coordinate of a row along y, z or w
+macro_rules! row_coord_ps {
+    ($chunk:expr, $axis:expr, $index:expr, $start:expr, $step:expr, $freq:expr, $version:expr) => {
+        match &$chunk {
+            Some(c) => S::set1_ps(c.axes[$axis][$index]),
+            None => S::mul_ps(
+                S::set1_ps(row_position($start, $step, $index, $version)),
+                $freq,
+            ),
+        }
+    };
+}
 
 macro_rules! get_1d_noise_helper_f32 {
-    ($Setting:expr,$f:expr $(,$arg:expr)*) => {
+    ($out:expr, $dim:expr, $freq:expr, $f:expr $(,$arg:expr)*) => {
  {
-    let dim = $Setting.get_dimensions();
-    let freq_x = S::set1_ps($Setting.freq_x);
+    let dim = $dim.with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
+    let freq_x = S::set1_ps($freq[0]);
     let start_x = dim.x;
+    let step_x = dim.step_x;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let mut min_s = S::set1_ps(f32::MAX);
     let mut max_s = S::set1_ps(f32::MIN);
@@ -25,30 +103,43 @@ macro_rules! get_1d_noise_helper_f32 {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result: Vec<f32> = Vec::with_capacity(width);
-    result.set_len(width);
-    let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
+        x_arr[i] = i as f32;
     }
-    let mut x = S::loadu_ps(&x_arr[0]);
+    let lanes = S::loadu_ps(&x_arr[0]);
+    let first_x = lanes_at_ps!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f32>::new(&dim, &[$freq[0]], vector_width);
+    let row_offset = 0;
+    let mut col = 0;
+    let mut x = first_x;
     for _ in 0..width / vector_width {
-        let f = $f(S::mul_ps(x, freq_x) $(,$arg)*);
-        max_s = S::max_ps(max_s, f);
-        min_s = S::min_ps(min_s, f);
-        S::storeu_ps(result.get_unchecked_mut(i), f);
-        i += vector_width;
-        x = S::add_ps(x, S::set1_ps(vector_width as f32));
+        let tx = transform_1d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x));
+        let f = $f(tx $(,$arg)*);
+        if track {
+            max_s = S::max_ps(max_s, f);
+            min_s = S::min_ps(min_s, f);
+        }
+        let base = $out.ptr.offset(row_offset + col as isize * $out.strides[0]);
+        if $out.strides[0] == 1 {
+            S::storeu_ps(&mut *base, f);
+        } else {
+            for j in 0..vector_width {
+                *base.offset(j as isize * $out.strides[0]) = f[j];
+            }
+        }
+        col += vector_width;
+        x = next_lanes_ps!(x, start_x, lanes, step_x, col, accumulate_x);
     }
     if remainder != 0 {
-        let f = $f(S::mul_ps(x, freq_x) $(,$arg)*);
+        let tx = transform_1d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x));
+        let f = $f(tx $(,$arg)*);
         for j in 0..remainder {
             let n = f[j];
-            *result.get_unchecked_mut(i) = n;
+            *$out.ptr.offset(row_offset + (col + j) as isize * $out.strides[0]) = n;
             // Note: This is unecessary for large images
             if n < min {
                 min = n;
@@ -56,7 +147,6 @@ macro_rules! get_1d_noise_helper_f32 {
             if n > max {
                 max = n;
             }
-            i += 1;
         }
     }
     for i in 0..vector_width {
@@ -67,19 +157,23 @@ macro_rules! get_1d_noise_helper_f32 {
             max = max_s[i];
         }
     }
-    (result, min, max)
+    (min, max)
  }
     }
 }
 
 macro_rules! get_2d_noise_helper_f32 {
-    ($Setting:expr,$f:expr $(,$arg:expr)*)=> {{
-    let dim = $Setting.get_dimensions();
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let freq_y = S::set1_ps($Setting.freq_y);
+    ($out:expr, $dim:expr, $freq:expr, $f:expr $(,$arg:expr)*)=> {{
+    let dim = $dim.with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
+    let freq_x = S::set1_ps($freq[0]);
+    let freq_y = S::set1_ps($freq[1]);
     let start_x = dim.x;
+    let step_x = dim.step_x;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let start_y = dim.y;
+    let step_y = dim.step_y;
     let height = dim.height;
 
     let mut min_s = S::set1_ps(f32::MAX);
@@ -87,42 +181,52 @@ macro_rules! get_2d_noise_helper_f32 {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result = Vec::with_capacity(width * height);
-    result.set_len(width * height);
-    let mut y = S::set1_ps(start_y);
-    let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
+        x_arr[i] = i as f32;
     }
-    for _ in 0..height {
-        let mut x = S::loadu_ps(&x_arr[0]);
+    let lanes = S::loadu_ps(&x_arr[0]);
+    let first_x = lanes_at_ps!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f32>::new(&dim, &[$freq[0], $freq[1]], vector_width);
+    for row_y in 0..height {
+        let row_offset = row_y as isize * $out.strides[1];
+        let mut col = 0;
+        let mut x = first_x;
         for _ in 0..width / vector_width {
-            let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y) $(,$arg)*);
-            max_s = S::max_ps(max_s, f);
-            min_s = S::min_ps(min_s, f);
-            S::storeu_ps(result.get_unchecked_mut(i), f);
-            i += vector_width;
-            x = S::add_ps(x, S::set1_ps(vector_width as f32));
+            let (tx, ty) = transform_2d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x), row_coord_ps!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version));
+            let f = $f(tx, ty $(,$arg)*);
+            if track {
+                max_s = S::max_ps(max_s, f);
+                min_s = S::min_ps(min_s, f);
+            }
+            let base = $out.ptr.offset(row_offset + col as isize * $out.strides[0]);
+            if $out.strides[0] == 1 {
+                S::storeu_ps(&mut *base, f);
+            } else {
+                for j in 0..vector_width {
+                    *base.offset(j as isize * $out.strides[0]) = f[j];
+                }
+            }
+            col += vector_width;
+            x = next_lanes_ps!(x, start_x, lanes, step_x, col, accumulate_x);
         }
         if remainder != 0 {
-            let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y) $(,$arg)*);
+            let (tx, ty) = transform_2d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x), row_coord_ps!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version));
+            let f = $f(tx, ty $(,$arg)*);
             for j in 0..remainder {
                 let n = f[j];
-                *result.get_unchecked_mut(i) = n;
+                *$out.ptr.offset(row_offset + (col + j) as isize * $out.strides[0]) = n;
                 if n < min {
                     min = n;
                 }
                 if n > max {
                     max = n;
                 }
-                i += 1;
             }
         }
-        y = S::add_ps(y, S::set1_ps(1.0));
     }
     for i in 0..vector_width {
         if min_s[i] < min {
@@ -132,22 +236,27 @@ macro_rules! get_2d_noise_helper_f32 {
             max = max_s[i];
         }
     }
-    (result, min, max)
+    (min, max)
 
 }};
 }
 
 macro_rules! get_3d_noise_helper_f32 {
-    ($Setting:expr,$f:expr $(,$arg:expr)*) => {{
-    let dim = $Setting.get_dimensions();
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let freq_y = S::set1_ps($Setting.freq_y);
-    let freq_z = S::set1_ps($Setting.freq_z);
+    ($out:expr, $dim:expr, $freq:expr, $f:expr $(,$arg:expr)*) => {{
+    let dim = $dim.with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
+    let freq_x = S::set1_ps($freq[0]);
+    let freq_y = S::set1_ps($freq[1]);
+    let freq_z = S::set1_ps($freq[2]);
     let start_x = dim.x;
+    let step_x = dim.step_x;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let start_y = dim.y;
+    let step_y = dim.step_y;
     let height = dim.height;
     let start_z = dim.z;
+    let step_z = dim.step_z;
     let depth = dim.depth;
 
     let mut min_s = S::set1_ps(f32::MAX);
@@ -155,47 +264,55 @@ macro_rules! get_3d_noise_helper_f32 {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result = Vec::with_capacity(width * height * depth);
-    result.set_len(width * height * depth);
-    let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
+        x_arr[i] = i as f32;
     }
+    let lanes = S::loadu_ps(&x_arr[0]);
+    let first_x = lanes_at_ps!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f32>::new(&dim, &[$freq[0], $freq[1], $freq[2]], vector_width);
 
-    let mut z = S::set1_ps(start_z);
-    for _ in 0..depth {
-        let mut y = S::set1_ps(start_y);
-        for _ in 0..height {
-            let mut x = S::loadu_ps(&x_arr[0]);
+    for row_z in 0..depth {
+        for row_y in 0..height {
+            let row_offset = row_y as isize * $out.strides[1] + row_z as isize * $out.strides[2];
+            let mut col = 0;
+            let mut x = first_x;
             for _ in 0..width / vector_width {
-                let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z) $(,$arg)*);
-                max_s = S::max_ps(max_s, f);
-                min_s = S::min_ps(min_s, f);
-                S::storeu_ps(result.get_unchecked_mut(i), f);
-                i += vector_width;
-                x = S::add_ps(x, S::set1_ps(vector_width as f32));
+                let (tx, ty, tz) = transform_3d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x), row_coord_ps!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_ps!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version));
+                let f = $f(tx, ty, tz $(,$arg)*);
+                if track {
+                    max_s = S::max_ps(max_s, f);
+                    min_s = S::min_ps(min_s, f);
+                }
+                let base = $out.ptr.offset(row_offset + col as isize * $out.strides[0]);
+                if $out.strides[0] == 1 {
+                    S::storeu_ps(&mut *base, f);
+                } else {
+                    for j in 0..vector_width {
+                        *base.offset(j as isize * $out.strides[0]) = f[j];
+                    }
+                }
+                col += vector_width;
+                x = next_lanes_ps!(x, start_x, lanes, step_x, col, accumulate_x);
             }
             if remainder != 0 {
-            let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z) $(,$arg)*);
+            let (tx, ty, tz) = transform_3d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x), row_coord_ps!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_ps!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version));
+            let f = $f(tx, ty, tz $(,$arg)*);
                 for j in 0..remainder {
                     let n = f[j];
-                    *result.get_unchecked_mut(i) = n;
+                    *$out.ptr.offset(row_offset + (col + j) as isize * $out.strides[0]) = n;
                     if n < min {
                         min = n;
                     }
                     if n > max {
                         max = n;
                     }
-                    i += 1;
                 }
             }
-            y = S::add_ps(y, S::set1_ps(1.0));
         }
-        z = S::add_ps(z, S::set1_ps(1.0));
     }
     for i in 0..vector_width {
         if min_s[i] < min {
@@ -205,24 +322,30 @@ macro_rules! get_3d_noise_helper_f32 {
             max = max_s[i];
         }
     }
-    (result, min, max)
+    (min, max)
 }};
 }
 
 macro_rules! get_4d_noise_helper_f32 {
-    ($Setting:expr,$f:expr $(,$arg:expr)*) => {{
-    let dim = $Setting.get_dimensions();
-    let freq_x = S::set1_ps($Setting.freq_x);
-    let freq_y = S::set1_ps($Setting.freq_y);
-    let freq_z = S::set1_ps($Setting.freq_z);
-    let freq_w = S::set1_ps($Setting.freq_w);
+    ($out:expr, $dim:expr, $freq:expr, $f:expr $(,$arg:expr)*) => {{
+    let dim = $dim.with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
+    let freq_x = S::set1_ps($freq[0]);
+    let freq_y = S::set1_ps($freq[1]);
+    let freq_z = S::set1_ps($freq[2]);
+    let freq_w = S::set1_ps($freq[3]);
     let start_x = dim.x;
+    let step_x = dim.step_x;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let start_y = dim.y;
+    let step_y = dim.step_y;
     let height = dim.height;
     let start_z = dim.z;
+    let step_z = dim.step_z;
     let depth = dim.depth;
     let start_w = dim.w;
+    let step_w = dim.step_w;
     let time = dim.time;
 
     let mut min_s = S::set1_ps(f32::MAX);
@@ -230,36 +353,46 @@ macro_rules! get_4d_noise_helper_f32 {
     let mut min = f32::MAX;
     let mut max = f32::MIN;
 
-    let mut result = Vec::with_capacity(width * height * depth * time);
-    result.set_len(width * height * depth * time);
-    let mut i = 0;
     let vector_width = S::VF32_WIDTH;
     let remainder = width % vector_width;
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f32;
+        x_arr[i] = i as f32;
     }
-    let mut w = S::set1_ps(start_w);
-    for _ in 0..time {
-        let mut z = S::set1_ps(start_z);
-        for _ in 0..depth {
-            let mut y = S::set1_ps(start_y);
-            for _ in 0..height {
-                let mut x = S::loadu_ps(&x_arr[0]);
+    let lanes = S::loadu_ps(&x_arr[0]);
+    let first_x = lanes_at_ps!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f32>::new(&dim, &[$freq[0], $freq[1], $freq[2], $freq[3]], vector_width);
+    for row_w in 0..time {
+        for row_z in 0..depth {
+            for row_y in 0..height {
+                let row_offset = row_y as isize * $out.strides[1] + row_z as isize * $out.strides[2] + row_w as isize * $out.strides[3];
+                let mut col = 0;
+                let mut x = first_x;
                 for _ in 0..width / vector_width {
-                    let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z), S::mul_ps(w, freq_w) $(,$arg)*);
-                    max_s = S::max_ps(max_s, f);
-                    min_s = S::min_ps(min_s, f);
-                    S::storeu_ps(result.get_unchecked_mut(i), f);
-                    i += vector_width;
-                    x = S::add_ps(x, S::set1_ps(vector_width as f32));
+                    let (tx, ty, tz, tw) = transform_4d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x), row_coord_ps!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_ps!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version), row_coord_ps!(chunk, 3, row_w, start_w, step_w, freq_w, dim.algorithm_version));
+                    let f = $f(tx, ty, tz, tw $(,$arg)*);
+                    if track {
+                        max_s = S::max_ps(max_s, f);
+                        min_s = S::min_ps(min_s, f);
+                    }
+                    let base = $out.ptr.offset(row_offset + col as isize * $out.strides[0]);
+                    if $out.strides[0] == 1 {
+                        S::storeu_ps(&mut *base, f);
+                    } else {
+                        for j in 0..vector_width {
+                            *base.offset(j as isize * $out.strides[0]) = f[j];
+                        }
+                    }
+                    col += vector_width;
+                    x = next_lanes_ps!(x, start_x, lanes, step_x, col, accumulate_x);
                 }
                 if remainder != 0 {
-                    let f = $f(S::mul_ps(x, freq_x), S::mul_ps(y, freq_y), S::mul_ps(z, freq_z), S::mul_ps(w, freq_w) $(,$arg)*);
+                    let (tx, ty, tz, tw) = transform_4d::<S>(&dim.transform, lane_coords_ps!(chunk, col, x, freq_x), row_coord_ps!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_ps!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version), row_coord_ps!(chunk, 3, row_w, start_w, step_w, freq_w, dim.algorithm_version));
+                    let f = $f(tx, ty, tz, tw $(,$arg)*);
                     for j in 0..remainder {
                         let n = f[j];
-                        *result.get_unchecked_mut(i) = n;
+                        *$out.ptr.offset(row_offset + (col + j) as isize * $out.strides[0]) = n;
                         // Note: This is unecessary for large images
                         if n < min {
                             min = n;
@@ -267,14 +400,10 @@ macro_rules! get_4d_noise_helper_f32 {
                         if n > max {
                             max = n;
                         }
-                        i += 1;
                     }
                 }
-                y = S::add_ps(y, S::set1_ps(1.0));
             }
-            z = S::add_ps(z, S::set1_ps(1.0));
         }
-        w = S::add_ps(w, S::set1_ps(1.0));
     }
     for i in 0..vector_width {
         if min_s[i] < min {
@@ -284,48 +413,180 @@ macro_rules! get_4d_noise_helper_f32 {
             max = max_s[i];
         }
     }
-    (result, min, max)
+    (min, max)
 }};
 }
 
+// Runs `$body` with the `NoiseSource` and frequencies of a built in noise type. Wavelet
+// noise builds its tile, unless `$tile` gives one already built for its settings.
+macro_rules! with_source {
+    ($noise_type:expr, |$source:ident, $freq:ident| $body:expr) => {
+        with_source!(
+            $noise_type,
+            |s| wavelet_tile(s, s.get_dimensions().dim),
+            |$source, $freq| $body
+        )
+    };
+    ($noise_type:expr, |$s:ident| $tile:expr, |$source:ident, $freq:ident| $body:expr) => {
+        match $noise_type {
+            NoiseType::Fbm(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, s.freq_w]);
+                $body
+            }
+            NoiseType::Ridge(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, s.freq_w]);
+                $body
+            }
+            NoiseType::Turbulence(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, s.freq_w]);
+                $body
+            }
+            NoiseType::Gradient(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, s.freq_w]);
+                $body
+            }
+            NoiseType::Cellular(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, 0.0]);
+                $body
+            }
+            NoiseType::Cellular2(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, 0.0]);
+                $body
+            }
+            NoiseType::WhiteNoise(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, s.freq_w]);
+                $body
+            }
+            NoiseType::Gabor(s) => {
+                let ($source, $freq) = (s, [s.freq_x, s.freq_y, s.freq_z, 0.0]);
+                $body
+            }
+            NoiseType::Wavelet($s) => {
+                let tile = $tile;
+                let wavelet = WaveletSource::new($s, &tile);
+                let ($source, $freq) = (&wavelet, [$s.freq_x, $s.freq_y, $s.freq_z, 0.0]);
+                $body
+            }
+        }
+    };
+}
+
+/// Writes a block of 1d noise from `source` to `out`, with coordinates scaled by
+/// `freq`, returning the measured min and max.
+#[inline(always)]
+pub(crate) unsafe fn get_1d_source_into<S: Simd, N: NoiseSource<S> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+    freq: &[f32; 4],
+    out: &BlockOut,
+) -> (f32, f32) {
+    get_1d_noise_helper_f32!(out, *dim, *freq, |x| source.sample_1d(x))
+}
+
+/// Writes a block of 2d noise from `source` to `out`, see `get_1d_source_into`
+#[inline(always)]
+pub(crate) unsafe fn get_2d_source_into<S: Simd, N: NoiseSource<S> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+    freq: &[f32; 4],
+    out: &BlockOut,
+) -> (f32, f32) {
+    get_2d_noise_helper_f32!(out, *dim, *freq, |x, y| source.sample_2d(x, y))
+}
+
+/// Writes a block of 3d noise from `source` to `out`, see `get_1d_source_into`
+#[inline(always)]
+pub(crate) unsafe fn get_3d_source_into<S: Simd, N: NoiseSource<S> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+    freq: &[f32; 4],
+    out: &BlockOut,
+) -> (f32, f32) {
+    get_3d_noise_helper_f32!(out, *dim, *freq, |x, y, z| source.sample_3d(x, y, z))
+}
+
+/// Writes a block of 4d noise from `source` to `out`, see `get_1d_source_into`
+#[inline(always)]
+pub(crate) unsafe fn get_4d_source_into<S: Simd, N: NoiseSource<S> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+    freq: &[f32; 4],
+    out: &BlockOut,
+) -> (f32, f32) {
+    get_4d_noise_helper_f32!(out, *dim, *freq, |x, y, z, w| source.sample_4d(x, y, z, w))
+}
+
+/// Gets the block of `dim` from a user defined source, at a frequency of 1, along
+/// with its min and max.
+#[inline(always)]
+pub unsafe fn get_source_noise<S: Simd, N: NoiseSource<S> + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+) -> (Vec<f32>, f32, f32) {
+    let mut result = Vec::with_capacity(block_len(dim));
+    result.set_len(block_len(dim));
+    let out = BlockOut::contiguous(&mut result, dim);
+    let freq = [1.0; 4];
+    let (min, max) = match dim.dim {
+        1 => get_1d_source_into::<S, N>(source, dim, &freq, &out),
+        2 => get_2d_source_into::<S, N>(source, dim, &freq, &out),
+        3 => get_3d_source_into::<S, N>(source, dim, &freq, &out),
+        4 => get_4d_source_into::<S, N>(source, dim, &freq, &out),
+        _ => panic!("not implemented"),
+    };
+    match dim.range_mode {
+        RangeMode::Measured => (result, min, max),
+        RangeMode::Analytic => match source.range() {
+            Some((min, max)) => (result, min, max),
+            None => (result, f32::NAN, f32::NAN),
+        },
+        RangeMode::Untracked => (result, f32::NAN, f32::NAN),
+    }
+}
+
+/// Gets a block of wavelet noise sampling `tile`, see `WaveletSettings::generate_with_tile`
+#[inline(always)]
+pub unsafe fn get_wavelet_noise<S: Simd>(
+    settings: &WaveletSettings,
+    tile: &WaveletTile,
+) -> (Vec<f32>, f32, f32) {
+    let dim = settings.get_dimensions();
+    let mut result = Vec::with_capacity(block_len(&dim));
+    result.set_len(block_len(&dim));
+    let out = BlockOut::contiguous(&mut result, &dim);
+    let source = WaveletSource::new(settings, tile);
+    let freq = [settings.freq_x, settings.freq_y, settings.freq_z, 0.0];
+    let (min, max) = match dim.dim {
+        2 => get_2d_source_into::<S, _>(&source, &dim, &freq, &out),
+        3 => get_3d_source_into::<S, _>(&source, &dim, &freq, &out),
+        _ => panic!("not implemented"),
+    };
+    let (min, max) = resolve_range(&NoiseType::Wavelet(*settings), min, max);
+    (result, min, max)
+}
+
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_1d_noise_helper_f32!(
-            s,
-            fbm_1d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Ridge(s) => get_1d_noise_helper_f32!(
-            s,
-            ridge_1d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Turbulence(s) => get_1d_noise_helper_f32!(
-            s,
-            turbulence_1d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Gradient(s) => {
-            get_1d_noise_helper_f32!(s, simplex_1d::<S>, s.get_dimensions().seed)
-        }
-        NoiseType::Cellular(_) => {
-            panic!("not implemented");
-        }
-        NoiseType::Cellular2(_) => {
-            panic!("not implemented");
-        }
-    }
+    let dim = noise_type.get_dimensions();
+    let mut result = Vec::with_capacity(block_len(&dim));
+    result.set_len(block_len(&dim));
+    let (min, max) = get_1d_noise_into::<S>(noise_type, &BlockOut::contiguous(&mut result, &dim));
+    (result, min, max)
+}
+
+/// Writes a block of 1d noise to `out`, returning the min and max.
+#[inline(always)]
+#[allow(dead_code)]
+pub(crate) unsafe fn get_1d_noise_into<S: Simd>(
+    noise_type: &NoiseType,
+    out: &BlockOut,
+) -> (f32, f32) {
+    let dim = noise_type.get_dimensions();
+    let (min, max) = with_source!(noise_type, |source, freq| {
+        get_1d_source_into::<S, _>(source, &dim, &freq, out)
+    });
+    resolve_range(noise_type, min, max)
 }
 
 /// Gets a width X height sized block of 2d noise, unscaled.
@@ -336,53 +597,25 @@ pub unsafe fn get_1d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_2d_noise_helper_f32!(
-            s,
-            fbm_2d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Ridge(s) => get_2d_noise_helper_f32!(
-            s,
-            ridge_2d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Turbulence(s) => get_2d_noise_helper_f32!(
-            s,
-            turbulence_2d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Gradient(s) => {
-            get_2d_noise_helper_f32!(s, simplex_2d::<S>, s.get_dimensions().seed)
-        }
-        NoiseType::Cellular(s) => get_2d_noise_helper_f32!(
-            s,
-            cellular_2d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.get_dimensions().seed
-        ),
-        NoiseType::Cellular2(s) => get_2d_noise_helper_f32!(
-            s,
-            cellular2_2d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.index0,
-            s.index1,
-            s.get_dimensions().seed
-        ),
-    }
+    let dim = noise_type.get_dimensions();
+    let mut result = Vec::with_capacity(block_len(&dim));
+    result.set_len(block_len(&dim));
+    let (min, max) = get_2d_noise_into::<S>(noise_type, &BlockOut::contiguous(&mut result, &dim));
+    (result, min, max)
+}
+
+/// Writes a block of 2d noise to `out`, returning the min and max.
+#[inline(always)]
+#[allow(dead_code)]
+pub(crate) unsafe fn get_2d_noise_into<S: Simd>(
+    noise_type: &NoiseType,
+    out: &BlockOut,
+) -> (f32, f32) {
+    let dim = noise_type.get_dimensions();
+    let (min, max) = with_source!(noise_type, |source, freq| {
+        get_2d_source_into::<S, _>(source, &dim, &freq, out)
+    });
+    resolve_range(noise_type, min, max)
 }
 
 /// Gets a width X height X depth sized block of 3d noise, unscaled,
@@ -393,91 +626,334 @@ pub unsafe fn get_2d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_3d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_3d_noise_helper_f32!(
-            s,
-            fbm_3d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Ridge(s) => get_3d_noise_helper_f32!(
-            s,
-            ridge_3d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Turbulence(s) => get_3d_noise_helper_f32!(
-            s,
-            turbulence_3d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Gradient(s) => {
-            get_3d_noise_helper_f32!(s, simplex_3d::<S>, s.get_dimensions().seed)
+    let dim = noise_type.get_dimensions();
+    let mut result = Vec::with_capacity(block_len(&dim));
+    result.set_len(block_len(&dim));
+    let (min, max) = get_3d_noise_into::<S>(noise_type, &BlockOut::contiguous(&mut result, &dim));
+    (result, min, max)
+}
+
+/// Writes a block of 3d noise to `out`, returning the min and max.
+#[inline(always)]
+#[allow(dead_code)]
+pub(crate) unsafe fn get_3d_noise_into<S: Simd>(
+    noise_type: &NoiseType,
+    out: &BlockOut,
+) -> (f32, f32) {
+    let dim = noise_type.get_dimensions();
+    let (min, max) = with_source!(noise_type, |source, freq| {
+        get_3d_source_into::<S, _>(source, &dim, &freq, out)
+    });
+    resolve_range(noise_type, min, max)
+}
+
+#[inline(always)]
+#[allow(dead_code)]
+pub unsafe fn get_4d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
+    let dim = noise_type.get_dimensions();
+    let mut result = Vec::with_capacity(block_len(&dim));
+    result.set_len(block_len(&dim));
+    let (min, max) = get_4d_noise_into::<S>(noise_type, &BlockOut::contiguous(&mut result, &dim));
+    (result, min, max)
+}
+
+/// Writes a block of 4d noise to `out`, returning the min and max.
+#[inline(always)]
+#[allow(dead_code)]
+pub(crate) unsafe fn get_4d_noise_into<S: Simd>(
+    noise_type: &NoiseType,
+    out: &BlockOut,
+) -> (f32, f32) {
+    let dim = noise_type.get_dimensions();
+    let (min, max) = with_source!(noise_type, |source, freq| {
+        get_4d_source_into::<S, _>(source, &dim, &freq, out)
+    });
+    resolve_range(noise_type, min, max)
+}
+
+/// Gets a block of noise of any dimension, unscaled, along with its min and max.
+#[inline(always)]
+pub unsafe fn get_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
+    match noise_type.get_dimensions().dim {
+        1 => get_1d_noise::<S>(noise_type),
+        2 => get_2d_noise::<S>(noise_type),
+        3 => get_3d_noise::<S>(noise_type),
+        4 => get_4d_noise::<S>(noise_type),
+        _ => panic!("not implemented"),
+    }
+}
+
+/// Writes a block of noise of any dimension to `out`, returning the min and max.
+#[cfg(feature = "ndarray")]
+#[inline(always)]
+pub(crate) unsafe fn get_noise_into<S: Simd>(noise_type: &NoiseType, out: &BlockOut) -> (f32, f32) {
+    match noise_type.get_dimensions().dim {
+        1 => get_1d_noise_into::<S>(noise_type, out),
+        2 => get_2d_noise_into::<S>(noise_type, out),
+        3 => get_3d_noise_into::<S>(noise_type, out),
+        4 => get_4d_noise_into::<S>(noise_type, out),
+        _ => panic!("not implemented"),
+    }
+}
+
+/// Generates a block of noise a row along x at a time, calling `f` with each row in the
+/// order of `get_Nd_noise`, and returns the min and max of the whole block. Stops at the
+/// first error `f` returns.
+#[inline(always)]
+pub(crate) unsafe fn get_noise_rows<S: Simd, E>(
+    noise_type: &NoiseType,
+    f: &mut dyn FnMut(&[f32]) -> Result<(), E>,
+) -> Result<(f32, f32), E> {
+    let dim = noise_type.get_dimensions();
+    let rows: usize = [dim.height, dim.depth, dim.time][..dim.dim - 1]
+        .iter()
+        .product();
+    let mut row = vec![0.0; dim.width];
+    let (min, max) = with_source!(noise_type, |source, freq| {
+        let mut min = f32::MAX;
+        let mut max = f32::MIN;
+        for index in 0..rows {
+            let row_dim = row_dimensions(&dim, index);
+            let out = BlockOut::contiguous(&mut row, &row_dim);
+            let (row_min, row_max) = match dim.dim {
+                1 => get_1d_source_into::<S, _>(source, &row_dim, &freq, &out),
+                2 => get_2d_source_into::<S, _>(source, &row_dim, &freq, &out),
+                3 => get_3d_source_into::<S, _>(source, &row_dim, &freq, &out),
+                4 => get_4d_source_into::<S, _>(source, &row_dim, &freq, &out),
+                _ => panic!("not implemented"),
+            };
+            min = min.min(row_min);
+            max = max.max(row_max);
+            f(&row)?;
+        }
+        (min, max)
+    });
+    Ok(resolve_range(noise_type, min, max))
+}
+
+// Row `index` of the block as a block of its own. The offsets are computed like the
+// row coordinates of the full block, so the row has exactly the same samples.
+fn row_dimensions(dim: &NoiseDimensions, index: usize) -> NoiseDimensions {
+    let mut row = *dim;
+    let extents = [dim.height, dim.depth, dim.time];
+    let mut rest = index;
+    for axis in 1..dim.dim {
+        let i = rest % extents[axis - 1];
+        rest /= extents[axis - 1];
+        match &mut row.chunk_origin {
+            Some(origin) => origin[axis] += i as i64,
+            None => match axis {
+                1 => row.y = row_position(dim.y, dim.step_y, i, dim.algorithm_version),
+                2 => row.z = row_position(dim.z, dim.step_z, i, dim.algorithm_version),
+                _ => row.w = row_position(dim.w, dim.step_w, i, dim.algorithm_version),
+            },
         }
-        NoiseType::Cellular(s) => get_3d_noise_helper_f32!(
-            s,
-            cellular_3d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.get_dimensions().seed
-        ),
-        NoiseType::Cellular2(s) => get_3d_noise_helper_f32!(
-            s,
-            cellular2_3d::<S>,
-            s.distance_function,
-            s.return_type,
-            S::set1_ps(s.jitter),
-            s.index0,
-            s.index1,
-            s.get_dimensions().seed
-        ),
     }
+    row.height = 1;
+    row.depth = 1;
+    row.time = 1;
+    row
 }
 
+/// The coordinate of row `index` along an axis starting at `start`, see
+/// `AlgorithmVersion::accumulates_steps`
+pub(crate) fn row_position(start: f32, step: f32, index: usize, version: AlgorithmVersion) -> f32 {
+    if version.accumulates_steps(step) {
+        (0..index).fold(start, |offset, _| offset + step)
+    } else {
+        start + index as f32 * step
+    }
+}
+
+/// Gets the raw integer hashes behind a block of white noise, in the same
+/// layout as the values returned by `get_Nd_noise`.
 #[inline(always)]
 #[allow(dead_code)]
-pub unsafe fn get_4d_noise<S: Simd>(noise_type: &NoiseType) -> (Vec<f32>, f32, f32) {
-    match noise_type {
-        NoiseType::Fbm(s) => get_4d_noise_helper_f32!(
-            s,
-            fbm_4d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Ridge(s) => get_4d_noise_helper_f32!(
-            s,
-            ridge_4d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Turbulence(s) => get_4d_noise_helper_f32!(
-            s,
-            turbulence_4d::<S>,
-            S::set1_ps(s.lacunarity),
-            S::set1_ps(s.gain),
-            s.octaves,
-            s.get_dimensions().seed
-        ),
-        NoiseType::Gradient(s) => {
-            get_4d_noise_helper_f32!(s, simplex_4d::<S>, s.get_dimensions().seed)
+pub unsafe fn get_white_hash<S: Simd>(settings: &WhiteNoiseSettings) -> Vec<i32> {
+    let dim = settings.get_dimensions().with_seed_offset();
+    let seed = dim.seed;
+    let width = dim.width;
+    let height = if dim.dim > 1 { dim.height } else { 1 };
+    let depth = if dim.dim > 2 { dim.depth } else { 1 };
+    let time = if dim.dim > 3 { dim.time } else { 1 };
+
+    let vector_width = S::VF32_WIDTH;
+    let mut result = Vec::with_capacity(width * height * depth * time);
+    let mut x_arr = Vec::with_capacity(vector_width);
+    x_arr.set_len(vector_width);
+    for i in (0..vector_width).rev() {
+        x_arr[i] = i as f32;
+    }
+    let lanes = S::loadu_ps(&x_arr[0]);
+    let freq_x = S::set1_ps(settings.freq_x);
+    let start_x = dim.x;
+    let step_x = dim.step_x;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
+    let first_x = lanes_at_ps!(start_x, lanes, step_x, 0);
+    let freq = [
+        settings.freq_x,
+        settings.freq_y,
+        settings.freq_z,
+        settings.freq_w,
+    ];
+    let chunk = ChunkAxes::<f32>::new(&dim, &freq, vector_width);
+    let row = |axis: usize, offset: f32, step: f32, index: usize| match &chunk {
+        Some(c) => S::set1_ps(c.axes[axis][index]),
+        None => S::set1_ps(row_position(offset, step, index, dim.algorithm_version) * freq[axis]),
+    };
+    for w in 0..time {
+        let w = row(3, dim.w, dim.step_w, w);
+        for z in 0..depth {
+            let z = row(2, dim.z, dim.step_z, z);
+            for y in 0..height {
+                let y = row(1, dim.y, dim.step_y, y);
+                let mut col = 0;
+                let mut x = first_x;
+                let mut remaining = width;
+                while remaining > 0 {
+                    let xf = lane_coords_ps!(chunk, col, x, freq_x);
+                    let t = &dim.transform;
+                    let hash = match dim.dim {
+                        1 => white_hash_1d::<S>(transform_1d::<S>(t, xf), seed),
+                        2 => {
+                            let (tx, ty) = transform_2d::<S>(t, xf, y);
+                            white_hash_2d::<S>(tx, ty, seed)
+                        }
+                        3 => {
+                            let (tx, ty, tz) = transform_3d::<S>(t, xf, y, z);
+                            white_hash_3d::<S>(tx, ty, tz, seed)
+                        }
+                        4 => {
+                            let (tx, ty, tz, tw) = transform_4d::<S>(t, xf, y, z, w);
+                            white_hash_4d::<S>(tx, ty, tz, tw, seed)
+                        }
+                        _ => panic!("not implemented"),
+                    };
+                    for j in 0..remaining.min(vector_width) {
+                        result.push(hash[j]);
+                    }
+                    remaining = remaining.saturating_sub(vector_width);
+                    col += vector_width;
+                    x = next_lanes_ps!(x, start_x, lanes, step_x, col, accumulate_x);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Gets a single vector of `dim` dimensional noise at unscaled coordinates, applying the
+/// frequency and transform of the settings the same way the block functions do. Wavelet
+/// noise samples `tile`, which must be built by `point::wavelet_tile` for its settings.
+#[inline(always)]
+pub(crate) unsafe fn sample_noise<S: Simd>(
+    noise_type: &NoiseType,
+    tile: Option<&WaveletTile>,
+    dim: usize,
+    x: S::Vf32,
+    y: S::Vf32,
+    z: S::Vf32,
+    w: S::Vf32,
+) -> S::Vf32 {
+    let transform = noise_type.get_dimensions().with_seed_offset().transform;
+    with_source!(
+        noise_type,
+        |_s| tile.expect("wavelet noise needs a tile"),
+        |source, freq| {
+            let x = S::mul_ps(x, S::set1_ps(freq[0]));
+            let y = S::mul_ps(y, S::set1_ps(freq[1]));
+            let z = S::mul_ps(z, S::set1_ps(freq[2]));
+            let w = S::mul_ps(w, S::set1_ps(freq[3]));
+            match dim {
+                1 => NoiseSource::<S>::sample_1d(source, transform_1d::<S>(&transform, x)),
+                2 => {
+                    let (x, y) = transform_2d::<S>(&transform, x, y);
+                    NoiseSource::<S>::sample_2d(source, x, y)
+                }
+                3 => {
+                    let (x, y, z) = transform_3d::<S>(&transform, x, y, z);
+                    NoiseSource::<S>::sample_3d(source, x, y, z)
+                }
+                4 => {
+                    let (x, y, z, w) = transform_4d::<S>(&transform, x, y, z, w);
+                    NoiseSource::<S>::sample_4d(source, x, y, z, w)
+                }
+                _ => panic!("not implemented"),
+            }
         }
-        NoiseType::Cellular(_) => {
-            panic!("not implemented");
+    )
+}
+
+/// Gets `dim` dimensional noise at 8 points, with one array of unscaled coordinates per
+/// axis. Only the vectors covering the first `len` points are evaluated.
+#[inline(always)]
+pub unsafe fn sample_lanes<S: Simd>(
+    noise_type: &NoiseType,
+    tile: Option<&WaveletTile>,
+    dim: usize,
+    coords: &[[f32; 8]; 4],
+    len: usize,
+) -> [f32; 8] {
+    let mut result = [0.0; 8];
+    let mut i = 0;
+    while i < len {
+        let x = S::loadu_ps(&coords[0][i]);
+        let y = S::loadu_ps(&coords[1][i]);
+        let z = S::loadu_ps(&coords[2][i]);
+        let w = S::loadu_ps(&coords[3][i]);
+        let v = sample_noise::<S>(noise_type, tile, dim, x, y, z, w);
+        S::storeu_ps(&mut result[i], v);
+        i += S::VF32_WIDTH;
+    }
+    result
+}
+
+/// Gets a block of noise from a composition graph, in the same layout and with the
+/// same min and max tracking as `get_Nd_noise`. The graph's own frequency and
+/// transform are applied first, then each source applies its own.
+#[inline(always)]
+#[allow(dead_code)]
+pub unsafe fn get_graph_noise<S: Simd>(graph: &NoiseGraph) -> (Vec<f32>, f32, f32) {
+    let dim = graph.get_dimensions();
+    let mut result = Vec::with_capacity(block_len(&dim));
+    result.set_len(block_len(&dim));
+    let out = BlockOut::contiguous(&mut result, &dim);
+    let freq = [graph.freq_x, graph.freq_y, graph.freq_z, graph.freq_w];
+    let mut regs = Vec::with_capacity(graph.len());
+    let (min, max) = match dim.dim {
+        1 => get_1d_noise_helper_f32!(out, dim, freq, |x| graph.eval_1d::<S>(&mut regs, x)),
+        2 => get_2d_noise_helper_f32!(out, dim, freq, |x, y| graph.eval_2d::<S>(&mut regs, x, y)),
+        3 => get_3d_noise_helper_f32!(out, dim, freq, |x, y, z| graph
+            .eval_3d::<S>(&mut regs, x, y, z)),
+        4 => get_4d_noise_helper_f32!(out, dim, freq, |x, y, z, w| graph
+            .eval_4d::<S>(&mut regs, x, y, z, w)),
+        _ => panic!("not implemented"),
+    };
+    match dim.range_mode {
+        RangeMode::Measured => (result, min, max),
+        RangeMode::Analytic => {
+            let (min, max) = graph.analytic_range();
+            (result, min, max)
         }
-        NoiseType::Cellular2(_) => {
-            panic!("not implemented");
+        RangeMode::Untracked => (result, f32::NAN, f32::NAN),
+    }
+}
+
+// Replaces the measured min and max according to the range mode of the settings
+fn resolve_range(noise_type: &NoiseType, min: f32, max: f32) -> (f32, f32) {
+    match noise_type.get_dimensions().range_mode {
+        RangeMode::Measured => (min, max),
+        RangeMode::Analytic => {
+            let (min, max) = noise_type.analytic_range();
+            (min as f32, max as f32)
         }
+        RangeMode::Untracked => (f32::NAN, f32::NAN),
     }
 }
+
+/// Number of samples in a block
+pub(crate) fn block_len(dim: &NoiseDimensions) -> usize {
+    [dim.width, dim.height, dim.depth, dim.time][..dim.dim]
+        .iter()
+        .product()
+}
@@ -1,23 +1,87 @@
+use alloc::vec::Vec;
+
 use simdeez::Simd;
 
 use super::NoiseType;
+use crate::chunk::ChunkAxes;
 use crate::dimensional_being::DimensionalBeing;
+use crate::range_mode::RangeMode;
+use crate::AlgorithmVersion;
+
+use crate::noise::cell2_64::{cellular2_2d_versioned, cellular2_3d_versioned};
+use crate::noise::cell_64::{cellular_2d_versioned, cellular_3d_versioned};
+use crate::noise::fbm_64::{fbm_1d_ext, fbm_2d_ext, fbm_3d_ext, fbm_4d_ext};
+use crate::noise::ridge_64::{ridge_1d_ext, ridge_2d_ext, ridge_3d_ext, ridge_4d_ext};
+use crate::noise::simplex_64::{
+    simplex_1d_versioned, simplex_2d_versioned, simplex_3d_versioned, simplex_4d_versioned,
+};
+use crate::noise::turbulence_64::{
+    turbulence_1d_ext, turbulence_2d_ext, turbulence_3d_ext, turbulence_4d_ext,
+};
+use crate::noise::white_64::{white_1d, white_2d, white_3d, white_4d};
+use crate::transform::{transform_1d_f64, transform_2d_f64, transform_3d_f64, transform_4d_f64};
 
-use crate::noise::cell2_64::{cellular2_2d, cellular2_3d};
-use crate::noise::cell_64::{cellular_2d, cellular_3d};
-use crate::noise::fbm_64::{fbm_1d, fbm_2d, fbm_3d, fbm_4d};
-use crate::noise::ridge_64::{ridge_1d, ridge_2d, ridge_3d, ridge_4d};
-use crate::noise::simplex_64::{simplex_1d, simplex_2d, simplex_3d, simplex_4d};
-use crate::noise::turbulence_64::{turbulence_1d, turbulence_2d, turbulence_3d, turbulence_4d};
+use core::f64;
+
+// The coordinates of the vector of samples starting at column `col`, `start + (lanes + col) *
+// step`. `lanes` holds 0, 1, 2...
+macro_rules! lanes_at_pd {
+    ($start:expr, $lanes:expr, $step:expr, $col:expr) => {
+        S::add_pd(
+            S::set1_pd($start),
+            S::mul_pd(
+                S::add_pd($lanes, S::set1_pd($col as f64)),
+                S::set1_pd($step),
+            ),
+        )
+    };
+}
 
-use std::f64;
+// The coordinates of the vector after `x`, which starts at column `col`. Accumulated
+// coordinates add the vector width like 3.1 did, see `AlgorithmVersion::accumulates_steps`.
+macro_rules! next_lanes_pd {
+    ($x:expr, $start:expr, $lanes:expr, $step:expr, $col:expr, $accumulate:expr) => {
+        if $accumulate {
+            S::add_pd($x, S::set1_pd(S::VF64_WIDTH as f64))
+        } else {
+            lanes_at_pd!($start, $lanes, $step, $col)
+        }
+    };
+}
+
+// The frequency scaled coordinates `x` of the vector of samples starting at column `col`,
+// taken from the chunk tables when generating a chunk
+macro_rules! lane_coords_pd {
+    ($chunk:expr, $col:expr, $x:expr, $freq:expr) => {
+        match &$chunk {
+            Some(c) => S::loadu_pd(&c.axes[0][$col]),
+            None => S::mul_pd($x, $freq),
+        }
+    };
+}
+
+// The frequency scaled coordinate of a row along y, z or w
+macro_rules! row_coord_pd {
+    ($chunk:expr, $axis:expr, $index:expr, $start:expr, $step:expr, $freq:expr, $version:expr) => {
+        match &$chunk {
+            Some(c) => S::set1_pd(c.axes[$axis][$index]),
+            None => S::mul_pd(
+                S::set1_pd(row_position_f64($start, $step, $index, $version)),
+                $freq,
+            ),
+        }
+    };
+}
 
 macro_rules! get_1d_noise_helper_f64  {
     ($Setting:expr,$f:expr $(,$arg:expr)*) => {
  {
-    let dim = $Setting.get_dimensions();
+    let dim = $Setting.get_dimensions().with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
     let freq_x = S::set1_pd($Setting.freq_x as f64);
     let start_x = dim.x as f64;
+    let step_x = dim.step_x as f64;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let mut min_s = S::set1_pd(f64::MAX);
     let mut max_s = S::set1_pd(f64::MIN);
@@ -33,19 +97,28 @@ macro_rules! get_1d_noise_helper_f64  {
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f64;
+        x_arr[i] = i as f64;
     }
-    let mut x = S::loadu_pd(&x_arr[0]);
+    let lanes = S::loadu_pd(&x_arr[0]);
+    let first_x = lanes_at_pd!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f64>::new(&dim, &[$Setting.freq_x], vector_width);
+    let mut col = 0;
+    let mut x = first_x;
     for _ in 0..width / vector_width {
-        let f = $f(S::mul_pd(x, freq_x) $(,$arg)*);
-        max_s = S::max_pd(max_s, f);
-        min_s = S::min_pd(min_s, f);
+        let tx = transform_1d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x));
+        let f = $f(tx $(,$arg)*);
+        if track {
+            max_s = S::max_pd(max_s, f);
+            min_s = S::min_pd(min_s, f);
+        }
         S::storeu_pd(result.get_unchecked_mut(i), f);
         i += vector_width;
-        x = S::add_pd(x, S::set1_pd(vector_width as f64));
+        col += vector_width;
+        x = next_lanes_pd!(x, start_x, lanes, step_x, col, accumulate_x);
     }
     if remainder != 0 {
-        let f = $f(S::mul_pd(x, freq_x) $(,$arg)*);
+        let tx = transform_1d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x));
+        let f = $f(tx $(,$arg)*);
         for j in 0..remainder {
             let n = f[j];
             *result.get_unchecked_mut(i) = n;
@@ -74,12 +147,16 @@ macro_rules! get_1d_noise_helper_f64  {
 
 macro_rules! get_2d_noise_helper_f64 {
     ($Setting:expr,$f:expr $(,$arg:expr)*)=> {{
-    let dim = $Setting.get_dimensions();
+    let dim = $Setting.get_dimensions().with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
     let freq_x = S::set1_pd($Setting.freq_x as f64);
     let freq_y = S::set1_pd($Setting.freq_y as f64);
     let start_x = dim.x as f64;
+    let step_x = dim.step_x as f64;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let start_y = dim.y as f64;
+    let step_y = dim.step_y as f64;
     let height = dim.height;
 
     let mut min_s = S::set1_pd(f64::MAX);
@@ -89,27 +166,35 @@ macro_rules! get_2d_noise_helper_f64 {
 
     let mut result = Vec::with_capacity(width * height);
     result.set_len(width * height);
-    let mut y = S::set1_pd(start_y);
     let mut i = 0;
     let vector_width = S::VF64_WIDTH;
     let remainder = width % vector_width;
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f64;
+        x_arr[i] = i as f64;
     }
-    for _ in 0..height {
-        let mut x = S::loadu_pd(&x_arr[0]);
+    let lanes = S::loadu_pd(&x_arr[0]);
+    let first_x = lanes_at_pd!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f64>::new(&dim, &[$Setting.freq_x, $Setting.freq_y], vector_width);
+    for row_y in 0..height {
+        let mut col = 0;
+        let mut x = first_x;
         for _ in 0..width / vector_width {
-            let f = $f(S::mul_pd(x, freq_x), S::mul_pd(y, freq_y) $(,$arg)*);
-            max_s = S::max_pd(max_s, f);
-            min_s = S::min_pd(min_s, f);
+            let (tx, ty) = transform_2d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x), row_coord_pd!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version));
+            let f = $f(tx, ty $(,$arg)*);
+            if track {
+                max_s = S::max_pd(max_s, f);
+                min_s = S::min_pd(min_s, f);
+            }
             S::storeu_pd(result.get_unchecked_mut(i), f);
             i += vector_width;
-            x = S::add_pd(x, S::set1_pd(vector_width as f64));
+            col += vector_width;
+            x = next_lanes_pd!(x, start_x, lanes, step_x, col, accumulate_x);
         }
         if remainder != 0 {
-            let f = $f(S::mul_pd(x, freq_x), S::mul_pd(y, freq_y) $(,$arg)*);
+            let (tx, ty) = transform_2d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x), row_coord_pd!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version));
+            let f = $f(tx, ty $(,$arg)*);
             for j in 0..remainder {
                 let n = f[j];
                 *result.get_unchecked_mut(i) = n;
@@ -122,7 +207,6 @@ macro_rules! get_2d_noise_helper_f64 {
                 i += 1;
             }
         }
-        y = S::add_pd(y, S::set1_pd(1.0));
     }
     for i in 0..vector_width {
         if min_s[i] < min {
@@ -139,15 +223,20 @@ macro_rules! get_2d_noise_helper_f64 {
 
 macro_rules! get_3d_noise_helper_f64 {
     ($Setting:expr,$f:expr $(,$arg:expr)*) => {{
-    let dim = $Setting.get_dimensions();
+    let dim = $Setting.get_dimensions().with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
     let freq_x = S::set1_pd($Setting.freq_x as f64);
     let freq_y = S::set1_pd($Setting.freq_y as f64);
     let freq_z = S::set1_pd($Setting.freq_z as f64);
     let start_x = dim.x as f64;
+    let step_x = dim.step_x as f64;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let start_y = dim.y as f64;
+    let step_y = dim.step_y as f64;
     let height = dim.height;
     let start_z = dim.z as f64;
+    let step_z = dim.step_z as f64;
     let depth = dim.depth;
 
     let mut min_s = S::set1_pd(f64::MAX);
@@ -163,24 +252,31 @@ macro_rules! get_3d_noise_helper_f64 {
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f64;
+        x_arr[i] = i as f64;
     }
+    let lanes = S::loadu_pd(&x_arr[0]);
+    let first_x = lanes_at_pd!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f64>::new(&dim, &[$Setting.freq_x, $Setting.freq_y, $Setting.freq_z], vector_width);
 
-    let mut z = S::set1_pd(start_z);
-    for _ in 0..depth {
-        let mut y = S::set1_pd(start_y);
-        for _ in 0..height {
-            let mut x = S::loadu_pd(&x_arr[0]);
+    for row_z in 0..depth {
+        for row_y in 0..height {
+            let mut col = 0;
+            let mut x = first_x;
             for _ in 0..width / vector_width {
-                let f = $f(S::mul_pd(x, freq_x), S::mul_pd(y, freq_y), S::mul_pd(z, freq_z) $(,$arg)*);
-                max_s = S::max_pd(max_s, f);
-                min_s = S::min_pd(min_s, f);
+                let (tx, ty, tz) = transform_3d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x), row_coord_pd!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_pd!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version));
+                let f = $f(tx, ty, tz $(,$arg)*);
+                if track {
+                    max_s = S::max_pd(max_s, f);
+                    min_s = S::min_pd(min_s, f);
+                }
                 S::storeu_pd(result.get_unchecked_mut(i), f);
                 i += vector_width;
-                x = S::add_pd(x, S::set1_pd(vector_width as f64));
+                col += vector_width;
+                x = next_lanes_pd!(x, start_x, lanes, step_x, col, accumulate_x);
             }
             if remainder != 0 {
-            let f = $f(S::mul_pd(x, freq_x), S::mul_pd(y, freq_y), S::mul_pd(z, freq_z) $(,$arg)*);
+            let (tx, ty, tz) = transform_3d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x), row_coord_pd!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_pd!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version));
+            let f = $f(tx, ty, tz $(,$arg)*);
                 for j in 0..remainder {
                     let n = f[j];
                     *result.get_unchecked_mut(i) = n;
@@ -193,9 +289,7 @@ macro_rules! get_3d_noise_helper_f64 {
                     i += 1;
                 }
             }
-            y = S::add_pd(y, S::set1_pd(1.0));
         }
-        z = S::add_pd(z, S::set1_pd(1.0));
     }
     for i in 0..vector_width {
         if min_s[i] < min {
@@ -211,18 +305,24 @@ macro_rules! get_3d_noise_helper_f64 {
 
 macro_rules! get_4d_noise_helper_f64 {
     ($Setting:expr,$f:expr $(,$arg:expr)*) => {{
-    let dim = $Setting.get_dimensions();
+    let dim = $Setting.get_dimensions().with_seed_offset();
+    let track = dim.range_mode == RangeMode::Measured;
     let freq_x = S::set1_pd($Setting.freq_x as f64);
     let freq_y = S::set1_pd($Setting.freq_y as f64);
     let freq_z = S::set1_pd($Setting.freq_z as f64);
     let freq_w = S::set1_pd($Setting.freq_w as f64);
     let start_x = dim.x as f64;
+    let step_x = dim.step_x as f64;
+    let accumulate_x = dim.algorithm_version.accumulates_steps(dim.step_x);
     let width = dim.width;
     let start_y = dim.y as f64;
+    let step_y = dim.step_y as f64;
     let height = dim.height;
     let start_z = dim.z as f64;
+    let step_z = dim.step_z as f64;
     let depth = dim.depth;
     let start_w = dim.w as f64;
+    let step_w = dim.step_w as f64;
     let time = dim.time;
 
     let mut min_s = S::set1_pd(f64::MAX);
@@ -238,25 +338,31 @@ macro_rules! get_4d_noise_helper_f64 {
     let mut x_arr = Vec::with_capacity(vector_width);
     x_arr.set_len(vector_width);
     for i in (0..vector_width).rev() {
-        x_arr[i] = start_x + i as f64;
+        x_arr[i] = i as f64;
     }
-    let mut w = S::set1_pd(start_w);
-    for _ in 0..time {
-        let mut z = S::set1_pd(start_z);
-        for _ in 0..depth {
-            let mut y = S::set1_pd(start_y);
-            for _ in 0..height {
-                let mut x = S::loadu_pd(&x_arr[0]);
+    let lanes = S::loadu_pd(&x_arr[0]);
+    let first_x = lanes_at_pd!(start_x, lanes, step_x, 0);
+    let chunk = ChunkAxes::<f64>::new(&dim, &[$Setting.freq_x, $Setting.freq_y, $Setting.freq_z, $Setting.freq_w], vector_width);
+    for row_w in 0..time {
+        for row_z in 0..depth {
+            for row_y in 0..height {
+                let mut col = 0;
+                let mut x = first_x;
                 for _ in 0..width / vector_width {
-                    let f = $f(S::mul_pd(x, freq_x), S::mul_pd(y, freq_y), S::mul_pd(z, freq_z), S::mul_pd(w, freq_w) $(,$arg)*);
-                    max_s = S::max_pd(max_s, f);
-                    min_s = S::min_pd(min_s, f);
+                    let (tx, ty, tz, tw) = transform_4d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x), row_coord_pd!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_pd!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version), row_coord_pd!(chunk, 3, row_w, start_w, step_w, freq_w, dim.algorithm_version));
+                    let f = $f(tx, ty, tz, tw $(,$arg)*);
+                    if track {
+                        max_s = S::max_pd(max_s, f);
+                        min_s = S::min_pd(min_s, f);
+                    }
                     S::storeu_pd(result.get_unchecked_mut(i), f);
                     i += vector_width;
-                    x = S::add_pd(x, S::set1_pd(vector_width as f64));
+                    col += vector_width;
+                    x = next_lanes_pd!(x, start_x, lanes, step_x, col, accumulate_x);
                 }
                 if remainder != 0 {
-                    let f = $f(S::mul_pd(x, freq_x), S::mul_pd(y, freq_y), S::mul_pd(z, freq_z), S::mul_pd(w, freq_w) $(,$arg)*);
+                    let (tx, ty, tz, tw) = transform_4d_f64::<S>(&dim.transform, lane_coords_pd!(chunk, col, x, freq_x), row_coord_pd!(chunk, 1, row_y, start_y, step_y, freq_y, dim.algorithm_version), row_coord_pd!(chunk, 2, row_z, start_z, step_z, freq_z, dim.algorithm_version), row_coord_pd!(chunk, 3, row_w, start_w, step_w, freq_w, dim.algorithm_version));
+                    let f = $f(tx, ty, tz, tw $(,$arg)*);
                     for j in 0..remainder {
                         let n = f[j];
                         *result.get_unchecked_mut(i) = n;
@@ -270,11 +376,8 @@ macro_rules! get_4d_noise_helper_f64 {
                         i += 1;
                     }
                 }
-                y = S::add_pd(y, S::set1_pd(1.0));
             }
-            z = S::add_pd(z, S::set1_pd(1.0));
         }
-        w = S::add_pd(w, S::set1_pd(1.0));
     }
     for i in 0..vector_width {
         if min_s[i] < min {
@@ -291,33 +394,44 @@ macro_rules! get_4d_noise_helper_f64 {
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
-    match noise_type {
+    let (result, min, max) = match noise_type {
         NoiseType::Fbm(s) => get_1d_noise_helper_f64!(
             s,
-            fbm_1d::<S>,
+            fbm_1d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Ridge(s) => get_1d_noise_helper_f64!(
             s,
-            ridge_1d::<S>,
+            ridge_1d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Turbulence(s) => get_1d_noise_helper_f64!(
             s,
-            turbulence_1d::<S>,
+            turbulence_1d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Gradient(s) => {
-            get_1d_noise_helper_f64!(s, simplex_1d::<S>, s.get_dimensions().seed as i64)
+            get_1d_noise_helper_f64!(
+                s,
+                simplex_1d_versioned::<S>,
+                s.get_dimensions().seed as i64,
+                s.get_dimensions().algorithm_version
+            )
         }
         NoiseType::Cellular(_) => {
             panic!("not implemented");
@@ -325,7 +439,17 @@ pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
         NoiseType::Cellular2(_) => {
             panic!("not implemented");
         }
-    }
+        NoiseType::WhiteNoise(s) => {
+            get_1d_noise_helper_f64!(s, white_1d::<S>, s.get_dimensions().seed as i64)
+        }
+        NoiseType::Gabor(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Wavelet(_) => {
+            panic!("not implemented");
+        }
+    };
+    resolve_range_f64(noise_type, result, min, max)
 }
 
 /// Gets a width X height sized block of 2d noise, unscaled.
@@ -336,53 +460,76 @@ pub unsafe fn get_1d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
-    match noise_type {
+    let (result, min, max) = match noise_type {
         NoiseType::Fbm(s) => get_2d_noise_helper_f64!(
             s,
-            fbm_2d::<S>,
+            fbm_2d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Ridge(s) => get_2d_noise_helper_f64!(
             s,
-            ridge_2d::<S>,
+            ridge_2d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Turbulence(s) => get_2d_noise_helper_f64!(
             s,
-            turbulence_2d::<S>,
+            turbulence_2d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Gradient(s) => {
-            get_2d_noise_helper_f64!(s, simplex_2d::<S>, s.get_dimensions().seed as i64)
+            get_2d_noise_helper_f64!(
+                s,
+                simplex_2d_versioned::<S>,
+                s.get_dimensions().seed as i64,
+                s.get_dimensions().algorithm_version
+            )
         }
         NoiseType::Cellular(s) => get_2d_noise_helper_f64!(
             s,
-            cellular_2d::<S>,
+            cellular_2d_versioned::<S>,
             s.distance_function,
             s.return_type,
             S::set1_pd(s.jitter as f64),
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Cellular2(s) => get_2d_noise_helper_f64!(
             s,
-            cellular2_2d::<S>,
+            cellular2_2d_versioned::<S>,
             s.distance_function,
             s.return_type,
             S::set1_pd(s.jitter as f64),
             s.index0,
             s.index1,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            s.get_dimensions().algorithm_version
         ),
-    }
+        NoiseType::WhiteNoise(s) => {
+            get_2d_noise_helper_f64!(s, white_2d::<S>, s.get_dimensions().seed as i64)
+        }
+        NoiseType::Gabor(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Wavelet(_) => {
+            panic!("not implemented");
+        }
+    };
+    resolve_range_f64(noise_type, result, min, max)
 }
 
 /// Gets a width X height X depth sized block of 3d noise, unscaled,
@@ -393,85 +540,119 @@ pub unsafe fn get_2d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_3d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
-    match noise_type {
+    let (result, min, max) = match noise_type {
         NoiseType::Fbm(s) => get_3d_noise_helper_f64!(
             s,
-            fbm_3d::<S>,
+            fbm_3d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Ridge(s) => get_3d_noise_helper_f64!(
             s,
-            ridge_3d::<S>,
+            ridge_3d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Turbulence(s) => get_3d_noise_helper_f64!(
             s,
-            turbulence_3d::<S>,
+            turbulence_3d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Gradient(s) => {
-            get_3d_noise_helper_f64!(s, simplex_3d::<S>, s.get_dimensions().seed as i64)
+            get_3d_noise_helper_f64!(
+                s,
+                simplex_3d_versioned::<S>,
+                s.get_dimensions().seed as i64,
+                s.get_dimensions().algorithm_version
+            )
         }
         NoiseType::Cellular(s) => get_3d_noise_helper_f64!(
             s,
-            cellular_3d::<S>,
+            cellular_3d_versioned::<S>,
             s.distance_function,
             s.return_type,
             S::set1_pd(s.jitter as f64),
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Cellular2(s) => get_3d_noise_helper_f64!(
             s,
-            cellular2_3d::<S>,
+            cellular2_3d_versioned::<S>,
             s.distance_function,
             s.return_type,
             S::set1_pd(s.jitter as f64),
             s.index0,
             s.index1,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            s.get_dimensions().algorithm_version
         ),
-    }
+        NoiseType::WhiteNoise(s) => {
+            get_3d_noise_helper_f64!(s, white_3d::<S>, s.get_dimensions().seed as i64)
+        }
+        NoiseType::Gabor(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Wavelet(_) => {
+            panic!("not implemented");
+        }
+    };
+    resolve_range_f64(noise_type, result, min, max)
 }
 
 #[inline(always)]
 #[allow(dead_code)]
 pub unsafe fn get_4d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f64, f64) {
-    match noise_type {
+    let (result, min, max) = match noise_type {
         NoiseType::Fbm(s) => get_4d_noise_helper_f64!(
             s,
-            fbm_4d::<S>,
+            fbm_4d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Ridge(s) => get_4d_noise_helper_f64!(
             s,
-            ridge_4d::<S>,
+            ridge_4d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Turbulence(s) => get_4d_noise_helper_f64!(
             s,
-            turbulence_4d::<S>,
+            turbulence_4d_ext::<S>,
             S::set1_pd(s.lacunarity as f64),
             S::set1_pd(s.gain as f64),
             s.octaves,
-            s.get_dimensions().seed as i64
+            s.get_dimensions().seed as i64,
+            &s.octave_params(),
+            s.get_dimensions().algorithm_version
         ),
         NoiseType::Gradient(s) => {
-            get_4d_noise_helper_f64!(s, simplex_4d::<S>, s.get_dimensions().seed as i64)
+            get_4d_noise_helper_f64!(
+                s,
+                simplex_4d_versioned::<S>,
+                s.get_dimensions().seed as i64,
+                s.get_dimensions().algorithm_version
+            )
         }
         NoiseType::Cellular(_) => {
             panic!("not implemented");
@@ -479,5 +660,41 @@ pub unsafe fn get_4d_noise_f64<S: Simd>(noise_type: &NoiseType) -> (Vec<f64>, f6
         NoiseType::Cellular2(_) => {
             panic!("not implemented");
         }
+        NoiseType::WhiteNoise(s) => {
+            get_4d_noise_helper_f64!(s, white_4d::<S>, s.get_dimensions().seed as i64)
+        }
+        NoiseType::Gabor(_) => {
+            panic!("not implemented");
+        }
+        NoiseType::Wavelet(_) => {
+            panic!("not implemented");
+        }
+    };
+    resolve_range_f64(noise_type, result, min, max)
+}
+
+// Replaces the measured min and max according to the range mode of the settings
+fn resolve_range_f64(
+    noise_type: &NoiseType,
+    result: Vec<f64>,
+    min: f64,
+    max: f64,
+) -> (Vec<f64>, f64, f64) {
+    match noise_type.get_dimensions().range_mode {
+        RangeMode::Measured => (result, min, max),
+        RangeMode::Analytic => {
+            let (min, max) = noise_type.analytic_range();
+            (result, min as f64, max as f64)
+        }
+        RangeMode::Untracked => (result, f64::NAN, f64::NAN),
+    }
+}
+
+// The coordinate of row `index`, see `noise_helpers_32::row_position`
+fn row_position_f64(start: f64, step: f64, index: usize, version: AlgorithmVersion) -> f64 {
+    if version.accumulates_steps(step as f32) {
+        (0..index).fold(start, |offset, _| offset + step)
+    } else {
+        start + index as f64 * step
     }
 }
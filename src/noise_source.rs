@@ -0,0 +1,457 @@
+//! User defined noise, generated with the same grid iteration, offsets, steps, chunks,
+//! transforms, min and max tracking and runtime SIMD detection as the built in noise.
+//!
+//! Implement `NoiseSource` for every backend, and keep the sample functions
+//! `#[inline(always)]` so they are compiled with each backend's instructions.
+//!
+//! ```rust
+//! use simdeez::Simd;
+//! use simdnoise::*;
+//!
+//! /// Concentric rings around the origin
+//! struct Rings {
+//!     spacing: f32,
+//! }
+//!
+//! impl<S: Simd> NoiseSource<S> for Rings {
+//!     #[inline(always)]
+//!     unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+//!         let r = S::sqrt_ps(S::add_ps(S::mul_ps(x, x), S::mul_ps(y, y)));
+//!         let t = S::mul_ps(r, S::set1_ps(1.0 / self.spacing));
+//!         S::sub_ps(t, S::floor_ps(t))
+//!     }
+//!
+//!     fn range(&self) -> Option<(f32, f32)> {
+//!         Some((0.0, 1.0))
+//!     }
+//! }
+//!
+//! let mut dim = NoiseDimensions::default(2);
+//! dim.width = 128;
+//! dim.height = 128;
+//! let (rings, min, max) = generate_source(&Rings { spacing: 9.5 }, &dim);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::dimensional_being::DimensionalBeing;
+use crate::noise::cell2_32::{cellular2_2d_versioned, cellular2_3d_versioned};
+use crate::noise::cell_32::{cellular_2d_versioned, cellular_3d_versioned};
+use crate::noise::fbm_32::{fbm_1d_ext, fbm_2d_ext, fbm_3d_ext, fbm_4d_ext};
+use crate::noise::gabor_32::{gabor_2d, gabor_3d};
+use crate::noise::ridge_32::{ridge_1d_ext, ridge_2d_ext, ridge_3d_ext, ridge_4d_ext};
+use crate::noise::simplex_32::{
+    simplex_1d_versioned, simplex_2d_versioned, simplex_3d_versioned, simplex_4d_versioned,
+};
+use crate::noise::turbulence_32::{
+    turbulence_1d_ext, turbulence_2d_ext, turbulence_3d_ext, turbulence_4d_ext,
+};
+use crate::noise::wavelet_32::{
+    wavelet_multiband_2d, wavelet_multiband_3d, wavelet_multiband_projected_3d, WaveletTile,
+};
+use crate::noise::white_32::{white_1d, white_2d, white_3d, white_4d};
+use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_type::NoiseType;
+use crate::settings::{
+    Cellular2Settings, CellularSettings, FbmSettings, GaborSettings, GradientSettings,
+    RidgeSettings, TurbulenceSettings, WaveletSettings, WhiteNoiseSettings,
+};
+
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use simdeez::{avx2::Avx2, sse2::Sse2, sse41::Sse41};
+use simdeez::{scalar::Scalar, Simd};
+
+/// A noise function evaluated a vector of points at a time. The coordinates are
+/// the sample positions of the block, scaled by the frequency and transformed when the
+/// source belongs to settings that have them. Dimensions a source doesn't implement
+/// panic when generated.
+pub trait NoiseSource<S: Simd> {
+    unsafe fn sample_1d(&self, _x: S::Vf32) -> S::Vf32 {
+        panic!("not implemented")
+    }
+
+    unsafe fn sample_2d(&self, _x: S::Vf32, _y: S::Vf32) -> S::Vf32 {
+        panic!("not implemented")
+    }
+
+    unsafe fn sample_3d(&self, _x: S::Vf32, _y: S::Vf32, _z: S::Vf32) -> S::Vf32 {
+        panic!("not implemented")
+    }
+
+    unsafe fn sample_4d(&self, _x: S::Vf32, _y: S::Vf32, _z: S::Vf32, _w: S::Vf32) -> S::Vf32 {
+        panic!("not implemented")
+    }
+
+    /// The min and max reported with `RangeMode::Analytic`. Without one, analytic
+    /// blocks report NaN like `RangeMode::Untracked`.
+    fn range(&self) -> Option<(f32, f32)> {
+        None
+    }
+}
+
+/// A `NoiseSource` for every backend, so it can be generated with runtime detection
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub trait SimdNoiseSource:
+    NoiseSource<Scalar> + NoiseSource<Sse2> + NoiseSource<Sse41> + NoiseSource<Avx2>
+{
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl<T> SimdNoiseSource for T where
+    T: NoiseSource<Scalar> + NoiseSource<Sse2> + NoiseSource<Sse41> + NoiseSource<Avx2> + ?Sized
+{
+}
+
+/// A `NoiseSource` for every backend, so it can be generated with runtime detection
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub trait SimdNoiseSource: NoiseSource<Scalar> {}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+impl<T> SimdNoiseSource for T where T: NoiseSource<Scalar> + ?Sized {}
+
+/// Generate the block of `dim` from `source`, with the best instruction set available,
+/// and the min and max value generated. The frequency is 1, so scale coordinates in
+/// the source or with the step of `dim`.
+pub fn generate_source<N: SimdNoiseSource + ?Sized>(
+    source: &N,
+    dim: &NoiseDimensions,
+) -> (Vec<f32>, f32, f32) {
+    get_source_noise!(source, dim)
+}
+
+macro_rules! fractal_source {
+    ($Setting:ident, $Variant:ident, $f1:ident, $f2:ident, $f3:ident, $f4:ident) => {
+        impl<S: Simd> NoiseSource<S> for $Setting {
+            #[inline(always)]
+            unsafe fn sample_1d(&self, x: S::Vf32) -> S::Vf32 {
+                $f1::<S>(
+                    x,
+                    S::set1_ps(self.lacunarity),
+                    S::set1_ps(self.gain),
+                    self.octaves,
+                    self.get_dimensions().seed,
+                    &self.octave_params(),
+                    self.get_dimensions().algorithm_version,
+                )
+            }
+
+            #[inline(always)]
+            unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+                $f2::<S>(
+                    x,
+                    y,
+                    S::set1_ps(self.lacunarity),
+                    S::set1_ps(self.gain),
+                    self.octaves,
+                    self.get_dimensions().seed,
+                    &self.octave_params(),
+                    self.get_dimensions().algorithm_version,
+                )
+            }
+
+            #[inline(always)]
+            unsafe fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+                $f3::<S>(
+                    x,
+                    y,
+                    z,
+                    S::set1_ps(self.lacunarity),
+                    S::set1_ps(self.gain),
+                    self.octaves,
+                    self.get_dimensions().seed,
+                    &self.octave_params(),
+                    self.get_dimensions().algorithm_version,
+                )
+            }
+
+            #[inline(always)]
+            unsafe fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+                $f4::<S>(
+                    x,
+                    y,
+                    z,
+                    w,
+                    S::set1_ps(self.lacunarity),
+                    S::set1_ps(self.gain),
+                    self.octaves,
+                    self.get_dimensions().seed,
+                    &self.octave_params(),
+                    self.get_dimensions().algorithm_version,
+                )
+            }
+
+            fn range(&self) -> Option<(f32, f32)> {
+                Some(NoiseType::$Variant(*self).analytic_range())
+            }
+        }
+    };
+}
+
+fractal_source!(
+    FbmSettings,
+    Fbm,
+    fbm_1d_ext,
+    fbm_2d_ext,
+    fbm_3d_ext,
+    fbm_4d_ext
+);
+fractal_source!(
+    RidgeSettings,
+    Ridge,
+    ridge_1d_ext,
+    ridge_2d_ext,
+    ridge_3d_ext,
+    ridge_4d_ext
+);
+fractal_source!(
+    TurbulenceSettings,
+    Turbulence,
+    turbulence_1d_ext,
+    turbulence_2d_ext,
+    turbulence_3d_ext,
+    turbulence_4d_ext
+);
+
+macro_rules! seeded_source {
+    ($Setting:ident, $Variant:ident, $f1:ident, $f2:ident, $f3:ident, $f4:ident $(, $version:ident)?) => {
+        impl<S: Simd> NoiseSource<S> for $Setting {
+            #[inline(always)]
+            unsafe fn sample_1d(&self, x: S::Vf32) -> S::Vf32 {
+                $f1::<S>(x, self.get_dimensions().seed $(, self.get_dimensions().$version)?)
+            }
+
+            #[inline(always)]
+            unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+                $f2::<S>(x, y, self.get_dimensions().seed $(, self.get_dimensions().$version)?)
+            }
+
+            #[inline(always)]
+            unsafe fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+                $f3::<S>(x, y, z, self.get_dimensions().seed $(, self.get_dimensions().$version)?)
+            }
+
+            #[inline(always)]
+            unsafe fn sample_4d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32, w: S::Vf32) -> S::Vf32 {
+                $f4::<S>(x, y, z, w, self.get_dimensions().seed $(, self.get_dimensions().$version)?)
+            }
+
+            fn range(&self) -> Option<(f32, f32)> {
+                Some(NoiseType::$Variant(*self).analytic_range())
+            }
+        }
+    };
+}
+
+seeded_source!(
+    GradientSettings,
+    Gradient,
+    simplex_1d_versioned,
+    simplex_2d_versioned,
+    simplex_3d_versioned,
+    simplex_4d_versioned,
+    algorithm_version
+);
+seeded_source!(
+    WhiteNoiseSettings,
+    WhiteNoise,
+    white_1d,
+    white_2d,
+    white_3d,
+    white_4d
+);
+
+impl<S: Simd> NoiseSource<S> for CellularSettings {
+    #[inline(always)]
+    unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        cellular_2d_versioned::<S>(
+            x,
+            y,
+            self.distance_function,
+            self.return_type,
+            S::set1_ps(self.jitter),
+            self.get_dimensions().seed,
+            self.get_dimensions().algorithm_version,
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        cellular_3d_versioned::<S>(
+            x,
+            y,
+            z,
+            self.distance_function,
+            self.return_type,
+            S::set1_ps(self.jitter),
+            self.get_dimensions().seed,
+            self.get_dimensions().algorithm_version,
+        )
+    }
+
+    fn range(&self) -> Option<(f32, f32)> {
+        Some(NoiseType::Cellular(*self).analytic_range())
+    }
+}
+
+impl<S: Simd> NoiseSource<S> for Cellular2Settings {
+    #[inline(always)]
+    unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        cellular2_2d_versioned::<S>(
+            x,
+            y,
+            self.distance_function,
+            self.return_type,
+            S::set1_ps(self.jitter),
+            self.index0,
+            self.index1,
+            self.get_dimensions().seed,
+            self.get_dimensions().algorithm_version,
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        cellular2_3d_versioned::<S>(
+            x,
+            y,
+            z,
+            self.distance_function,
+            self.return_type,
+            S::set1_ps(self.jitter),
+            self.index0,
+            self.index1,
+            self.get_dimensions().seed,
+            self.get_dimensions().algorithm_version,
+        )
+    }
+
+    fn range(&self) -> Option<(f32, f32)> {
+        Some(NoiseType::Cellular2(*self).analytic_range())
+    }
+}
+
+impl<S: Simd> NoiseSource<S> for GaborSettings {
+    #[inline(always)]
+    unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        gabor_2d::<S>(
+            x,
+            y,
+            S::set1_ps(self.frequency),
+            self.orientation,
+            S::set1_ps(self.bandwidth),
+            self.impulse_density,
+            self.get_dimensions().seed,
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        gabor_3d::<S>(
+            x,
+            y,
+            z,
+            S::set1_ps(self.frequency),
+            self.orientation,
+            self.elevation,
+            S::set1_ps(self.bandwidth),
+            self.impulse_density,
+            self.get_dimensions().seed,
+        )
+    }
+
+    fn range(&self) -> Option<(f32, f32)> {
+        Some(NoiseType::Gabor(*self).analytic_range())
+    }
+}
+
+/// Wavelet noise along with the coefficient tile it samples
+pub(crate) struct WaveletSource<'a> {
+    settings: WaveletSettings,
+    tile: &'a WaveletTile,
+}
+
+impl<'a> WaveletSource<'a> {
+    /// `tile` must be built by `point::wavelet_tile` from the same settings
+    pub(crate) fn new(settings: &WaveletSettings, tile: &'a WaveletTile) -> WaveletSource<'a> {
+        WaveletSource {
+            settings: *settings,
+            tile,
+        }
+    }
+}
+
+impl<'a, S: Simd> NoiseSource<S> for WaveletSource<'a> {
+    #[inline(always)]
+    unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+        let s = &self.settings;
+        match s.normal {
+            None => wavelet_multiband_2d::<S>(x, y, s.gain, s.octaves, self.tile),
+            Some(normal) => {
+                let z = S::set1_ps(s.get_dimensions().z * s.freq_z);
+                wavelet_multiband_projected_3d::<S>(x, y, z, normal, s.gain, s.octaves, self.tile)
+            }
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn sample_3d(&self, x: S::Vf32, y: S::Vf32, z: S::Vf32) -> S::Vf32 {
+        let s = &self.settings;
+        match s.normal {
+            None => wavelet_multiband_3d::<S>(x, y, z, s.gain, s.octaves, self.tile),
+            Some(normal) => {
+                wavelet_multiband_projected_3d::<S>(x, y, z, normal, s.gain, s.octaves, self.tile)
+            }
+        }
+    }
+
+    fn range(&self) -> Option<(f32, f32)> {
+        Some(NoiseType::Wavelet(self.settings).analytic_range())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intrinsics::scalar;
+    use crate::{NoiseBuilder, RangeMode, Settings};
+
+    struct Ramp(f32);
+
+    impl<S: Simd> NoiseSource<S> for Ramp {
+        #[inline(always)]
+        unsafe fn sample_2d(&self, x: S::Vf32, y: S::Vf32) -> S::Vf32 {
+            S::add_ps(x, S::mul_ps(y, S::set1_ps(self.0)))
+        }
+    }
+
+    #[test]
+    fn user_sources_get_the_block_helpers() {
+        let mut dim = NoiseDimensions::default(2);
+        dim.width = 13;
+        dim.height = 3;
+        dim.x = -4.0;
+        dim.y = 2.0;
+        let (ramp, min, max) = generate_source(&Ramp(100.0), &dim);
+        assert_eq!(ramp.len(), 13 * 3);
+        assert_eq!(ramp[0], -4.0 + 200.0);
+        assert_eq!(ramp[12 + 13 * 2], 8.0 + 400.0);
+        assert_eq!((min, max), (196.0, 408.0));
+
+        dim.range_mode = RangeMode::Analytic;
+        let (_, min, _) = generate_source(&Ramp(100.0), &dim);
+        assert!(min.is_nan());
+
+        let settings = *NoiseBuilder::gradient_2d(19, 5).with_freq(1.0).with_seed(9);
+        let dim = settings.get_dimensions();
+        let (expected, _, _) = settings.generate();
+        assert_eq!(generate_source(&settings, &dim).0, expected);
+
+        let dynamic: &dyn NoiseSource<Scalar> = &settings;
+        let (scalar, _, _) = unsafe { scalar::get_source_noise(dynamic, &dim) };
+        let (expected, _, _) = unsafe { scalar::get_2d_noise(&settings.wrap()) };
+        assert_eq!(scalar, expected);
+    }
+}
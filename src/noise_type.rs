@@ -1,10 +1,15 @@
 use super::{
-    Cellular2Settings, CellularSettings, DimensionalBeing, FbmSettings, GradientSettings,
-    NoiseDimensions, RidgeSettings, TurbulenceSettings,
+    Cell2ReturnType, CellDistanceFunction, CellReturnType, Cellular2Settings, CellularSettings,
+    DimensionalBeing, FbmSettings, GaborSettings, GradientSettings, NoiseDimensions, RidgeSettings,
+    TurbulenceSettings, WaveletSettings, WhiteNoiseSettings,
 };
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+use crate::range_mode::RangeMode;
 
 /// Specifies what type of noise to generate and contains any relevant settings.
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NoiseType {
     Fbm(FbmSettings),
     Ridge(RidgeSettings),
@@ -12,6 +17,9 @@ pub enum NoiseType {
     Gradient(GradientSettings),
     Cellular(CellularSettings),
     Cellular2(Cellular2Settings),
+    WhiteNoise(WhiteNoiseSettings),
+    Gabor(GaborSettings),
+    Wavelet(WaveletSettings),
 }
 
 impl DimensionalBeing for NoiseType {
@@ -23,6 +31,105 @@ impl DimensionalBeing for NoiseType {
             NoiseType::Gradient(s) => s.get_dimensions(),
             NoiseType::Cellular(s) => s.get_dimensions(),
             NoiseType::Cellular2(s) => s.get_dimensions(),
+            NoiseType::WhiteNoise(s) => s.get_dimensions(),
+            NoiseType::Gabor(s) => s.get_dimensions(),
+            NoiseType::Wavelet(s) => s.get_dimensions(),
         }
     }
 }
+
+impl NoiseType {
+    /// The same noise over different dimensions
+    pub(crate) fn with_dimensions(mut self, dim: NoiseDimensions) -> NoiseType {
+        match &mut self {
+            NoiseType::Fbm(s) => s.set_dimensions(dim),
+            NoiseType::Ridge(s) => s.set_dimensions(dim),
+            NoiseType::Turbulence(s) => s.set_dimensions(dim),
+            NoiseType::Gradient(s) => s.set_dimensions(dim),
+            NoiseType::Cellular(s) => s.set_dimensions(dim),
+            NoiseType::Cellular2(s) => s.set_dimensions(dim),
+            NoiseType::WhiteNoise(s) => s.set_dimensions(dim),
+            NoiseType::Gabor(s) => s.set_dimensions(dim),
+            NoiseType::Wavelet(s) => s.set_dimensions(dim),
+        }
+        self
+    }
+
+    /// The theoretical range of the noise for its type, dimension and settings. Gabor and
+    /// wavelet noise are unbounded in theory, their range covers 3 standard deviations.
+    /// Unbounded ends, such as cellular2 `Distance2Div` with `index0 > index1`, are infinite.
+    pub fn analytic_range(&self) -> (f32, f32) {
+        let dim = self.get_dimensions().dim;
+        match self {
+            NoiseType::Fbm(s) => {
+                // The 1d kernels don't weight their octaves
+                let amp_sum = octave_amplitudes(dim, s.gain, s.octaves)
+                    .map(f32::abs)
+                    .sum::<f32>();
+                (-amp_sum, amp_sum)
+            }
+            NoiseType::Ridge(s) => {
+                // Every octave is 1 - |n * amp|, 1 - |n| for the first
+                octave_amplitudes(dim, s.gain, s.octaves).fold((0.0, 0.0), |(lo, hi), amp| {
+                    let amp = if dim == 4 { amp.abs() } else { amp };
+                    (lo + (1.0 - amp).min(1.0), hi + (1.0 - amp).max(1.0))
+                })
+            }
+            NoiseType::Turbulence(s) => {
+                let amp_sum = octave_amplitudes(dim, s.gain, s.octaves)
+                    .map(f32::abs)
+                    .sum::<f32>();
+                (0.0, amp_sum)
+            }
+            NoiseType::Gradient(_) | NoiseType::WhiteNoise(_) => (-1.0, 1.0),
+            NoiseType::Cellular(s) => match s.return_type {
+                CellReturnType::CellValue => (-1.0, 1.0),
+                // The closest point is the one for the nearest lattice corner at most
+                CellReturnType::Distance => (
+                    0.0,
+                    cell_distance_bound(s.distance_function, dim, 0.5 + s.jitter.abs()),
+                ),
+            },
+            NoiseType::Cellular2(s) => {
+                // Any of the nearest few is within the 3^dim searched cells
+                let d = cell_distance_bound(s.distance_function, dim, 1.5 + s.jitter.abs());
+                match s.return_type {
+                    Cell2ReturnType::Distance2 => (0.0, d),
+                    Cell2ReturnType::Distance2Add => (0.0, 2.0 * d),
+                    Cell2ReturnType::Distance2Sub => (-d, d),
+                    Cell2ReturnType::Distance2Mul => (0.0, d * d),
+                    // The distances are sorted, so the ratio is at most 1 unless the indices
+                    // are swapped, and then it is at least 1 with no upper bound
+                    Cell2ReturnType::Distance2Div if s.index0 > s.index1 => (1.0, f32::INFINITY),
+                    Cell2ReturnType::Distance2Div => (0.0, 1.0),
+                }
+            }
+            NoiseType::Gabor(_) | NoiseType::Wavelet(_) => (-1.0, 1.0),
+        }
+    }
+
+    // The min and max `generate_scaled` maps to its output range
+    pub(crate) fn scaling_range(&self, min: f32, max: f32) -> (f32, f32) {
+        match self.get_dimensions().range_mode {
+            RangeMode::Measured => (min, max),
+            RangeMode::Analytic | RangeMode::Untracked => self.analytic_range(),
+        }
+    }
+}
+
+// The weight of each octave of the fractal kernels
+fn octave_amplitudes(dim: usize, gain: f32, octaves: u8) -> impl Iterator<Item = f32> {
+    let gain = if dim == 1 { 1.0 } else { gain };
+    (0..octaves.max(1) as i32).map(move |i| gain.powi(i))
+}
+
+// Largest cell distance for a point at most `offset` away from the sample along each axis
+fn cell_distance_bound(distance_function: CellDistanceFunction, dim: usize, offset: f32) -> f32 {
+    let euclidean = dim as f32 * offset * offset;
+    let manhattan = dim as f32 * offset;
+    match distance_function {
+        CellDistanceFunction::Euclidean => euclidean,
+        CellDistanceFunction::Manhattan => manhattan,
+        CellDistanceFunction::Natural => euclidean + manhattan,
+    }
+}
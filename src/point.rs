@@ -0,0 +1,249 @@
+//! Safe functions for single points of noise, and for small arrays of points
+//!
+//! Each function picks the best available instruction set at runtime, like the block
+//! functions. Coordinates are the same as block coordinates: the frequency and transform
+//! of the settings are applied, so `fbm_2d(x, y, &settings)` matches the block generated
+//! from `settings` at `(x, y)`. The `simplex_` functions take no settings and evaluate raw
+//! simplex noise, like `intrinsics::sse2::simplex_2d` and friends.
+//!
+//! The `_lanes` variants evaluate `LANES` points at once, one array of coordinates per
+//! axis, which is much faster than calling the single point functions in a loop.
+//!
+//! ```rust
+//! use simdnoise::{point, NoiseBuilder, Settings};
+//!
+//! let v = point::simplex_2d(5.0, 10.0, 42);
+//!
+//! let settings = NoiseBuilder::fbm_3d(1, 1, 1).with_octaves(5).with_seed(42).clone();
+//! let v = point::fbm_3d(1.0, 2.0, 3.0, &settings);
+//! let lanes = point::fbm_3d_lanes([1.0; 8], [2.0; 8], [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], &settings);
+//! ```
+
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+use crate::noise::wavelet_32::WaveletTile;
+use crate::settings::{
+    Cellular2Settings, CellularSettings, FbmSettings, GaborSettings, GradientSettings,
+    RidgeSettings, Settings, TurbulenceSettings, WaveletSettings, WhiteNoiseSettings,
+};
+use crate::{NoiseDimensions, NoiseType};
+
+/// Number of points evaluated by the `_lanes` functions
+pub const LANES: usize = 8;
+
+// Evaluates the first `len` points, padding the missing axes with zero
+fn sample(
+    noise_type: &NoiseType,
+    tile: Option<&WaveletTile>,
+    axes: &[[f32; LANES]],
+    len: usize,
+) -> [f32; LANES] {
+    let mut coords = [[0.0; LANES]; 4];
+    coords[..axes.len()].copy_from_slice(axes);
+    sample_lanes!(noise_type, tile, axes.len(), &coords, len)
+}
+
+fn simplex(dim: usize, seed: i32) -> NoiseType {
+    GradientSettings::default(NoiseDimensions::default(dim))
+        .with_freq(1.0)
+        .with_seed(seed)
+        .wrap()
+}
+
+/// Builds the tile the wavelet functions of dimension `dim` need for `settings`, the same
+/// tile a block generated from `settings` uses. Building a tile is expensive, build it
+/// once and share it between calls.
+pub fn wavelet_tile(settings: &WaveletSettings, dim: usize) -> WaveletTile {
+    WaveletTile::new(
+        settings.tile_size,
+        wavelet_tile_dim(settings, dim),
+        settings.get_dimensions().seed,
+    )
+}
+
+macro_rules! point_fns {
+    ($($doc:literal, $name:ident, $lanes:ident, $setting:ty, ($($axis:ident),+);)+) => {$(
+        #[doc = $doc]
+        pub fn $name($($axis: f32,)+ settings: &$setting) -> f32 {
+            sample(&settings.wrap(), None, &[$([$axis; LANES]),+], 1)[0]
+        }
+
+        #[doc = $doc]
+        #[doc = ""]
+        #[doc = "At `LANES` points at once, one array of coordinates per axis."]
+        pub fn $lanes($($axis: [f32; LANES],)+ settings: &$setting) -> [f32; LANES] {
+            sample(&settings.wrap(), None, &[$($axis),+], LANES)
+        }
+    )+};
+}
+
+macro_rules! simplex_fns {
+    ($($doc:literal, $name:ident, $lanes:ident, ($($axis:ident),+);)+) => {$(
+        #[doc = $doc]
+        pub fn $name($($axis: f32,)+ seed: i32) -> f32 {
+            let axes = [$([$axis; LANES]),+];
+            sample(&simplex(axes.len(), seed), None, &axes, 1)[0]
+        }
+
+        #[doc = $doc]
+        #[doc = ""]
+        #[doc = "At `LANES` points at once, one array of coordinates per axis."]
+        pub fn $lanes($($axis: [f32; LANES],)+ seed: i32) -> [f32; LANES] {
+            let axes = [$($axis),+];
+            sample(&simplex(axes.len(), seed), None, &axes, LANES)
+        }
+    )+};
+}
+
+macro_rules! wavelet_fns {
+    ($($doc:literal, $name:ident, $lanes:ident, ($($axis:ident),+);)+) => {$(
+        #[doc = $doc]
+        #[doc = ""]
+        #[doc = "`tile` must be built by `wavelet_tile` from the same settings."]
+        pub fn $name($($axis: f32,)+ settings: &WaveletSettings, tile: &WaveletTile) -> f32 {
+            let axes = [$([$axis; LANES]),+];
+            check_tile(settings, axes.len(), tile);
+            sample(&settings.wrap(), Some(tile), &axes, 1)[0]
+        }
+
+        #[doc = $doc]
+        #[doc = ""]
+        #[doc = "At `LANES` points at once, one array of coordinates per axis. `tile` must be"]
+        #[doc = "built by `wavelet_tile` from the same settings."]
+        pub fn $lanes(
+            $($axis: [f32; LANES],)+
+            settings: &WaveletSettings,
+            tile: &WaveletTile,
+        ) -> [f32; LANES] {
+            let axes = [$($axis),+];
+            check_tile(settings, axes.len(), tile);
+            sample(&settings.wrap(), Some(tile), &axes, LANES)
+        }
+    )+};
+}
+
+pub(crate) fn check_tile(settings: &WaveletSettings, dim: usize, tile: &WaveletTile) {
+    let expected = wavelet_tile_dim(settings, dim);
+    let seed = settings.get_dimensions().seed;
+    if tile.dimensions() != expected || tile.size() != settings.tile_size || tile.seed() != seed {
+        panic!(
+            "wavelet tile must be {}d with size {} and seed {}, see `wavelet_tile`",
+            expected, settings.tile_size, seed
+        );
+    }
+}
+
+fn wavelet_tile_dim(settings: &WaveletSettings, dim: usize) -> usize {
+    if dim == 2 && settings.normal.is_none() {
+        2
+    } else {
+        3
+    }
+}
+
+simplex_fns! {
+    "1d simplex noise", simplex_1d, simplex_1d_lanes, (x);
+    "2d simplex noise", simplex_2d, simplex_2d_lanes, (x, y);
+    "3d simplex noise", simplex_3d, simplex_3d_lanes, (x, y, z);
+    "4d simplex noise", simplex_4d, simplex_4d_lanes, (x, y, z, w);
+}
+
+point_fns! {
+    "1d gradient noise", gradient_1d, gradient_1d_lanes, GradientSettings, (x);
+    "2d gradient noise", gradient_2d, gradient_2d_lanes, GradientSettings, (x, y);
+    "3d gradient noise", gradient_3d, gradient_3d_lanes, GradientSettings, (x, y, z);
+    "4d gradient noise", gradient_4d, gradient_4d_lanes, GradientSettings, (x, y, z, w);
+    "1d fractal brownian motion", fbm_1d, fbm_1d_lanes, FbmSettings, (x);
+    "2d fractal brownian motion", fbm_2d, fbm_2d_lanes, FbmSettings, (x, y);
+    "3d fractal brownian motion", fbm_3d, fbm_3d_lanes, FbmSettings, (x, y, z);
+    "4d fractal brownian motion", fbm_4d, fbm_4d_lanes, FbmSettings, (x, y, z, w);
+    "1d ridge noise", ridge_1d, ridge_1d_lanes, RidgeSettings, (x);
+    "2d ridge noise", ridge_2d, ridge_2d_lanes, RidgeSettings, (x, y);
+    "3d ridge noise", ridge_3d, ridge_3d_lanes, RidgeSettings, (x, y, z);
+    "4d ridge noise", ridge_4d, ridge_4d_lanes, RidgeSettings, (x, y, z, w);
+    "1d turbulence", turbulence_1d, turbulence_1d_lanes, TurbulenceSettings, (x);
+    "2d turbulence", turbulence_2d, turbulence_2d_lanes, TurbulenceSettings, (x, y);
+    "3d turbulence", turbulence_3d, turbulence_3d_lanes, TurbulenceSettings, (x, y, z);
+    "4d turbulence", turbulence_4d, turbulence_4d_lanes, TurbulenceSettings, (x, y, z, w);
+    "2d cellular noise", cellular_2d, cellular_2d_lanes, CellularSettings, (x, y);
+    "3d cellular noise", cellular_3d, cellular_3d_lanes, CellularSettings, (x, y, z);
+    "2d cellular2 noise", cellular2_2d, cellular2_2d_lanes, Cellular2Settings, (x, y);
+    "3d cellular2 noise", cellular2_3d, cellular2_3d_lanes, Cellular2Settings, (x, y, z);
+    "2d gabor noise", gabor_2d, gabor_2d_lanes, GaborSettings, (x, y);
+    "3d gabor noise", gabor_3d, gabor_3d_lanes, GaborSettings, (x, y, z);
+    "1d white noise", white_1d, white_1d_lanes, WhiteNoiseSettings, (x);
+    "2d white noise", white_2d, white_2d_lanes, WhiteNoiseSettings, (x, y);
+    "3d white noise", white_3d, white_3d_lanes, WhiteNoiseSettings, (x, y, z);
+    "4d white noise", white_4d, white_4d_lanes, WhiteNoiseSettings, (x, y, z, w);
+}
+
+wavelet_fns! {
+    "2d wavelet noise", wavelet_2d, wavelet_2d_lanes, (x, y);
+    "3d wavelet noise", wavelet_3d, wavelet_3d_lanes, (x, y, z);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoiseBuilder;
+
+    #[test]
+    fn points_match_blocks() {
+        let fbm = NoiseBuilder::fbm_2d_offset(3.0, 16, -2.0, 8)
+            .with_octaves(4)
+            .with_seed(7)
+            .clone();
+        let (block, _, _) = fbm.generate();
+        for y in 0..8 {
+            for x in 0..16 {
+                let v = fbm_2d(3.0 + x as f32, -2.0 + y as f32, &fbm);
+                assert_eq!(block[y * 16 + x], v);
+            }
+        }
+
+        let cell = NoiseBuilder::cellular_3d(8, 4, 2).with_seed(7).clone();
+        let (block, _, _) = cell.generate();
+        for z in 0..2 {
+            for y in 0..4 {
+                for x in 0..8 {
+                    let v = cellular_3d(x as f32, y as f32, z as f32, &cell);
+                    assert_eq!(block[(z * 4 + y) * 8 + x], v);
+                }
+            }
+        }
+
+        let wavelet = NoiseBuilder::wavelet_2d(8, 2).with_seed(7).clone();
+        let tile = wavelet_tile(&wavelet, 2);
+        let (block, _, _) = wavelet.generate();
+        for x in 0..8 {
+            assert_eq!(block[8 + x], wavelet_2d(x as f32, 1.0, &wavelet, &tile));
+        }
+        assert_eq!(wavelet.generate_with_tile(&tile).0, block);
+    }
+
+    #[test]
+    #[should_panic(expected = "seed 8")]
+    fn tiles_must_match_the_seed() {
+        let mut wavelet = NoiseBuilder::wavelet_2d(8, 2).with_seed(7).clone();
+        let tile = wavelet_tile(&wavelet, 2);
+        wavelet.with_seed(8);
+        wavelet_2d(0.5, 1.0, &wavelet, &tile);
+    }
+
+    #[test]
+    fn lanes_match_points() {
+        let xs = [0.5, 1.5, -2.0, 3.25, 100.0, -7.5, 0.0, 12.0];
+        let ys = [1.0, -1.0, 2.5, 0.0, 3.0, 8.0, -4.0, 6.5];
+        let lanes = simplex_2d_lanes(xs, ys, 42);
+        let turbulence = NoiseBuilder::turbulence_2d(1, 1).with_seed(3).clone();
+        let turbulence_lanes = turbulence_2d_lanes(xs, ys, &turbulence);
+        for i in 0..LANES {
+            assert_eq!(lanes[i], simplex_2d(xs[i], ys[i], 42));
+            assert_eq!(
+                turbulence_lanes[i],
+                turbulence_2d(xs[i], ys[i], &turbulence)
+            );
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Saving and loading noise settings, enabled with the `serde` feature.
+//!
+//! Every settings type, `NoiseType` and `NoiseDimensions` implement `Serialize` and
+//! `Deserialize`. Store them inside a `NoisePreset`, which records the schema version
+//! and checks it and the settings when loaded, so a preset reproduces the same noise in
+//! any release that can read it.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let settings = *NoiseBuilder::fbm_2d(64, 64).with_octaves(5).with_seed(7);
+//! let saved = serde_json::to_string(&NoisePreset::new(settings.wrap())).unwrap();
+//!
+//! let loaded: NoisePreset = serde_json::from_str(&saved).unwrap();
+//! assert_eq!(loaded.generate().0, settings.generate().0);
+//! ```
+
+use crate::noise_error::NoiseError;
+use crate::noise_type::NoiseType;
+use crate::settings::Settings;
+
+use serde::{Deserialize, Serialize};
+
+use core::convert::TryFrom;
+
+/// Version of the preset schema written by this release. It changes whenever a
+/// setting is added, removed or changes meaning. Presets from earlier versions load
+/// with the defaults of settings they don't have.
+///
+/// 1. The first schema
+/// 2. Adds `octave_seeding` to the fractal settings
+/// 3. Adds `algorithm_version` to the dimensions
+/// 4. Adds `wide_seed` to the dimensions
+pub const PRESET_VERSION: u32 = 4;
+
+/// Noise settings together with the version of the schema they were saved with
+#[derive(Copy, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawPreset")]
+pub struct NoisePreset {
+    version: u32,
+    pub noise: NoiseType,
+}
+
+// The unchecked form a preset is read as
+#[derive(Deserialize)]
+struct RawPreset {
+    version: u32,
+    noise: NoiseType,
+}
+
+impl TryFrom<RawPreset> for NoisePreset {
+    type Error = NoiseError;
+
+    fn try_from(raw: RawPreset) -> Result<NoisePreset, NoiseError> {
+        if raw.version < 1 || raw.version > PRESET_VERSION {
+            return Err(NoiseError::UnsupportedPresetVersion(raw.version));
+        }
+        let preset = NoisePreset {
+            version: raw.version,
+            noise: raw.noise,
+        };
+        preset.try_validate()?;
+        Ok(preset)
+    }
+}
+
+impl NoisePreset {
+    /// Preset of the noise at the current schema version
+    pub fn new(noise: NoiseType) -> NoisePreset {
+        NoisePreset {
+            version: PRESET_VERSION,
+            noise,
+        }
+    }
+
+    /// The schema version the preset was saved with
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Checks the settings of the noise, see `Settings::try_validate`
+    pub fn try_validate(&self) -> Result<(), NoiseError> {
+        match self.noise {
+            NoiseType::Fbm(s) => s.try_validate(),
+            NoiseType::Ridge(s) => s.try_validate(),
+            NoiseType::Turbulence(s) => s.try_validate(),
+            NoiseType::Gradient(s) => s.try_validate(),
+            NoiseType::Cellular(s) => s.try_validate(),
+            NoiseType::Cellular2(s) => s.try_validate(),
+            NoiseType::WhiteNoise(s) => s.try_validate(),
+            NoiseType::Gabor(s) => s.try_validate(),
+            NoiseType::Wavelet(s) => s.try_validate(),
+        }
+    }
+
+    /// Generate the chunk of noise described by the preset, along with the min and max
+    /// value generated
+    pub fn generate(&self) -> (Vec<f32>, f32, f32) {
+        match self.noise {
+            NoiseType::Fbm(s) => s.generate(),
+            NoiseType::Ridge(s) => s.generate(),
+            NoiseType::Turbulence(s) => s.generate(),
+            NoiseType::Gradient(s) => s.generate(),
+            NoiseType::Cellular(s) => s.generate(),
+            NoiseType::Cellular2(s) => s.generate(),
+            NoiseType::WhiteNoise(s) => s.generate(),
+            NoiseType::Gabor(s) => s.generate(),
+            NoiseType::Wavelet(s) => s.generate(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn presets_check_version_and_settings() {
+        let preset = NoisePreset::new(NoiseBuilder::cellular_2d(8, 8).wrap());
+        let json = serde_json::to_string(&preset).unwrap();
+        assert!(json.starts_with("{\"version\":4,"));
+        let loaded: NoisePreset = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.version(), PRESET_VERSION);
+
+        let future = json.replacen("\"version\":4", "\"version\":5", 1);
+        let error = serde_json::from_str::<NoisePreset>(&future).err().unwrap();
+        assert!(error.to_string().contains("preset version 5"));
+
+        let fbm = NoisePreset::new(NoiseBuilder::fbm_2d(8, 8).wrap());
+        let v1 = serde_json::to_string(&fbm)
+            .unwrap()
+            .replacen("\"version\":4", "\"version\":1", 1)
+            .replacen(",\"octave_seeding\":\"Shared\"", "", 1)
+            .replacen(",\"wide_seed\":null", "", 1)
+            .replacen(",\"algorithm_version\":\"V3_2\"", "", 1);
+        assert!(
+            !v1.contains("octave_seeding")
+                && !v1.contains("wide_seed")
+                && !v1.contains("algorithm_version")
+        );
+        let loaded: NoisePreset = serde_json::from_str(&v1).unwrap();
+        assert_eq!(loaded.version(), 1);
+        assert_eq!(
+            loaded.noise.get_dimensions().algorithm_version,
+            AlgorithmVersion::V3_1
+        );
+        assert_eq!(loaded.generate().0, fbm.generate().0);
+
+        let jittered = json.replacen("\"jitter\":0.25", "\"jitter\":4.0", 1);
+        assert_ne!(jittered, json);
+        assert!(serde_json::from_str::<NoisePreset>(&jittered).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+/// How the min and max returned with a block of noise, and used by `generate_scaled`,
+/// are found
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RangeMode {
+    /// The smallest and largest values in the block. Separately generated chunks are
+    /// scaled differently, and show seams where they meet.
+    Measured,
+    /// The theoretical range of the noise type and its settings. It is the same for
+    /// every chunk, and the block is not scanned for its min and max.
+    Analytic,
+    /// The block is not scanned for its min and max, and `generate` returns NaN for
+    /// both. `generate_scaled` falls back to the analytic range.
+    Untracked,
+}
+
+impl Default for RangeMode {
+    fn default() -> RangeMode {
+        RangeMode::Measured
+    }
+}
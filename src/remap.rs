@@ -0,0 +1,268 @@
+//! Remapping operators for post-processing noise values: curves through control
+//! points, terraces, and power curves. Apply them in place to a generated block with
+//! `Remap::apply`, or inside a composition graph with `Node::remap`.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let mut noise = NoiseBuilder::fbm_2d(64, 64).generate_scaled(0.0, 1.0);
+//! Remap::terrace(vec![0.0, 0.3, 0.5, 0.9, 1.0], false).apply(&mut noise);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+use crate::noise::math_32::pow_ps;
+
+use simdeez::Simd;
+
+/// A remapping operator, built with `linear`, `spline`, `terrace` or `power`, which check
+/// their arguments
+#[derive(Clone, Debug, PartialEq)]
+pub struct Remap(Curve);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Curve {
+    /// Straight lines between `[input, output]` control points, sorted by input.
+    /// Values outside the first and last inputs take the end outputs.
+    Linear(Vec<[f32; 2]>),
+    /// libnoise's cubic interpolation (`CubicInterp`) through `[input, output]` control
+    /// points, sorted by input. Each segment uses the outputs of the two points on either
+    /// side. Values outside the first and last inputs take the end outputs.
+    Spline(Vec<[f32; 2]>),
+    /// Flat plateaus at each of the sorted `points`, rising quadratically to the next
+    /// one. Inverted terraces rise steeply out of a plateau instead of into one.
+    Terrace { points: Vec<f32>, invert: bool },
+    /// |v|^exponent, keeping the sign of v. Below 1 lifts values in -1..1 towards the
+    /// ends, above 1 pulls them towards 0.
+    Power(f32),
+}
+
+impl Remap {
+    /// Piecewise linear curve, `points` are sorted by input. Panics with fewer than 2 points
+    /// or an input that is not finite.
+    pub fn linear(mut points: Vec<[f32; 2]>) -> Remap {
+        if points.len() < 2 {
+            panic!("a linear curve needs at least 2 control points");
+        }
+        if points.iter().any(|p| !p[0].is_finite()) {
+            panic!("linear curve inputs must be finite");
+        }
+        points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+        Remap(Curve::Linear(points))
+    }
+
+    /// Cubic curve through the control points, with libnoise's cubic interpolation.
+    /// `points` are sorted by input. Panics with fewer than 2 points or an input that is
+    /// not finite.
+    pub fn spline(mut points: Vec<[f32; 2]>) -> Remap {
+        if points.len() < 2 {
+            panic!("a spline curve needs at least 2 control points");
+        }
+        if points.iter().any(|p| !p[0].is_finite()) {
+            panic!("spline curve inputs must be finite");
+        }
+        points.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap());
+        Remap(Curve::Spline(points))
+    }
+
+    /// Terraces at `points`, which are sorted. Panics with fewer than 2 points or a point
+    /// that is not finite.
+    pub fn terrace(mut points: Vec<f32>, invert: bool) -> Remap {
+        if points.len() < 2 {
+            panic!("terraces need at least 2 points");
+        }
+        if points.iter().any(|p| !p.is_finite()) {
+            panic!("terrace points must be finite");
+        }
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Remap(Curve::Terrace { points, invert })
+    }
+
+    /// Power curve. Panics unless `exponent` is positive.
+    pub fn power(exponent: f32) -> Remap {
+        if !(exponent > 0.0) {
+            panic!("the power exponent must be positive");
+        }
+        Remap(Curve::Power(exponent))
+    }
+
+    /// Remap a buffer of noise values in place
+    pub fn apply(&self, data: &mut [f32]) {
+        remap_noise!(self, data)
+    }
+}
+
+impl Remap {
+    /// Bounds of the output for inputs within `min..=max`
+    pub(crate) fn output_range(&self, min: f32, max: f32) -> (f32, f32) {
+        match &self.0 {
+            Curve::Linear(points) => points.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+                (lo.min(p[1]), hi.max(p[1]))
+            }),
+            Curve::Spline(points) => {
+                let last = points.len() - 1;
+                let mut range = (points[0][1], points[0][1]);
+                for i in 0..last {
+                    let n0 = points[i.saturating_sub(1)][1];
+                    let n1 = points[i][1];
+                    let n2 = points[i + 1][1];
+                    let n3 = points[(i + 2).min(last)][1];
+                    let p = (n3 - n2) - (n0 - n1);
+                    let q = (n0 - n1) - p;
+                    let r = n2 - n0;
+                    let cubic = |t: f32| ((p * t + q) * t + r) * t + n1;
+                    // The segment's extremes are at its ends or where 3pt² + 2qt + r = 0
+                    let mut candidates = vec![0.0, 1.0];
+                    if p.abs() > f32::EPSILON {
+                        let disc = q * q - 3.0 * p * r;
+                        if disc >= 0.0 {
+                            candidates.push((-q + disc.sqrt()) / (3.0 * p));
+                            candidates.push((-q - disc.sqrt()) / (3.0 * p));
+                        }
+                    } else if q.abs() > f32::EPSILON {
+                        candidates.push(-r / (2.0 * q));
+                    }
+                    for t in candidates {
+                        if t >= 0.0 && t <= 1.0 {
+                            let v = cubic(t);
+                            range = (range.0.min(v), range.1.max(v));
+                        }
+                    }
+                }
+                range
+            }
+            Curve::Terrace { points, .. } => (points[0], points[points.len() - 1]),
+            Curve::Power(exponent) => {
+                let power = |v: f32| v.abs().powf(*exponent).copysign(v);
+                (power(min), power(max))
+            }
+        }
+    }
+}
+
+/// Remaps one vector of values.
+///
+/// Every segment is evaluated for every lane, and lanes keep the last segment whose
+/// start they have passed, so the cost is linear in the number of control points.
+#[inline(always)]
+pub(crate) unsafe fn remap_ps<S: Simd>(remap: &Remap, v: S::Vf32) -> S::Vf32 {
+    match &remap.0 {
+        Curve::Linear(points) => {
+            let last = points.len() - 1;
+            let mut result = S::set1_ps(points[0][1]);
+            for i in 0..last {
+                let [x0, y0] = points[i];
+                let [x1, y1] = points[i + 1];
+                let slope = if x1 > x0 { (y1 - y0) / (x1 - x0) } else { 0.0 };
+                let segment = S::add_ps(
+                    S::set1_ps(y0),
+                    S::mul_ps(S::sub_ps(v, S::set1_ps(x0)), S::set1_ps(slope)),
+                );
+                result = S::blendv_ps(result, segment, S::cmpge_ps(v, S::set1_ps(x0)));
+            }
+            let past_end = S::cmpge_ps(v, S::set1_ps(points[last][0]));
+            S::blendv_ps(result, S::set1_ps(points[last][1]), past_end)
+        }
+        Curve::Spline(points) => {
+            let last = points.len() - 1;
+            let mut result = S::set1_ps(points[0][1]);
+            for i in 0..last {
+                let x0 = points[i][0];
+                let x1 = points[i + 1][0];
+                let inv_width = if x1 > x0 { 1.0 / (x1 - x0) } else { 0.0 };
+                let n0 = points[i.saturating_sub(1)][1];
+                let n1 = points[i][1];
+                let n2 = points[i + 1][1];
+                let n3 = points[(i + 2).min(last)][1];
+                let p = (n3 - n2) - (n0 - n1);
+                let q = (n0 - n1) - p;
+                let r = n2 - n0;
+
+                let t = S::mul_ps(S::sub_ps(v, S::set1_ps(x0)), S::set1_ps(inv_width));
+                let mut segment = S::set1_ps(p);
+                segment = S::add_ps(S::mul_ps(segment, t), S::set1_ps(q));
+                segment = S::add_ps(S::mul_ps(segment, t), S::set1_ps(r));
+                segment = S::add_ps(S::mul_ps(segment, t), S::set1_ps(n1));
+                result = S::blendv_ps(result, segment, S::cmpge_ps(v, S::set1_ps(x0)));
+            }
+            let past_end = S::cmpge_ps(v, S::set1_ps(points[last][0]));
+            S::blendv_ps(result, S::set1_ps(points[last][1]), past_end)
+        }
+        Curve::Terrace { points, invert } => {
+            let last = points.len() - 1;
+            let mut result = S::set1_ps(points[0]);
+            for i in 0..last {
+                let (v0, v1) = (points[i], points[i + 1]);
+                let inv_width = if v1 > v0 { 1.0 / (v1 - v0) } else { 0.0 };
+                let mut t = S::mul_ps(S::sub_ps(v, S::set1_ps(v0)), S::set1_ps(inv_width));
+                let segment = if *invert {
+                    t = S::sub_ps(S::set1_ps(1.0), t);
+                    S::add_ps(
+                        S::set1_ps(v1),
+                        S::mul_ps(S::mul_ps(t, t), S::set1_ps(v0 - v1)),
+                    )
+                } else {
+                    S::add_ps(
+                        S::set1_ps(v0),
+                        S::mul_ps(S::mul_ps(t, t), S::set1_ps(v1 - v0)),
+                    )
+                };
+                result = S::blendv_ps(result, segment, S::cmpge_ps(v, S::set1_ps(v0)));
+            }
+            let past_end = S::cmpge_ps(v, S::set1_ps(points[last]));
+            S::blendv_ps(result, S::set1_ps(points[last]), past_end)
+        }
+        Curve::Power(exponent) => {
+            let sign = S::and_ps(v, S::set1_ps(-0.0));
+            let magnitude = pow_ps::<S>(S::abs_ps(v), S::set1_ps(*exponent));
+            S::xor_ps(magnitude, sign)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simdeez::scalar::{F32x1, Scalar};
+
+    fn remap_scalar(remap: &Remap, v: f32) -> f32 {
+        unsafe { remap_ps::<Scalar>(remap, F32x1(v)).0 }
+    }
+
+    #[test]
+    fn curves_pass_through_control_points() {
+        let points = vec![[-1.0, -1.0], [-0.2, 0.4], [0.3, 0.1], [1.0, 1.0]];
+        let linear = Remap::linear(points.clone());
+        let spline = Remap::spline(points.clone());
+        for p in &points {
+            assert!((remap_scalar(&linear, p[0]) - p[1]).abs() < 1e-6);
+            assert!((remap_scalar(&spline, p[0]) - p[1]).abs() < 1e-6);
+        }
+        assert!((remap_scalar(&linear, -0.6) + 0.3).abs() < 1e-6);
+        assert_eq!(remap_scalar(&linear, -3.0), -1.0);
+        assert_eq!(remap_scalar(&spline, 3.0), 1.0);
+    }
+
+    #[test]
+    fn terrace_steps() {
+        let terrace = Remap::terrace(vec![0.0, 0.5, 1.0], false);
+        assert_eq!(remap_scalar(&terrace, 0.25), 0.125);
+        assert_eq!(remap_scalar(&terrace, 0.5), 0.5);
+        let inverted = Remap::terrace(vec![0.0, 0.5, 1.0], true);
+        assert_eq!(remap_scalar(&inverted, 0.25), 0.375);
+
+        let power = Remap::power(2.0);
+        assert!((remap_scalar(&power, -0.5) + 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    #[should_panic(expected = "spline curve inputs must be finite")]
+    fn nan_inputs_are_rejected() {
+        Remap::spline(vec![[0.0, 0.0], [f32::NAN, 1.0], [1.0, 1.0]]);
+    }
+}
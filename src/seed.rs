@@ -0,0 +1,175 @@
+//! Seeds from 64 bit integers, bytes, strings, tuples and anything else that is `Hash`.
+//!
+//! The noise kernels hash with 32 bit seeds, so `seed_from_u64` mixes wider seeds down to
+//! one. `Settings::with_seed_u64` keeps the full seed as well, and from
+//! `AlgorithmVersion::V3_2` on it is used in full, see `NoiseDimensions::seed_u64`.
+//! Earlier versions only have 2^32 different noises: two random 64 bit seeds give the
+//! same noise once in 2^32 pairs, so among about 77,000 seeds two are likely to share
+//! noise. With those, keep seeds that must never collide, such as world ids, within
+//! `i32` and use `with_seed`.
+//!
+//! The mixing is fixed and does not depend on the platform, its endianness or pointer
+//! width. `seed_from_u64` and `seed_from_bytes` give the same seed everywhere and in
+//! every release. `seed_from_hash` feeds `SeedHasher` whatever the `Hash` impl of the
+//! value writes, and std does not promise its impls write the same bytes in every Rust
+//! release, so hash a defined encoding with `seed_from_bytes` for seeds that are saved.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let world = NoiseBuilder::fbm_2d(64, 64)
+//!     .with_seed_u64(0x9e37_79b9_7f4a_7c15)
+//!     .generate();
+//! let region = NoiseBuilder::fbm_2d(64, 64)
+//!     .with_seed(seed_from_bytes(b"overworld"))
+//!     .generate();
+//! let session = NoiseBuilder::fbm_2d(64, 64)
+//!     .with_seed_hash(&("overworld", 12, -7))
+//!     .generate();
+//! assert_eq!(seed_from_u64(42), 42);
+//!
+//! // 3.1 keeps generating wide seeds from the seed they are mixed down to
+//! let pinned = NoiseBuilder::fbm_2d(64, 64)
+//!     .with_seed_u64(1 << 40)
+//!     .with_algorithm_version(AlgorithmVersion::V3_1)
+//!     .generate();
+//! let mixed = NoiseBuilder::fbm_2d(64, 64)
+//!     .with_seed(seed_from_u64(1 << 40))
+//!     .with_algorithm_version(AlgorithmVersion::V3_1)
+//!     .generate();
+//! assert_eq!(pinned.0, mixed.0);
+//! ```
+
+use core::hash::{Hash, Hasher};
+
+/// Mixes a 64 bit seed into the 32 bit seed used by the kernels. Seeds that are an
+/// `i32` sign extended to 64 bits are kept as they are, so `with_seed_u64(n as u64)`
+/// matches `with_seed(n)`; wider seeds are mixed and can collide, see the module docs.
+pub fn seed_from_u64(seed: u64) -> i32 {
+    let narrow = seed as i32;
+    if narrow as i64 as u64 == seed {
+        narrow
+    } else {
+        fold(seed)
+    }
+}
+
+/// Hashes `bytes` into a seed with `SeedHasher`. The seed only depends on the bytes, so
+/// it is the same in every release.
+pub fn seed_from_bytes(bytes: &[u8]) -> i32 {
+    let mut hasher = SeedHasher::default();
+    hasher.write(bytes);
+    fold(hasher.finish())
+}
+
+/// Hashes `value` into a seed with `SeedHasher`. The seed depends on the bytes the
+/// `Hash` impl of `T` writes, see the module docs.
+pub fn seed_from_hash<T: Hash + ?Sized>(value: &T) -> i32 {
+    let mut hasher = SeedHasher::default();
+    value.hash(&mut hasher);
+    fold(hasher.finish())
+}
+
+fn fold(hash: u64) -> i32 {
+    (mix64(hash) >> 32) as i32
+}
+
+/// A 64 bit FNV-1a hasher that writes integers little endian and sizes as 64 bits,
+/// so the same writes hash the same on every platform and in every release. Not for
+/// hash maps.
+#[derive(Copy, Clone, Debug)]
+pub struct SeedHasher {
+    state: u64,
+}
+
+impl Default for SeedHasher {
+    fn default() -> SeedHasher {
+        SeedHasher {
+            state: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+}
+
+impl Hasher for SeedHasher {
+    fn finish(&self) -> u64 {
+        mix64(self.state)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.state = (self.state ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16)
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32)
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64)
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128)
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64)
+    }
+}
+
+/// The splitmix64 finalizer
+#[inline(always)]
+pub(crate) fn mix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_are_stable() {
+        // Hashes must never change, saved worlds depend on them
+        assert_eq!(seed_from_u64(1 << 40), 0x1fdd_7128);
+        assert_eq!(seed_from_bytes(b"overworld"), 0x01a4_053d);
+        assert_eq!(seed_from_u64(7), 7);
+        assert_eq!(seed_from_u64(-7i64 as u64), -7);
+        assert_eq!(seed_from_u64(i32::MIN as i64 as u64), i32::MIN);
+        assert_ne!(seed_from_u64(1 << 31), i32::MIN);
+        assert_eq!(
+            seed_from_hash(&(1u8, -1i64, "a")),
+            seed_from_hash(&(1u8, -1i64, "a"))
+        );
+        assert_ne!(seed_from_hash(&(1, 2)), seed_from_hash(&(2, 1)));
+        assert_ne!(seed_from_hash(&("ab", "c")), seed_from_hash(&("a", "bc")));
+        assert_eq!(seed_from_hash(&7usize), seed_from_hash(&7u64));
+    }
+}
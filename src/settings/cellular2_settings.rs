@@ -1,15 +1,25 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
 use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 use crate::intrinsics::{avx2, scalar, sse2, sse41};
 pub use crate::noise::cell2_return_type::Cell2ReturnType;
 pub use crate::noise::cell_distance_function::CellDistanceFunction;
 pub use crate::noise::cell_return_type::CellReturnType;
+pub use crate::noise_block::NoiseBlock;
 pub use crate::noise_builder::NoiseBuilder;
 pub use crate::noise_dimensions::NoiseDimensions;
+pub use crate::noise_error::NoiseError;
+use crate::noise_error::{check_frequencies, check_jitter};
 pub use crate::noise_type::NoiseType;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
 
 use super::Settings;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cellular2Settings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -43,7 +53,37 @@ impl Settings for Cellular2Settings {
         }
     }
     fn with_seed(&mut self, seed: i32) -> &mut Cellular2Settings {
-        self.dim.seed = seed;
+        self.dim.set_seed(seed);
+        self
+    }
+
+    fn with_seed_u64(&mut self, seed: u64) -> &mut Cellular2Settings {
+        self.dim.set_seed_u64(seed);
+        self
+    }
+
+    fn with_transform(&mut self, transform: Transform) -> &mut Cellular2Settings {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut Cellular2Settings {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut Cellular2Settings {
+        self.dim.algorithm_version = version;
+        self
+    }
+
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut Cellular2Settings {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    fn with_step(&mut self, step: &[f32]) -> &mut Cellular2Settings {
+        self.dim.set_step(step);
         self
     }
 
@@ -90,10 +130,17 @@ impl Settings for Cellular2Settings {
         }
     }
 
-    fn validate(&self) {
+    fn try_validate(&self) -> Result<(), NoiseError> {
+        self.dim.try_validate("cellular2", &[2, 3])?;
+        check_frequencies(&[self.freq_x, self.freq_y, self.freq_z])?;
+        check_jitter(self.jitter)?;
         if self.index0 > 2 || self.index1 > 3 || self.index0 >= self.index1 {
-            panic!("invalid index settings in cellular2 noise");
+            return Err(NoiseError::InvalidCellIndex {
+                index0: self.index0,
+                index1: self.index1,
+            });
         }
+        Ok(())
     }
 
     fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
@@ -111,6 +158,10 @@ impl Settings for Cellular2Settings {
 }
 
 impl Cellular2Settings {
+    pub(crate) fn set_dimensions(&mut self, dim: NoiseDimensions) {
+        self.dim = dim;
+    }
+
     pub fn with_distance_function(&mut self, dist: CellDistanceFunction) -> &mut Cellular2Settings {
         self.distance_function = dist;
         self
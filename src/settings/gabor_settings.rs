@@ -0,0 +1,205 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+pub use crate::noise::cell2_return_type::Cell2ReturnType;
+pub use crate::noise::cell_distance_function::CellDistanceFunction;
+pub use crate::noise::cell_return_type::CellReturnType;
+pub use crate::noise_block::NoiseBlock;
+pub use crate::noise_builder::NoiseBuilder;
+pub use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_error::check_frequencies;
+pub use crate::noise_error::NoiseError;
+pub use crate::noise_type::NoiseType;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
+
+use super::Settings;
+
+use core::f32;
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GaborSettings {
+    dim: NoiseDimensions,
+    pub freq_x: f32,
+    pub freq_y: f32,
+    pub freq_z: f32,
+    /// Cycles of the wave per unit of the frequency scaled coordinates
+    pub frequency: f32,
+    /// Direction of the wave in the xy plane, in radians
+    pub orientation: f32,
+    /// Angle of the wave above the xy plane, in radians, 3d only
+    pub elevation: f32,
+    /// Inverse of the kernel radius, lower values give longer, more coherent streaks
+    pub bandwidth: f32,
+    /// Number of impulses per kernel radius sized cell
+    pub impulse_density: u8,
+}
+
+impl DimensionalBeing for GaborSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        return self.dim;
+    }
+}
+
+impl Settings for GaborSettings {
+    fn default(dim: NoiseDimensions) -> GaborSettings {
+        GaborSettings {
+            dim,
+            freq_x: 0.02,
+            freq_y: 0.02,
+            freq_z: 0.02,
+            frequency: 4.0,
+            orientation: f32::consts::FRAC_PI_4,
+            elevation: 0.0,
+            bandwidth: 1.0,
+            impulse_density: 8,
+        }
+    }
+
+    fn with_seed(&mut self, seed: i32) -> &mut GaborSettings {
+        self.dim.set_seed(seed);
+        self
+    }
+
+    fn with_seed_u64(&mut self, seed: u64) -> &mut GaborSettings {
+        self.dim.set_seed_u64(seed);
+        self
+    }
+
+    fn with_transform(&mut self, transform: Transform) -> &mut GaborSettings {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut GaborSettings {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut GaborSettings {
+        self.dim.algorithm_version = version;
+        self
+    }
+
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut GaborSettings {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    fn with_step(&mut self, step: &[f32]) -> &mut GaborSettings {
+        self.dim.set_step(step);
+        self
+    }
+
+    fn with_freq(&mut self, freq: f32) -> &mut GaborSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self
+    }
+
+    fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut GaborSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut GaborSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    // There is no 4d kernel, so the w frequency is dropped and `try_validate` reports the
+    // dimension as unsupported
+    fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        _freq_w: f32,
+    ) -> &mut GaborSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    fn wrap(self) -> NoiseType {
+        self.validate();
+        NoiseType::Gabor(self)
+    }
+
+    fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => get_2d_noise!(&NoiseType::Gabor(self)),
+            3 => get_3d_noise!(&NoiseType::Gabor(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    fn try_validate(&self) -> Result<(), NoiseError> {
+        self.dim.try_validate("gabor", &[2, 3])?;
+        check_frequencies(&[self.freq_x, self.freq_y, self.freq_z])?;
+        if !(self.bandwidth > 0.0) {
+            return Err(NoiseError::InvalidSetting(
+                "gabor bandwidth must be positive",
+            ));
+        }
+        if self.impulse_density == 0 {
+            return Err(NoiseError::InvalidSetting(
+                "gabor impulse density must be at least 1",
+            ));
+        }
+        Ok(())
+    }
+
+    fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            2 => get_2d_scaled_noise!(&NoiseType::Gabor(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Gabor(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+impl GaborSettings {
+    pub(crate) fn set_dimensions(&mut self, dim: NoiseDimensions) {
+        self.dim = dim;
+    }
+
+    pub fn with_frequency(&mut self, frequency: f32) -> &mut GaborSettings {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_orientation(&mut self, orientation: f32) -> &mut GaborSettings {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_elevation(&mut self, elevation: f32) -> &mut GaborSettings {
+        self.elevation = elevation;
+        self
+    }
+
+    pub fn with_bandwidth(&mut self, bandwidth: f32) -> &mut GaborSettings {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    pub fn with_impulse_density(&mut self, impulse_density: u8) -> &mut GaborSettings {
+        self.impulse_density = impulse_density;
+        self
+    }
+}
@@ -1,15 +1,25 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
 use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 use crate::intrinsics::{avx2, scalar, sse2, sse41};
 pub use crate::noise::cell2_return_type::Cell2ReturnType;
 pub use crate::noise::cell_distance_function::CellDistanceFunction;
 pub use crate::noise::cell_return_type::CellReturnType;
+pub use crate::noise_block::NoiseBlock;
 pub use crate::noise_builder::NoiseBuilder;
 pub use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_error::check_frequencies;
+pub use crate::noise_error::NoiseError;
 pub use crate::noise_type::NoiseType;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
 
 use crate::settings::Settings;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GradientSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -35,7 +45,37 @@ impl Settings for GradientSettings {
         }
     }
     fn with_seed(&mut self, seed: i32) -> &mut GradientSettings {
-        self.dim.seed = seed;
+        self.dim.set_seed(seed);
+        self
+    }
+
+    fn with_seed_u64(&mut self, seed: u64) -> &mut GradientSettings {
+        self.dim.set_seed_u64(seed);
+        self
+    }
+
+    fn with_transform(&mut self, transform: Transform) -> &mut GradientSettings {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut GradientSettings {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut GradientSettings {
+        self.dim.algorithm_version = version;
+        self
+    }
+
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut GradientSettings {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    fn with_step(&mut self, step: &[f32]) -> &mut GradientSettings {
+        self.dim.set_step(step);
         self
     }
 
@@ -79,8 +119,10 @@ impl Settings for GradientSettings {
         NoiseType::Gradient(self)
     }
 
-    fn validate(&self) {
-        //todo
+    fn try_validate(&self) -> Result<(), NoiseError> {
+        self.dim.try_validate("gradient", &[1, 2, 3, 4])?;
+        check_frequencies(&[self.freq_x, self.freq_y, self.freq_z, self.freq_w])?;
+        Ok(())
     }
 
     fn generate(self) -> (Vec<f32>, f32, f32) {
@@ -109,4 +151,8 @@ impl Settings for GradientSettings {
     }
 }
 
-impl GradientSettings {}
+impl GradientSettings {
+    pub(crate) fn set_dimensions(&mut self, dim: NoiseDimensions) {
+        self.dim = dim;
+    }
+}
@@ -1,18 +1,66 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
 pub use crate::noise::cell2_return_type::Cell2ReturnType;
 pub use crate::noise::cell_distance_function::CellDistanceFunction;
 pub use crate::noise::cell_return_type::CellReturnType;
+pub use crate::noise_block::NoiseBlock;
 pub use crate::noise_builder::NoiseBuilder;
 pub use crate::noise_dimensions::NoiseDimensions;
+pub use crate::noise_error::NoiseError;
 pub use crate::noise_type::NoiseType;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
+
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+use crate::seed::seed_from_hash;
+
+use core::hash::Hash;
 
-pub trait Settings {
+pub trait Settings: DimensionalBeing {
     fn default(dim: NoiseDimensions) -> Self;
     fn with_seed(&mut self, seed: i32) -> &mut Self;
+
+    /// Seed with a 64 bit value. The kernels hash with the seed `seed_from_u64` mixes it
+    /// down to, and from `AlgorithmVersion::V3_2` on the rest of it shifts the sampling
+    /// domain, see `NoiseDimensions::seed_u64`
+    fn with_seed_u64(&mut self, seed: u64) -> &mut Self;
+
+    /// Seed with the hash of a string, tuple or any other `Hash` value, see `SeedHasher`
+    fn with_seed_hash<T: Hash + ?Sized>(&mut self, value: &T) -> &mut Self {
+        self.with_seed(seed_from_hash(value))
+    }
     fn with_freq(&mut self, freq: f32) -> &mut Self;
     fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut Self;
     fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut Self;
     fn with_freq_4d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32, freq_w: f32) -> &mut Self;
 
+    /// Rotate, shear or translate the sampling domain. The transform is applied to the
+    /// coordinates after they are scaled by the frequency.
+    fn with_transform(&mut self, transform: Transform) -> &mut Self;
+
+    /// Choose how the min and max of generated blocks are found. Use `RangeMode::Analytic`
+    /// to scale separately generated chunks consistently.
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut Self;
+
+    /// Generate with the algorithms of `version` instead of `AlgorithmVersion::LATEST`,
+    /// so the noise stays the same when later releases change the algorithms
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut Self;
+
+    /// Generate the block as the chunk at integer coordinates `chunk`, one per dimension,
+    /// on a grid with `size` samples between chunk origins. Coordinates are computed
+    /// exactly from the integer sample positions and replace the offset, so samples that
+    /// neighbouring chunks share are bit-identical. Make the block `size + 1` samples
+    /// wide to overlap neighbours by one sample.
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut Self;
+
+    /// Space samples `step` apart, one step per dimension, instead of 1.0. A block
+    /// with a step of k samples the same points as every k-th sample of a block with
+    /// a step of 1 and the same offset, which is what coarser levels of detail need.
+    fn with_step(&mut self, step: &[f32]) -> &mut Self;
+
     /// If you want to call noise functions by hand, call wrap on the settings
     /// to get back a NoiseType to call the noise functions with
     fn wrap(self) -> NoiseType;
@@ -20,10 +68,70 @@ pub trait Settings {
     /// Generate a chunk of noise based on your settings, and the min and max value
     /// generated, so you can scale it as you wish
     fn generate(self) -> (Vec<f32>, f32, f32);
-    fn validate(&self);
+
+    /// Generate a chunk of noise as a `NoiseBlock`, which carries its shape, min, max
+    /// and these settings along with the data. Panics if the block is empty.
+    fn generate_block(self) -> NoiseBlock
+    where
+        Self: Sized + Copy,
+    {
+        let (data, min, max) = self.generate();
+        NoiseBlock::new(self.wrap(), data, min, max)
+    }
+
+    /// Panics if the settings are invalid, see `try_validate`
+    fn validate(&self) {
+        if let Err(e) = self.try_validate() {
+            panic!("{}", e);
+        }
+    }
+
+    /// Checks the dimension is supported by the noise type, frequencies are finite, and
+    /// type specific settings such as octaves, jitter and cell indices are in range
+    fn try_validate(&self) -> Result<(), NoiseError>;
+
+    /// Like `wrap`, but returns an error for invalid settings instead of panicking
+    fn try_wrap(self) -> Result<NoiseType, NoiseError>
+    where
+        Self: Sized,
+    {
+        self.try_validate()?;
+        Ok(self.wrap())
+    }
+
+    /// Like `generate`, but returns an error for invalid settings, or an empty or
+    /// unindexably large block, instead of panicking or producing garbage
+    fn try_generate(self) -> Result<(Vec<f32>, f32, f32), NoiseError>
+    where
+        Self: Sized,
+    {
+        self.try_validate()?;
+        self.get_dimensions().try_validate_block()?;
+        Ok(self.generate())
+    }
 
     /// Generate a chunk of noise with values scaled from min to max
     fn generate_scaled(self, min: f32, max: f32) -> Vec<f32>;
+
+    /// Generate a chunk of noise scaled from its min and max to 0..=255. Ordered
+    /// dithering replaces rounding when `dither` is set, to avoid banding. Panics if
+    /// the settings are invalid.
+    fn generate_u8(self, dither: bool) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        // wrap calls validate
+        get_u8_noise!(&self.wrap(), dither)
+    }
+
+    /// Generate a chunk of noise scaled from its min and max to 0..=65535, see `generate_u8`
+    fn generate_u16(self, dither: bool) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        // wrap calls validate
+        get_u16_noise!(&self.wrap(), dither)
+    }
 }
 
 pub trait SimplexSettings {
@@ -35,13 +143,19 @@ pub trait SimplexSettings {
 mod cellular2_settings;
 mod cellular_settings;
 mod fbm_settings;
+mod gabor_settings;
 mod gradient_settings;
 mod ridge_settings;
 mod turbulence_settings;
+mod wavelet_settings;
+mod white_noise_settings;
 
 pub use cellular2_settings::Cellular2Settings;
 pub use cellular_settings::CellularSettings;
 pub use fbm_settings::FbmSettings;
+pub use gabor_settings::GaborSettings;
 pub use gradient_settings::GradientSettings;
 pub use ridge_settings::RidgeSettings;
 pub use turbulence_settings::TurbulenceSettings;
+pub use wavelet_settings::WaveletSettings;
+pub use white_noise_settings::WhiteNoiseSettings;
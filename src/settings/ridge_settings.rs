@@ -1,15 +1,27 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
 use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
 use crate::intrinsics::{avx2, scalar, sse2, sse41};
 pub use crate::noise::cell2_return_type::Cell2ReturnType;
 pub use crate::noise::cell_distance_function::CellDistanceFunction;
 pub use crate::noise::cell_return_type::CellReturnType;
+use crate::noise::octave_params::OctaveParams;
+pub use crate::noise::octave_params::OctaveSeeding;
+pub use crate::noise_block::NoiseBlock;
 pub use crate::noise_builder::NoiseBuilder;
 pub use crate::noise_dimensions::NoiseDimensions;
+pub use crate::noise_error::NoiseError;
+use crate::noise_error::{check_frequencies, check_octaves};
 pub use crate::noise_type::NoiseType;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
 
 use super::{Settings, SimplexSettings};
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RidgeSettings {
     dim: NoiseDimensions,
     pub freq_x: f32,
@@ -19,6 +31,11 @@ pub struct RidgeSettings {
     pub lacunarity: f32,
     pub gain: f32,
     pub octaves: u8,
+    /// Rotation applied between octaves, see `with_octave_rotation`
+    pub octave_rotation: Option<Transform>,
+    /// Seed or offset of each octave, see `with_octave_seeding`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub octave_seeding: OctaveSeeding,
 }
 
 impl DimensionalBeing for RidgeSettings {
@@ -38,11 +55,43 @@ impl Settings for RidgeSettings {
             lacunarity: 0.5,
             gain: 2.0,
             octaves: 3,
+            octave_rotation: None,
+            octave_seeding: OctaveSeeding::Shared,
         }
     }
 
     fn with_seed(&mut self, seed: i32) -> &mut RidgeSettings {
-        self.dim.seed = seed;
+        self.dim.set_seed(seed);
+        self
+    }
+
+    fn with_seed_u64(&mut self, seed: u64) -> &mut RidgeSettings {
+        self.dim.set_seed_u64(seed);
+        self
+    }
+
+    fn with_transform(&mut self, transform: Transform) -> &mut RidgeSettings {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut RidgeSettings {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut RidgeSettings {
+        self.dim.algorithm_version = version;
+        self
+    }
+
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut RidgeSettings {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    fn with_step(&mut self, step: &[f32]) -> &mut RidgeSettings {
+        self.dim.set_step(step);
         self
     }
 
@@ -86,8 +135,11 @@ impl Settings for RidgeSettings {
         NoiseType::Ridge(self)
     }
 
-    fn validate(&self) {
-        //todo
+    fn try_validate(&self) -> Result<(), NoiseError> {
+        self.dim.try_validate("ridge", &[1, 2, 3, 4])?;
+        check_frequencies(&[self.freq_x, self.freq_y, self.freq_z, self.freq_w])?;
+        check_octaves(self.octaves)?;
+        Ok(())
     }
 
     fn generate(self) -> (Vec<f32>, f32, f32) {
@@ -133,4 +185,32 @@ impl SimplexSettings for RidgeSettings {
     }
 }
 
-impl RidgeSettings {}
+impl RidgeSettings {
+    pub(crate) fn set_dimensions(&mut self, dim: NoiseDimensions) {
+        self.dim = dim;
+    }
+
+    /// Rotate the domain by the linear part of `rotation` before each octave after the
+    /// first, so the lattice artifacts of successive octaves don't line up. A small
+    /// rotation that isn't a multiple of 90 degrees works best. 1d noise ignores it.
+    pub fn with_octave_rotation(&mut self, rotation: Transform) -> &mut RidgeSettings {
+        self.octave_rotation = Some(rotation);
+        self
+    }
+
+    /// Give each octave its own seed, or its own offset, instead of sharing the seed
+    /// of the settings. Sharing it is the default and matches earlier releases, but
+    /// correlates the octaves, which shows as artifacts near the origin.
+    pub fn with_octave_seeding(&mut self, seeding: OctaveSeeding) -> &mut RidgeSettings {
+        self.octave_seeding = seeding;
+        self
+    }
+
+    /// The per-octave options passed to the fractal kernels
+    pub(crate) fn octave_params(&self) -> OctaveParams {
+        OctaveParams {
+            rotation: self.octave_rotation,
+            seeding: self.octave_seeding,
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+pub use crate::noise::cell2_return_type::Cell2ReturnType;
+pub use crate::noise::cell_distance_function::CellDistanceFunction;
+pub use crate::noise::cell_return_type::CellReturnType;
+use crate::noise::wavelet_32::WaveletTile;
+pub use crate::noise_block::NoiseBlock;
+pub use crate::noise_builder::NoiseBuilder;
+pub use crate::noise_dimensions::NoiseDimensions;
+pub use crate::noise_error::NoiseError;
+use crate::noise_error::{check_frequencies, check_octaves};
+pub use crate::noise_type::NoiseType;
+use crate::point::check_tile;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
+
+use super::Settings;
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WaveletSettings {
+    dim: NoiseDimensions,
+    pub freq_x: f32,
+    pub freq_y: f32,
+    pub freq_z: f32,
+    /// Weight of each band relative to the previous one, bands double in frequency
+    pub gain: f32,
+    pub octaves: u8,
+    /// Edge length of the precomputed coefficient tile, a power of two
+    pub tile_size: usize,
+    /// Surface normal to project 3d noise along, see `with_projection`
+    pub normal: Option<[f32; 3]>,
+}
+
+impl DimensionalBeing for WaveletSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        return self.dim;
+    }
+}
+
+impl Settings for WaveletSettings {
+    fn default(dim: NoiseDimensions) -> WaveletSettings {
+        WaveletSettings {
+            dim,
+            freq_x: 0.05,
+            freq_y: 0.05,
+            freq_z: 0.05,
+            gain: 0.5,
+            octaves: 3,
+            tile_size: 64,
+            normal: None,
+        }
+    }
+
+    fn with_seed(&mut self, seed: i32) -> &mut WaveletSettings {
+        self.dim.set_seed(seed);
+        self
+    }
+
+    fn with_seed_u64(&mut self, seed: u64) -> &mut WaveletSettings {
+        self.dim.set_seed_u64(seed);
+        self
+    }
+
+    fn with_transform(&mut self, transform: Transform) -> &mut WaveletSettings {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut WaveletSettings {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut WaveletSettings {
+        self.dim.algorithm_version = version;
+        self
+    }
+
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut WaveletSettings {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    fn with_step(&mut self, step: &[f32]) -> &mut WaveletSettings {
+        self.dim.set_step(step);
+        self
+    }
+
+    fn with_freq(&mut self, freq: f32) -> &mut WaveletSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self
+    }
+
+    fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut WaveletSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut WaveletSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    // There is no 4d kernel, so the w frequency is dropped and `try_validate` reports the
+    // dimension as unsupported
+    fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        _freq_w: f32,
+    ) -> &mut WaveletSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    fn wrap(self) -> NoiseType {
+        self.validate();
+        NoiseType::Wavelet(self)
+    }
+
+    fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            2 => get_2d_noise!(&NoiseType::Wavelet(self)),
+            3 => get_3d_noise!(&NoiseType::Wavelet(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    fn try_validate(&self) -> Result<(), NoiseError> {
+        self.dim.try_validate("wavelet", &[2, 3])?;
+        check_frequencies(&[self.freq_x, self.freq_y, self.freq_z])?;
+        check_octaves(self.octaves)?;
+        if self.tile_size < 8 || !self.tile_size.is_power_of_two() {
+            return Err(NoiseError::InvalidSetting(
+                "wavelet tile size must be a power of two of at least 8",
+            ));
+        }
+        Ok(())
+    }
+
+    fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            2 => get_2d_scaled_noise!(&NoiseType::Wavelet(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::Wavelet(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+impl WaveletSettings {
+    pub(crate) fn set_dimensions(&mut self, dim: NoiseDimensions) {
+        self.dim = dim;
+    }
+
+    pub fn with_gain(&mut self, gain: f32) -> &mut WaveletSettings {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_octaves(&mut self, octaves: u8) -> &mut WaveletSettings {
+        self.octaves = octaves;
+        self
+    }
+
+    pub fn with_tile_size(&mut self, tile_size: usize) -> &mut WaveletSettings {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// Generates the block like `generate`, sampling `tile` instead of building a new one.
+    /// Build the tile once with `point::wavelet_tile` and share it between blocks.
+    pub fn generate_with_tile(self, tile: &WaveletTile) -> (Vec<f32>, f32, f32) {
+        self.validate();
+        check_tile(&self, self.dim.dim, tile);
+        get_wavelet_noise!(&self, tile)
+    }
+
+    /// Evaluate 3d noise projected along `normal`, the noise seen by a surface with that normal.
+    /// 2d blocks are taken from the z = 0 plane (shifted by the z offset).
+    pub fn with_projection(&mut self, normal: [f32; 3]) -> &mut WaveletSettings {
+        let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+        if !(length > 0.0) {
+            panic!("projection normal must not be zero");
+        }
+        self.normal = Some([normal[0] / length, normal[1] / length, normal[2] / length]);
+        self
+    }
+}
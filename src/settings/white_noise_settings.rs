@@ -0,0 +1,164 @@
+use alloc::vec::Vec;
+
+pub use crate::algorithm_version::AlgorithmVersion;
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+pub use crate::noise::cell2_return_type::Cell2ReturnType;
+pub use crate::noise::cell_distance_function::CellDistanceFunction;
+pub use crate::noise::cell_return_type::CellReturnType;
+pub use crate::noise_block::NoiseBlock;
+pub use crate::noise_builder::NoiseBuilder;
+pub use crate::noise_dimensions::NoiseDimensions;
+use crate::noise_error::check_frequencies;
+pub use crate::noise_error::NoiseError;
+pub use crate::noise_type::NoiseType;
+pub use crate::range_mode::RangeMode;
+pub use crate::transform::Transform;
+
+use crate::settings::Settings;
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhiteNoiseSettings {
+    dim: NoiseDimensions,
+    pub freq_x: f32,
+    pub freq_y: f32,
+    pub freq_z: f32,
+    pub freq_w: f32,
+}
+
+impl DimensionalBeing for WhiteNoiseSettings {
+    fn get_dimensions(&self) -> NoiseDimensions {
+        return self.dim;
+    }
+}
+
+impl Settings for WhiteNoiseSettings {
+    fn default(dim: NoiseDimensions) -> WhiteNoiseSettings {
+        WhiteNoiseSettings {
+            dim,
+            freq_x: 1.0,
+            freq_y: 1.0,
+            freq_z: 1.0,
+            freq_w: 1.0,
+        }
+    }
+    fn with_seed(&mut self, seed: i32) -> &mut WhiteNoiseSettings {
+        self.dim.set_seed(seed);
+        self
+    }
+
+    fn with_seed_u64(&mut self, seed: u64) -> &mut WhiteNoiseSettings {
+        self.dim.set_seed_u64(seed);
+        self
+    }
+
+    fn with_transform(&mut self, transform: Transform) -> &mut WhiteNoiseSettings {
+        self.dim.transform = Some(transform);
+        self
+    }
+
+    fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut WhiteNoiseSettings {
+        self.dim.range_mode = range_mode;
+        self
+    }
+
+    fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut WhiteNoiseSettings {
+        self.dim.algorithm_version = version;
+        self
+    }
+
+    fn with_chunk(&mut self, chunk: &[i64], size: usize) -> &mut WhiteNoiseSettings {
+        self.dim.set_chunk(chunk, size);
+        self
+    }
+
+    fn with_step(&mut self, step: &[f32]) -> &mut WhiteNoiseSettings {
+        self.dim.set_step(step);
+        self
+    }
+
+    fn with_freq(&mut self, freq: f32) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq;
+        self.freq_y = freq;
+        self.freq_z = freq;
+        self.freq_w = freq;
+        self
+    }
+
+    fn with_freq_2d(&mut self, freq_x: f32, freq_y: f32) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self
+    }
+
+    fn with_freq_3d(&mut self, freq_x: f32, freq_y: f32, freq_z: f32) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self
+    }
+
+    fn with_freq_4d(
+        &mut self,
+        freq_x: f32,
+        freq_y: f32,
+        freq_z: f32,
+        freq_w: f32,
+    ) -> &mut WhiteNoiseSettings {
+        self.freq_x = freq_x;
+        self.freq_y = freq_y;
+        self.freq_z = freq_z;
+        self.freq_w = freq_w;
+        self
+    }
+
+    fn wrap(self) -> NoiseType {
+        self.validate();
+        NoiseType::WhiteNoise(self)
+    }
+
+    fn try_validate(&self) -> Result<(), NoiseError> {
+        self.dim.try_validate("white", &[1, 2, 3, 4])?;
+        check_frequencies(&[self.freq_x, self.freq_y, self.freq_z, self.freq_w])?;
+        Ok(())
+    }
+
+    fn generate(self) -> (Vec<f32>, f32, f32) {
+        let d = self.dim.dim;
+        match d {
+            1 => get_1d_noise!(&NoiseType::WhiteNoise(self)),
+            2 => get_2d_noise!(&NoiseType::WhiteNoise(self)),
+            3 => get_3d_noise!(&NoiseType::WhiteNoise(self)),
+            4 => get_4d_noise!(&NoiseType::WhiteNoise(self)),
+            _ => panic!("not implemented"),
+        }
+    }
+
+    fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+        let d = self.dim.dim;
+        let mut new_self = self;
+        new_self.dim.min = min;
+        new_self.dim.max = max;
+        match d {
+            1 => get_1d_scaled_noise!(&NoiseType::WhiteNoise(new_self)),
+            2 => get_2d_scaled_noise!(&NoiseType::WhiteNoise(new_self)),
+            3 => get_3d_scaled_noise!(&NoiseType::WhiteNoise(new_self)),
+            4 => get_4d_scaled_noise!(&NoiseType::WhiteNoise(new_self)),
+            _ => panic!("not implemented"),
+        }
+    }
+}
+
+impl WhiteNoiseSettings {
+    pub(crate) fn set_dimensions(&mut self, dim: NoiseDimensions) {
+        self.dim = dim;
+    }
+
+    /// Generate a chunk of the raw integer hashes the white noise values are derived from,
+    /// in the same layout as `generate`
+    pub fn generate_hash(self) -> Vec<i32> {
+        get_white_hash!(&self)
+    }
+}
@@ -1,5 +1,19 @@
+use alloc::vec::Vec;
+
+use crate::noise_dimensions::NoiseDimensions;
+use crate::remap::{remap_ps, Remap};
+
 use simdeez::Simd;
 
+// Multiplier and offset taking min..max to scale_min..scale_max
+#[inline(always)]
+fn scale_params(scale_min: f32, scale_max: f32, min: f32, max: f32) -> (f32, f32) {
+    let scale_range = scale_max - scale_min;
+    let range = max - min;
+    let multiplier = scale_range / range;
+    (multiplier, scale_min - min * multiplier)
+}
+
 #[inline(always)]
 pub unsafe fn scale_noise<S: Simd>(
     scale_min: f32,
@@ -8,10 +22,7 @@ pub unsafe fn scale_noise<S: Simd>(
     max: f32,
     data: &mut Vec<f32>,
 ) {
-    let scale_range = scale_max - scale_min;
-    let range = max - min;
-    let multiplier = scale_range / range;
-    let offset = scale_min - min * multiplier;
+    let (multiplier, offset) = scale_params(scale_min, scale_max, min, max);
     let vector_width = S::VF32_WIDTH;
     let mut i = 0;
     if data.len() >= vector_width {
@@ -30,3 +41,108 @@ pub unsafe fn scale_noise<S: Simd>(
         i += 1;
     }
 }
+
+#[inline(always)]
+pub unsafe fn remap_noise<S: Simd>(remap: &Remap, data: &mut [f32]) {
+    let vector_width = S::VF32_WIDTH;
+    let mut i = 0;
+    if data.len() >= vector_width {
+        while i <= data.len() - vector_width {
+            let value = remap_ps::<S>(remap, S::loadu_ps(&data[i]));
+            S::storeu_ps(data.get_unchecked_mut(i), value);
+            i += vector_width;
+        }
+    }
+    // Pad the tail out to a full vector so it goes through the same code
+    let remainder = data.len() - i;
+    if remainder > 0 {
+        let mut tail = [0.0f32; 16];
+        tail[..remainder].copy_from_slice(&data[i..]);
+        let value = remap_ps::<S>(remap, S::loadu_ps(&tail[0]));
+        S::storeu_ps(&mut tail[0], value);
+        data[i..].copy_from_slice(&tail[..remainder]);
+    }
+}
+
+/// Integer types noise can be quantized to
+pub trait Quantized: Copy {
+    const MAX: f32;
+    fn from_i32(v: i32) -> Self;
+}
+
+impl Quantized for u8 {
+    const MAX: f32 = u8::MAX as f32;
+    fn from_i32(v: i32) -> u8 {
+        v as u8
+    }
+}
+
+impl Quantized for u16 {
+    const MAX: f32 = u16::MAX as f32;
+    fn from_i32(v: i32) -> u16 {
+        v as u16
+    }
+}
+
+// 4x4 Bayer matrix
+const BAYER: [f32; 16] = [
+    0.0, 8.0, 2.0, 10.0, 12.0, 4.0, 14.0, 6.0, 3.0, 11.0, 1.0, 9.0, 15.0, 7.0, 13.0, 5.0,
+];
+
+/// Scales `data` from min..max to the full range of `T`, rounding and saturating in
+/// SIMD. With `dither`, a 4x4 ordered dither pattern over x and y replaces plain
+/// rounding, which breaks up banding in smooth gradients.
+#[inline(always)]
+pub unsafe fn quantize_noise<S: Simd, T: Quantized>(
+    data: &[f32],
+    dim: &NoiseDimensions,
+    min: f32,
+    max: f32,
+    dither: bool,
+) -> Vec<T> {
+    let (multiplier, offset) = scale_params(0.0, T::MAX, min, max);
+    let vector_width = S::VF32_WIDTH;
+    let width = dim.width.max(1);
+    let height = if dim.dim > 1 { dim.height.max(1) } else { 1 };
+
+    // Rounding offsets for each row of the pattern, long enough to load a vector
+    // from any column
+    let rows: Vec<Vec<f32>> = (0..4)
+        .map(|y| {
+            (0..width + vector_width)
+                .map(|x| {
+                    if dither {
+                        (BAYER[(y & 3) * 4 + (x & 3)] + 0.5) / 16.0
+                    } else {
+                        0.5
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut result = Vec::with_capacity(data.len());
+    let mut tail = [0.0f32; 16];
+    for (r, row) in data.chunks(width).enumerate() {
+        let rounding = &rows[(r % height) & 3];
+        let mut x = 0;
+        while x < row.len() {
+            let n = (row.len() - x).min(vector_width);
+            let v = if n == vector_width {
+                S::loadu_ps(&row[x])
+            } else {
+                tail[..n].copy_from_slice(&row[x..]);
+                S::loadu_ps(&tail[0])
+            };
+            let scaled = S::add_ps(S::mul_ps(S::set1_ps(multiplier), v), S::set1_ps(offset));
+            let rounded = S::floor_ps(S::add_ps(scaled, S::loadu_ps(&rounding[x])));
+            let clamped = S::min_ps(S::max_ps(rounded, S::setzero_ps()), S::set1_ps(T::MAX));
+            let q = S::cvtps_epi32(clamped);
+            for j in 0..n {
+                result.push(T::from_i32(q[j]));
+            }
+            x += n;
+        }
+    }
+    result
+}
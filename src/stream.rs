@@ -0,0 +1,147 @@
+//! Generate blocks a row at a time, for outputs too big to hold in memory.
+//!
+//! Rows run along x, in y, then z, then w order, the same order as the `Vec` returned by
+//! `generate`, and have exactly the same values. Only one row is held in memory, and each
+//! row is generated with SIMD like a block. The min and max of the whole block are returned
+//! once every row has been generated.
+//!
+//! ```rust
+//! use simdnoise::*;
+//!
+//! let mut sum = 0.0;
+//! let (min, max) = NoiseBuilder::fbm_2d(1024, 16).generate_rows(|row| {
+//!     sum += row.iter().sum::<f32>();
+//! });
+//!
+//! let mut file = Vec::new();
+//! let (min, max) = NoiseBuilder::ridge_3d(64, 64, 4)
+//!     .with_seed(7)
+//!     .write_rows(&mut file)
+//!     .unwrap();
+//! assert_eq!(file.len(), 64 * 64 * 4 * 4);
+//! ```
+
+use crate::dimensional_being::DimensionalBeing;
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+use crate::intrinsics::{avx2, scalar, sse2, sse41};
+use crate::noise_type::NoiseType;
+use crate::settings::Settings;
+
+use core::convert::Infallible;
+
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+pub trait StreamSettings: Settings {
+    /// Generate the block a row along x at a time, calling `f` with each row, and return
+    /// the min and max of the whole block
+    fn generate_rows<F: FnMut(&[f32])>(self, f: F) -> (f32, f32);
+
+    /// Like `generate_rows`, but stops at the first error `f` returns
+    fn try_generate_rows<E, F: FnMut(&[f32]) -> Result<(), E>>(self, f: F)
+        -> Result<(f32, f32), E>;
+
+    /// Write the block to `writer` a row at a time, as little endian `f32`s, and return
+    /// the min and max of the whole block
+    #[cfg(feature = "std")]
+    fn write_rows<W: Write>(self, writer: W) -> io::Result<(f32, f32)>;
+}
+
+impl<T: Settings> StreamSettings for T {
+    fn generate_rows<F: FnMut(&[f32])>(self, mut f: F) -> (f32, f32) {
+        let result: Result<_, Infallible> = rows(&self.wrap(), &mut |row| {
+            f(row);
+            Ok(())
+        });
+        match result {
+            Ok(range) => range,
+            Err(never) => match never {},
+        }
+    }
+
+    fn try_generate_rows<E, F: FnMut(&[f32]) -> Result<(), E>>(
+        self,
+        mut f: F,
+    ) -> Result<(f32, f32), E> {
+        rows(&self.wrap(), &mut f)
+    }
+
+    #[cfg(feature = "std")]
+    fn write_rows<W: Write>(self, mut writer: W) -> io::Result<(f32, f32)> {
+        let noise_type = self.wrap();
+        let mut bytes = Vec::with_capacity(noise_type.get_dimensions().width * 4);
+        rows(&noise_type, &mut |row| {
+            bytes.clear();
+            for v in row {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+            writer.write_all(&bytes)
+        })
+    }
+}
+
+pub(crate) fn rows<E>(
+    noise_type: &NoiseType,
+    f: &mut dyn FnMut(&[f32]) -> Result<(), E>,
+) -> Result<(f32, f32), E> {
+    get_noise_rows!(noise_type, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoiseBuilder, RangeMode};
+
+    fn collect_rows<T: Settings + Copy>(settings: T) -> (Vec<f32>, f32, f32) {
+        let mut data = Vec::new();
+        let (min, max) = settings.generate_rows(|row| data.extend_from_slice(row));
+        (data, min, max)
+    }
+
+    #[test]
+    fn rows_match_blocks() {
+        let fbm = *NoiseBuilder::fbm_2d_offset(-3.5, 37, 10.25, 9)
+            .with_step(&[0.5, 3.0])
+            .with_seed(3);
+        assert_eq!(collect_rows(fbm), fbm.generate());
+
+        let cellular = *NoiseBuilder::cellular_3d(19, 5, 3).with_chunk(&[-2, 7, 1], 16);
+        assert_eq!(collect_rows(cellular), cellular.generate());
+
+        let gradient = *NoiseBuilder::gradient_4d_offset(1.0, 11, 2.0, 3, 3.0, 2, 4.0, 2)
+            .with_range_mode(RangeMode::Analytic);
+        assert_eq!(collect_rows(gradient), gradient.generate());
+
+        let wavelet = *NoiseBuilder::wavelet_2d(16, 4).with_seed(9);
+        assert_eq!(collect_rows(wavelet), wavelet.generate());
+    }
+
+    #[test]
+    fn write_rows_writes_every_value() {
+        let turbulence = *NoiseBuilder::turbulence_1d(21).with_seed(5);
+        let mut bytes = Vec::new();
+        let (min, max) = turbulence.write_rows(&mut bytes).unwrap();
+        let (data, block_min, block_max) = turbulence.generate();
+        let written: Vec<f32> = bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        assert_eq!(written, data);
+        assert_eq!((min, max), (block_min, block_max));
+    }
+
+    #[test]
+    fn errors_stop_generation() {
+        let mut count = 0;
+        let result = NoiseBuilder::ridge_2d(8, 8).try_generate_rows(|_| {
+            count += 1;
+            if count == 3 {
+                Err("full")
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err("full"));
+        assert_eq!(count, 3);
+    }
+}
@@ -0,0 +1,494 @@
+//! Settings typed by their dimension, so invalid combinations don't compile.
+//!
+//! `typed::FbmSettings<D2>` only has the 2d frequency setter, chunk and step take exactly
+//! two components, and noise types without a 1d or 4d implementation can't be built in
+//! those dimensions. The settings from `NoiseBuilder` remain available; typed settings
+//! wrap them, convert into them with `From`, and are checked out of them with `TryFrom`.
+//!
+//! ```rust
+//! use simdnoise::typed::{CellularSettings, FbmSettings, D2, D3};
+//!
+//! let (terrain, _min, _max) = FbmSettings::<D2>::new(256, 256)
+//!     .with_freq_2d(0.01, 0.02)
+//!     .with_octaves(5)
+//!     .generate();
+//! let cells = CellularSettings::<D3>::new(32, 32, 32).with_jitter(0.5).generate_block();
+//! ```
+//!
+//! ```rust,compile_fail
+//! use simdnoise::typed::{CellularSettings, D2};
+//!
+//! // Cellular noise has no 4d frequency
+//! CellularSettings::<D2>::new(64, 64).with_freq_4d(0.1, 0.1, 0.1, 0.1);
+//! ```
+//!
+//! ```rust,compile_fail
+//! use simdnoise::typed::{CellularSettings, D4};
+//!
+//! // Cellular noise is only implemented in 2d and 3d
+//! CellularSettings::<D4>::new(8, 8, 8, 8);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::algorithm_version::AlgorithmVersion;
+use crate::dimensional_being::DimensionalBeing;
+use crate::noise_block::NoiseBlock;
+use crate::noise_builder::NoiseBuilder;
+use crate::noise_error::NoiseError;
+use crate::noise_type::NoiseType;
+use crate::range_mode::RangeMode;
+use crate::settings::{self as untyped, Settings, SimplexSettings};
+use crate::transform::Transform;
+use crate::{Cell2ReturnType, CellDistanceFunction, CellReturnType, OctaveSeeding};
+
+use core::convert::TryFrom;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// The dimension of a typed setting, one of `D1`, `D2`, `D3` or `D4`
+pub trait Dim: private::Sealed + Copy {
+    const DIM: usize;
+    /// Integer chunk coordinates, one per axis
+    type Chunk: AsRef<[i64]>;
+    /// Sample spacings, one per axis
+    type Step: AsRef<[f32]>;
+}
+
+/// Dimensions with cellular, gabor and wavelet implementations
+pub trait Dim2Or3: Dim {}
+
+#[derive(Copy, Clone, Debug)]
+pub struct D1;
+#[derive(Copy, Clone, Debug)]
+pub struct D2;
+#[derive(Copy, Clone, Debug)]
+pub struct D3;
+#[derive(Copy, Clone, Debug)]
+pub struct D4;
+
+macro_rules! dim {
+    ($D:ident, $n:expr) => {
+        impl private::Sealed for $D {}
+        impl Dim for $D {
+            const DIM: usize = $n;
+            type Chunk = [i64; $n];
+            type Step = [f32; $n];
+        }
+    };
+}
+
+dim!(D1, 1);
+dim!(D2, 2);
+dim!(D3, 3);
+dim!(D4, 4);
+
+impl Dim2Or3 for D2 {}
+impl Dim2Or3 for D3 {}
+
+// A typed wrapper of an untyped settings type, with the setters and generate calls that
+// are valid in every dimension the bound allows
+macro_rules! typed_settings {
+    ($Name:ident, $Bound:ident) => {
+        #[derive(Copy, Clone)]
+        pub struct $Name<D: $Bound> {
+            inner: untyped::$Name,
+            dim: PhantomData<D>,
+        }
+
+        impl<D: $Bound> $Name<D> {
+            fn typed(inner: untyped::$Name) -> $Name<D> {
+                $Name {
+                    inner,
+                    dim: PhantomData,
+                }
+            }
+
+            /// The untyped settings, as `NoiseBuilder` would return them
+            pub fn untyped(&self) -> untyped::$Name {
+                self.inner
+            }
+
+            pub fn with_seed(&mut self, seed: i32) -> &mut $Name<D> {
+                self.inner.with_seed(seed);
+                self
+            }
+
+            pub fn with_seed_u64(&mut self, seed: u64) -> &mut $Name<D> {
+                self.inner.with_seed_u64(seed);
+                self
+            }
+
+            pub fn with_seed_hash<T: Hash + ?Sized>(&mut self, value: &T) -> &mut $Name<D> {
+                self.inner.with_seed_hash(value);
+                self
+            }
+
+            pub fn with_freq(&mut self, freq: f32) -> &mut $Name<D> {
+                self.inner.with_freq(freq);
+                self
+            }
+
+            /// See `Settings::with_transform`
+            pub fn with_transform(&mut self, transform: Transform) -> &mut $Name<D> {
+                self.inner.with_transform(transform);
+                self
+            }
+
+            /// See `Settings::with_range_mode`
+            pub fn with_range_mode(&mut self, range_mode: RangeMode) -> &mut $Name<D> {
+                self.inner.with_range_mode(range_mode);
+                self
+            }
+
+            /// See `Settings::with_algorithm_version`
+            pub fn with_algorithm_version(&mut self, version: AlgorithmVersion) -> &mut $Name<D> {
+                self.inner.with_algorithm_version(version);
+                self
+            }
+
+            /// See `Settings::with_chunk`
+            pub fn with_chunk(&mut self, chunk: D::Chunk, size: usize) -> &mut $Name<D> {
+                self.inner.with_chunk(chunk.as_ref(), size);
+                self
+            }
+
+            /// See `Settings::with_step`
+            pub fn with_step(&mut self, step: D::Step) -> &mut $Name<D> {
+                self.inner.with_step(step.as_ref());
+                self
+            }
+
+            pub fn wrap(self) -> NoiseType {
+                self.inner.wrap()
+            }
+
+            pub fn generate(self) -> (Vec<f32>, f32, f32) {
+                self.inner.generate()
+            }
+
+            pub fn generate_scaled(self, min: f32, max: f32) -> Vec<f32> {
+                self.inner.generate_scaled(min, max)
+            }
+
+            pub fn generate_block(self) -> NoiseBlock {
+                self.inner.generate_block()
+            }
+
+            pub fn generate_u8(self, dither: bool) -> Vec<u8> {
+                self.inner.generate_u8(dither)
+            }
+
+            pub fn generate_u16(self, dither: bool) -> Vec<u16> {
+                self.inner.generate_u16(dither)
+            }
+
+            /// Settings that are only known at runtime, such as jitter or octaves, can
+            /// still be invalid, see `Settings::try_validate`
+            pub fn try_validate(&self) -> Result<(), NoiseError> {
+                self.inner.try_validate()
+            }
+
+            pub fn try_generate(self) -> Result<(Vec<f32>, f32, f32), NoiseError> {
+                self.inner.try_generate()
+            }
+        }
+
+        impl<D: $Bound> From<$Name<D>> for untyped::$Name {
+            fn from(settings: $Name<D>) -> untyped::$Name {
+                settings.inner
+            }
+        }
+
+        /// Fails with `NoiseError::InvalidDimension` unless the settings are `D` dimensional
+        impl<D: $Bound> TryFrom<untyped::$Name> for $Name<D> {
+            type Error = NoiseError;
+
+            fn try_from(settings: untyped::$Name) -> Result<$Name<D>, NoiseError> {
+                let dim = settings.get_dimensions().dim;
+                if dim != D::DIM {
+                    return Err(NoiseError::InvalidDimension(dim));
+                }
+                Ok($Name::typed(settings))
+            }
+        }
+    };
+}
+
+// The constructors and frequency setter of one dimension, 1d uses the common `with_freq`
+macro_rules! typed_dim {
+    ($Name:ident, $D:ident, $new:ident($($size:ident),*), $offset:ident($($at:ident, $len:ident),*)
+     $(, $with_freq:ident($($freq:ident),*))?) => {
+        impl $Name<$D> {
+            pub fn new($($size: usize),*) -> $Name<$D> {
+                $Name::typed(NoiseBuilder::$new($($size),*))
+            }
+
+            pub fn offset($($at: f32, $len: usize),*) -> $Name<$D> {
+                $Name::typed(NoiseBuilder::$offset($($at, $len),*))
+            }
+
+            $(
+                pub fn $with_freq(&mut self, $($freq: f32),*) -> &mut $Name<$D> {
+                    self.inner.$with_freq($($freq),*);
+                    self
+                }
+            )?
+        }
+    };
+}
+
+macro_rules! typed_dims_1_to_4 {
+    ($Name:ident, $d1:ident, $d1_offset:ident, $d2:ident, $d2_offset:ident, $d3:ident,
+     $d3_offset:ident, $d4:ident, $d4_offset:ident) => {
+        typed_dim!($Name, D1, $d1(width), $d1_offset(x_offset, width));
+        typed_dims_2_and_3!($Name, $d2, $d2_offset, $d3, $d3_offset);
+        typed_dim!(
+            $Name,
+            D4,
+            $d4(width, height, depth, time),
+            $d4_offset(x_offset, width, y_offset, height, z_offset, depth, w_offset, time),
+            with_freq_4d(freq_x, freq_y, freq_z, freq_w)
+        );
+    };
+}
+
+macro_rules! typed_dims_2_and_3 {
+    ($Name:ident, $d2:ident, $d2_offset:ident, $d3:ident, $d3_offset:ident) => {
+        typed_dim!(
+            $Name,
+            D2,
+            $d2(width, height),
+            $d2_offset(x_offset, width, y_offset, height),
+            with_freq_2d(freq_x, freq_y)
+        );
+        typed_dim!(
+            $Name,
+            D3,
+            $d3(width, height, depth),
+            $d3_offset(x_offset, width, y_offset, height, z_offset, depth),
+            with_freq_3d(freq_x, freq_y, freq_z)
+        );
+    };
+}
+
+// Setters of the untyped settings that are valid in every dimension of the bound
+macro_rules! forward_setters {
+    ($Name:ident<$Bound:ident> { $($(#[$attr:meta])* fn $f:ident($($arg:ident: $T:ty),*);)* }) => {
+        impl<D: $Bound> $Name<D> {
+            $(
+                $(#[$attr])*
+                pub fn $f(&mut self, $($arg: $T),*) -> &mut $Name<D> {
+                    self.inner.$f($($arg),*);
+                    self
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! fractal_setters {
+    ($Name:ident) => {
+        forward_setters!($Name<Dim> {
+            fn with_lacunarity(lacunarity: f32);
+            fn with_gain(gain: f32);
+            fn with_octaves(octaves: u8);
+            fn with_octave_rotation(rotation: Transform);
+            fn with_octave_seeding(seeding: OctaveSeeding);
+        });
+    };
+}
+
+typed_settings!(FbmSettings, Dim);
+typed_dims_1_to_4!(
+    FbmSettings,
+    fbm_1d,
+    fbm_1d_offset,
+    fbm_2d,
+    fbm_2d_offset,
+    fbm_3d,
+    fbm_3d_offset,
+    fbm_4d,
+    fbm_4d_offset
+);
+fractal_setters!(FbmSettings);
+
+typed_settings!(RidgeSettings, Dim);
+typed_dims_1_to_4!(
+    RidgeSettings,
+    ridge_1d,
+    ridge_1d_offset,
+    ridge_2d,
+    ridge_2d_offset,
+    ridge_3d,
+    ridge_3d_offset,
+    ridge_4d,
+    ridge_4d_offset
+);
+fractal_setters!(RidgeSettings);
+
+typed_settings!(TurbulenceSettings, Dim);
+typed_dims_1_to_4!(
+    TurbulenceSettings,
+    turbulence_1d,
+    turbulence_1d_offset,
+    turbulence_2d,
+    turbulence_2d_offset,
+    turbulence_3d,
+    turbulence_3d_offset,
+    turbulence_4d,
+    turbulence_4d_offset
+);
+fractal_setters!(TurbulenceSettings);
+
+typed_settings!(GradientSettings, Dim);
+typed_dims_1_to_4!(
+    GradientSettings,
+    gradient_1d,
+    gradient_1d_offset,
+    gradient_2d,
+    gradient_2d_offset,
+    gradient_3d,
+    gradient_3d_offset,
+    gradient_4d,
+    gradient_4d_offset
+);
+
+typed_settings!(WhiteNoiseSettings, Dim);
+typed_dims_1_to_4!(
+    WhiteNoiseSettings,
+    white_noise_1d,
+    white_noise_1d_offset,
+    white_noise_2d,
+    white_noise_2d_offset,
+    white_noise_3d,
+    white_noise_3d_offset,
+    white_noise_4d,
+    white_noise_4d_offset
+);
+
+impl<D: Dim> WhiteNoiseSettings<D> {
+    /// See `WhiteNoiseSettings::generate_hash`
+    pub fn generate_hash(self) -> Vec<i32> {
+        self.inner.generate_hash()
+    }
+}
+
+typed_settings!(CellularSettings, Dim2Or3);
+typed_dims_2_and_3!(
+    CellularSettings,
+    cellular_2d,
+    cellular_2d_offset,
+    cellular_3d,
+    cellular_3d_offset
+);
+forward_setters!(CellularSettings<Dim2Or3> {
+    fn with_distance_function(dist: CellDistanceFunction);
+    fn with_return_type(return_type: CellReturnType);
+    fn with_jitter(jitter: f32);
+});
+
+typed_settings!(Cellular2Settings, Dim2Or3);
+typed_dims_2_and_3!(
+    Cellular2Settings,
+    cellular2_2d,
+    cellular2_2d_offset,
+    cellular2_3d,
+    cellular2_3d_offset
+);
+forward_setters!(Cellular2Settings<Dim2Or3> {
+    fn with_distance_function(dist: CellDistanceFunction);
+    fn with_return_type(return_type: Cell2ReturnType);
+    fn with_jitter(jitter: f32);
+    fn with_index0(i: usize);
+    fn with_index1(i: usize);
+});
+
+typed_settings!(GaborSettings, Dim2Or3);
+typed_dims_2_and_3!(
+    GaborSettings,
+    gabor_2d,
+    gabor_2d_offset,
+    gabor_3d,
+    gabor_3d_offset
+);
+forward_setters!(GaborSettings<Dim2Or3> {
+    fn with_frequency(frequency: f32);
+    fn with_orientation(orientation: f32);
+    fn with_bandwidth(bandwidth: f32);
+    fn with_impulse_density(impulse_density: u8);
+});
+
+impl GaborSettings<D3> {
+    /// Angle of the wave above the xy plane, in radians
+    pub fn with_elevation(&mut self, elevation: f32) -> &mut GaborSettings<D3> {
+        self.inner.with_elevation(elevation);
+        self
+    }
+}
+
+typed_settings!(WaveletSettings, Dim2Or3);
+typed_dims_2_and_3!(
+    WaveletSettings,
+    wavelet_2d,
+    wavelet_2d_offset,
+    wavelet_3d,
+    wavelet_3d_offset
+);
+forward_setters!(WaveletSettings<Dim2Or3> {
+    fn with_gain(gain: f32);
+    fn with_octaves(octaves: u8);
+    fn with_tile_size(tile_size: usize);
+});
+
+impl WaveletSettings<D3> {
+    /// See `WaveletSettings::with_projection`
+    pub fn with_projection(&mut self, normal: [f32; 3]) -> &mut WaveletSettings<D3> {
+        self.inner.with_projection(normal);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoiseBuilder;
+
+    #[test]
+    fn typed_settings_match_untyped() {
+        let (typed, _, _) = FbmSettings::<D3>::offset(4.0, 16, 8.0, 8, 2.0, 4)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_octaves(5)
+            .with_seed(7)
+            .generate();
+        let (expected, _, _) = NoiseBuilder::fbm_3d_offset(4.0, 16, 8.0, 8, 2.0, 4)
+            .with_freq_3d(0.05, 0.04, 0.01)
+            .with_octaves(5)
+            .with_seed(7)
+            .generate();
+        assert_eq!(typed, expected);
+
+        let chunked = CellularSettings::<D2>::new(9, 9)
+            .with_chunk([-1, 3], 8)
+            .with_jitter(0.5)
+            .generate_u8(false);
+        let expected = NoiseBuilder::cellular_2d(9, 9)
+            .with_chunk(&[-1, 3], 8)
+            .with_jitter(0.5)
+            .generate_u8(false);
+        assert_eq!(chunked, expected);
+
+        let untyped = NoiseBuilder::gabor_3d(4, 4, 4);
+        assert!(GaborSettings::<D3>::try_from(untyped).is_ok());
+        assert_eq!(
+            GaborSettings::<D2>::try_from(untyped).err(),
+            Some(NoiseError::InvalidDimension(3))
+        );
+        let round_trip: untyped::GaborSettings = GaborSettings::<D2>::new(4, 4).into();
+        assert_eq!(round_trip.get_dimensions().dim, 2);
+    }
+}
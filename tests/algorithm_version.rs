@@ -0,0 +1,169 @@
+// Golden tests for every algorithm version. The output of a version must never change,
+// so these files are only ever added to, never regenerated.
+
+use simdnoise::{AlgorithmVersion, NoiseBuilder, Settings, SimplexSettings};
+
+mod helpers;
+use helpers::{read_from_file_f32, save_to_file_f32, BIN_PATH};
+
+// Every version is checked with the same settings, named by noise, offset and dimension
+fn generate_cases(
+    version: AlgorithmVersion,
+) -> Vec<(&'static str, &'static str, &'static str, Vec<f32>)> {
+    vec![
+        (
+            "cellular",
+            "nooffset",
+            "2d",
+            NoiseBuilder::cellular_2d(64, 32)
+                .with_freq_2d(0.04, 0.01)
+                .with_seed(1337)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "cellular2",
+            "nooffset",
+            "3d",
+            NoiseBuilder::cellular2_3d(64, 32, 16)
+                .with_freq_3d(0.05, 0.04, 0.01)
+                .with_seed(1337)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "fbm",
+            "nooffset",
+            "2d",
+            NoiseBuilder::fbm_2d(64, 32)
+                .with_freq_2d(0.04, 0.01)
+                .with_seed(1337)
+                .with_octaves(5)
+                .with_gain(2.0)
+                .with_lacunarity(0.5)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "ridge",
+            "offset",
+            "3d",
+            NoiseBuilder::ridge_3d_offset(16.0, 64, 32.0, 32, 64.0, 16)
+                .with_freq_3d(0.05, 0.04, 0.01)
+                .with_seed(1337)
+                .with_octaves(5)
+                .with_gain(2.0)
+                .with_lacunarity(0.5)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "turbulence",
+            "nooffset",
+            "1d",
+            NoiseBuilder::turbulence_1d(64)
+                .with_freq(0.01)
+                .with_seed(1337)
+                .with_octaves(5)
+                .with_gain(2.0)
+                .with_lacunarity(0.5)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "gradient",
+            "offset",
+            "3d",
+            NoiseBuilder::gradient_3d_offset(16.0, 64, 32.0, 32, 64.0, 16)
+                .with_freq_3d(0.05, 0.04, 0.01)
+                .with_seed(1337)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "gradient",
+            "offset",
+            "4d",
+            NoiseBuilder::gradient_4d_offset(16.0, 64, 32.0, 32, 64.0, 16, 128.0, 8)
+                .with_freq_4d(0.10, 0.05, 0.04, 0.01)
+                .with_seed(1337)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+        (
+            "gradient",
+            "fractionaloffset",
+            "4d",
+            NoiseBuilder::gradient_4d_offset(-4.56, 64, 0.12, 32, 1.14, 16, 0.28, 8)
+                .with_freq_4d(0.10, 0.05, 0.04, 0.01)
+                .with_seed(1337)
+                .with_algorithm_version(version)
+                .generate()
+                .0,
+        ),
+    ]
+}
+
+#[test]
+fn test_algorithm_version_v3_1() {
+    for (noise, offset, dim, generated) in generate_cases(AlgorithmVersion::V3_1) {
+        // 3.1 is the version the noisebuilder files were generated with, so it shares them
+        let file_name = format!(
+            "{}/{}_{}_{}_{}_{}.bin",
+            BIN_PATH, "noisebuilder", noise, offset, "32", dim
+        );
+        let expected = read_from_file_f32(&file_name).unwrap();
+        assert!(expected == generated, "{} differs", file_name);
+    }
+}
+
+#[test]
+fn test_algorithm_version_v3_1_deterministic() {
+    for (noise, offset, dim, generated) in generate_cases(AlgorithmVersion::V3_1Deterministic) {
+        // Deterministic noise is bit-identical on every backend and CPU, so these files hold
+        // on every machine
+        let file_name = format!(
+            "{}/{}_{}_{}_{}_{}.bin",
+            BIN_PATH, "algorithm_version_v3_1_deterministic", noise, offset, "32", dim
+        );
+        //save_to_file_f32(&file_name, &generated).unwrap();
+        let expected = read_from_file_f32(&file_name).unwrap();
+        assert_eq!(expected.len(), generated.len());
+        for (a, b) in expected.iter().zip(generated.iter()) {
+            assert!(a.to_bits() == b.to_bits(), "{} differs", file_name);
+        }
+    }
+}
+
+// SSE2 approximates `rsqrt`, which cellular noise uses to place the jittered points, and
+// the deterministic version computes exactly
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_algorithm_version_selects_operations() {
+    use simdnoise::intrinsics::{scalar, sse2};
+
+    let generate = |version: AlgorithmVersion| {
+        let setting = NoiseBuilder::cellular_2d(64, 32)
+            .with_freq_2d(0.04, 0.01)
+            .with_seed(1337)
+            .with_algorithm_version(version)
+            .wrap();
+        unsafe {
+            (
+                scalar::get_2d_noise(&setting).0,
+                sse2::get_2d_noise(&setting).0,
+            )
+        }
+    };
+    let (_, fast_sse2) = generate(AlgorithmVersion::V3_1);
+    let (exact_scalar, exact_sse2) = generate(AlgorithmVersion::V3_1Deterministic);
+    assert_ne!(fast_sse2, exact_sse2);
+    assert_eq!(exact_scalar, exact_sse2);
+}
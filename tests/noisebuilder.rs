@@ -783,6 +783,29 @@ mod noise {
                     assert_eq!(expected, noise);
                 }
             }
+
+            // The x offset sums the same at every vector width, so every backend
+            // accumulates the same coordinates
+            mod fractionaloffset {
+                use super::*;
+
+                #[test]
+                fn test_noisebuilder_gradient_fractionaloffset_f32_4d() {
+                    let file_name = format!(
+                        "{}/{}_{}_{}_{}_{}.bin",
+                        BIN_PATH, "noisebuilder", "gradient", "fractionaloffset", "32", "4d"
+                    );
+                    let (noise, _min, _max) =
+                        NoiseBuilder::gradient_4d_offset(-4.56, 64, 0.12, 32, 1.14, 16, 0.28, 8)
+                            .with_freq_4d(0.10, 0.05, 0.04, 0.01)
+                            .with_seed(1337)
+                            .generate();
+
+                    //save_to_file_f32(&file_name, noise.as_slice()).unwrap();
+                    let expected = read_from_file_f32(&file_name).unwrap();
+                    assert_eq!(expected, noise);
+                }
+            }
         }
     }
 }
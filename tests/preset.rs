@@ -0,0 +1,62 @@
+// Compares with the golden files, see noisebuilder.rs
+#![cfg(feature = "serde")]
+
+use simdnoise::{NoiseBuilder, NoisePreset, Settings, SimplexSettings};
+
+mod helpers;
+use helpers::{read_from_file_f32, BIN_PATH};
+
+fn round_trip<T: Settings>(settings: T) -> NoisePreset {
+    let json = serde_json::to_string(&NoisePreset::new(settings.wrap())).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+fn expected(noise: &str, offset: &str, dim: &str) -> Vec<f32> {
+    let file_name = format!(
+        "{}/{}_{}_{}_{}_{}.bin",
+        BIN_PATH, "noisebuilder", noise, offset, "32", dim
+    );
+    read_from_file_f32(&file_name).unwrap()
+}
+
+#[test]
+fn test_preset_cellular_offset_f32_3d() {
+    let settings = *NoiseBuilder::cellular_3d_offset(16.0, 64, 32.0, 32, 64.0, 16)
+        .with_freq_3d(0.05, 0.04, 0.01)
+        .with_seed(1337);
+    let (noise, _min, _max) = round_trip(settings).generate();
+    assert_eq!(expected("cellular", "offset", "3d"), noise);
+}
+
+#[test]
+fn test_preset_cellular2_nooffset_f32_2d() {
+    let settings = *NoiseBuilder::cellular2_2d(64, 32)
+        .with_freq_2d(0.04, 0.01)
+        .with_seed(1337);
+    let (noise, _min, _max) = round_trip(settings).generate();
+    assert_eq!(expected("cellular2", "nooffset", "2d"), noise);
+}
+
+#[test]
+fn test_preset_fbm_offset_f32_3d() {
+    let settings = *NoiseBuilder::fbm_3d_offset(16.0, 64, 32.0, 32, 64.0, 16)
+        .with_freq_3d(0.05, 0.04, 0.01)
+        .with_seed(1337)
+        .with_octaves(5)
+        .with_gain(2.0)
+        .with_lacunarity(0.5);
+    let (noise, _min, _max) = round_trip(settings).generate();
+    assert_eq!(expected("fbm", "offset", "3d"), noise);
+}
+
+#[test]
+fn test_preset_turbulence_offset_f32_4d() {
+    let settings = *NoiseBuilder::turbulence_4d_offset(16.0, 64, 32.0, 32, 64.0, 16, 128.0, 8)
+        .with_freq_4d(0.10, 0.05, 0.04, 0.01)
+        .with_seed(1337)
+        .with_octaves(5)
+        .with_gain(2.0)
+        .with_lacunarity(0.5);
+    let (noise, _min, _max) = round_trip(settings).generate();
+    assert_eq!(expected("turbulence", "offset", "4d"), noise);
+}